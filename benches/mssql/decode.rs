@@ -0,0 +1,49 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use sqlx::mssql::{Mssql, MssqlRow};
+use sqlx::Row;
+use sqlx_test::new;
+
+const ROWS: usize = 100_000;
+
+async fn fetch_int_rows() -> Vec<MssqlRow> {
+    let mut conn = new::<Mssql>().await.unwrap();
+
+    sqlx::query(
+        "SELECT TOP (@p1) CAST(ROW_NUMBER() OVER (ORDER BY (SELECT NULL)) AS INT) AS n \
+         FROM sys.all_objects a CROSS JOIN sys.all_objects b",
+    )
+    .bind(i64::try_from(ROWS).unwrap())
+    .fetch_all(&mut conn)
+    .await
+    .unwrap()
+}
+
+fn decode_int(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let rows = runtime.block_on(fetch_int_rows());
+
+    // INT into i32 takes the exact-match arm; INT into i64 takes a widening arm.
+    c.bench_with_input(
+        BenchmarkId::new("decode", "int_to_i32"),
+        &rows,
+        |b, rows| {
+            b.iter(|| {
+                rows.iter()
+                    .map(|row| row.get::<i32, _>(0))
+                    .fold(0i64, |acc, n| acc + i64::from(n))
+            });
+        },
+    );
+
+    c.bench_with_input(
+        BenchmarkId::new("decode", "int_to_i64"),
+        &rows,
+        |b, rows| {
+            b.iter(|| rows.iter().map(|row| row.get::<i64, _>(0)).sum::<i64>());
+        },
+    );
+}
+
+criterion_group!(benches, decode_int);
+criterion_main!(benches);
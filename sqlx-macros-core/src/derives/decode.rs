@@ -179,6 +179,32 @@ fn expand_derive_decode_strong_enum(
 
     let mut tts = TokenStream::new();
 
+    if cfg!(feature = "mssql") {
+        tts.extend(quote!(
+            #[automatically_derived]
+            impl<'r> ::sqlx::decode::Decode<'r, ::sqlx::mssql::Mssql> for #ident {
+                fn decode(
+                    value: ::sqlx::mssql::MssqlValueRef<'r>,
+                ) -> ::std::result::Result<
+                    Self,
+                    ::std::boxed::Box<
+                        dyn ::std::error::Error
+                            + 'static
+                            + ::std::marker::Send
+                            + ::std::marker::Sync,
+                    >,
+                > {
+                    let value = <&'r ::std::primitive::str as ::sqlx::decode::Decode<
+                        'r,
+                        ::sqlx::mssql::Mssql,
+                    >>::decode(value)?;
+
+                    #values
+                }
+            }
+        ));
+    }
+
     if cfg!(feature = "mysql") {
         tts.extend(quote!(
             #[automatically_derived]
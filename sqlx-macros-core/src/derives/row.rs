@@ -196,6 +196,29 @@ fn expand_derive_from_row_struct(
                 },
             };
 
+            // Name the struct field in column decode errors so schema/struct mismatches are
+            // easy to track down. Flattened fields already carry the context of their own
+            // fields.
+            let expr: Expr = if attributes.flatten {
+                expr
+            } else {
+                let field_s = format!("{}::{}", ident, id.to_string().trim_start_matches("r#"));
+
+                parse_quote!(
+                    #expr.map_err(|e| match e {
+                        ::sqlx::Error::ColumnDecode { index, source } => {
+                            ::sqlx::Error::ColumnDecode {
+                                index,
+                                source: ::std::boxed::Box::new(
+                                    ::sqlx::spec_error::FieldDecodeError::new(#field_s, source),
+                                ),
+                            }
+                        },
+                        e => e
+                    })
+                )
+            };
+
             if attributes.default {
                 Some(parse_quote!(
                     let #id: #ty = #expr.or_else(|e| match e {
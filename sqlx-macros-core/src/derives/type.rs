@@ -186,6 +186,21 @@ fn expand_derive_has_sql_type_strong_enum(
     let ident = &input.ident;
     let mut tts = TokenStream::new();
 
+    if cfg!(feature = "mssql") {
+        tts.extend(quote!(
+            #[automatically_derived]
+            impl ::sqlx::Type<::sqlx::Mssql> for #ident {
+                fn type_info() -> ::sqlx::mssql::MssqlTypeInfo {
+                    <::std::primitive::str as ::sqlx::Type<::sqlx::Mssql>>::type_info()
+                }
+
+                fn compatible(ty: &::sqlx::mssql::MssqlTypeInfo) -> ::std::primitive::bool {
+                    <&::std::primitive::str as ::sqlx::types::Type<::sqlx::Mssql>>::compatible(ty)
+                }
+            }
+        ));
+    }
+
     if cfg!(feature = "mysql") {
         tts.extend(quote!(
             #[automatically_derived]
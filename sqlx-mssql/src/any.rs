@@ -90,21 +90,12 @@ impl AnyConnectionBackend for MssqlConnection {
             }
         };
 
-        Box::pin(
-            stream::once(async move {
-                let results = self.run(query.as_str(), arguments).await?;
-                Ok::<_, sqlx_core::Error>(results)
+        Box::pin(self.run_stream(query, arguments).map(|res| {
+            Ok(match res? {
+                Either::Left(result) => Either::Left(map_result(result)),
+                Either::Right(row) => Either::Right(AnyRow::try_from(&row)?),
             })
-            .map_ok(|results| {
-                futures_util::stream::iter(results.into_iter().map(|res| {
-                    Ok(match res {
-                        Either::Left(result) => Either::Left(map_result(result)),
-                        Either::Right(row) => Either::Right(AnyRow::try_from(&row)?),
-                    })
-                }))
-            })
-            .try_flatten(),
-        )
+        }))
     }
 
     fn fetch_optional(
@@ -121,15 +112,19 @@ impl AnyConnectionBackend for MssqlConnection {
 
         Box::pin(async move {
             let arguments = arguments?;
-            let results = self.run(query.as_str(), arguments).await?;
+            let mut results = self.run_stream(query, arguments);
+            let mut first = None;
 
-            for result in results {
+            // Read to the end, as `Executor::fetch_optional` does.
+            while let Some(result) = results.try_next().await? {
                 if let Either::Right(row) = result {
-                    return Ok(Some(AnyRow::try_from(&row)?));
+                    if first.is_none() {
+                        first = Some(AnyRow::try_from(&row)?);
+                    }
                 }
             }
 
-            Ok(None)
+            Ok(first)
         })
     }
 
@@ -227,6 +222,6 @@ impl<'a> TryFrom<&'a AnyConnectOptions> for MssqlConnectOptions {
 fn map_result(result: MssqlQueryResult) -> AnyQueryResult {
     AnyQueryResult {
         rows_affected: result.rows_affected,
-        last_insert_id: None,
+        last_insert_id: result.last_insert_id,
     }
 }
@@ -2,6 +2,7 @@ use std::fmt::{self, Write};
 
 use crate::database::MssqlArgumentValue;
 use crate::encode::Encode;
+use crate::error::Error;
 use crate::types::Type;
 use crate::Mssql;
 pub(crate) use sqlx_core::arguments::*;
@@ -13,7 +14,24 @@ pub struct MssqlArguments {
     pub(crate) values: Vec<MssqlArgumentValue>,
 }
 
+/// The most parameters a query can bind: SQL Server accepts 2100 per request, and
+/// `sp_executesql` takes two of them for the statement and its parameter declarations.
+pub(crate) const MAX_PARAMETERS: usize = 2098;
+
 impl MssqlArguments {
+    /// Check that the arguments fit in a single request, so an oversized query fails with a
+    /// clear error instead of being rejected by the server.
+    pub(crate) fn check_parameter_limit(&self) -> Result<(), Error> {
+        if self.values.len() > MAX_PARAMETERS {
+            return Err(Error::InvalidArgument(format!(
+                "query binds {} parameters, but SQL Server allows at most {MAX_PARAMETERS}",
+                self.values.len()
+            )));
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn add<'q, T>(&mut self, value: T) -> Result<(), BoxDynError>
     where
         T: Encode<'q, Mssql> + Type<Mssql>,
@@ -57,3 +75,22 @@ impl Arguments for MssqlArguments {
         write!(writer, "@p{}", self.values.len())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_limits_the_number_of_parameters() {
+        let mut args = MssqlArguments::default();
+        for i in 0..2098 {
+            args.add(i).unwrap();
+        }
+        assert!(args.check_parameter_limit().is_ok());
+
+        args.add(0).unwrap();
+        let err = args.check_parameter_limit().unwrap_err();
+        assert!(matches!(err, Error::InvalidArgument(_)));
+        assert!(err.to_string().contains("2099 parameters"), "{err}");
+    }
+}
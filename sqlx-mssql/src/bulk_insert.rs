@@ -1,5 +1,15 @@
+use std::borrow::Cow;
+
+use futures_util::TryStreamExt;
+use tiberius::numeric::Numeric;
+use tiberius::time::{Date, DateTime2, DateTimeOffset, SmallDateTime, Time};
+use tiberius::xml::XmlData;
+use tiberius::{ColumnData, QueryItem, QueryStream, Row, Uuid};
+
 use crate::error::{tiberius_err, Error};
 use crate::io::SocketAdapter;
+use crate::query_as::query_as;
+use crate::MssqlConnection;
 use sqlx_core::net::Socket;
 
 /// A bulk insert operation for high-performance data loading into SQL Server.
@@ -21,15 +31,27 @@ use sqlx_core::net::Socket;
 /// # Ok(())
 /// # }
 /// ```
+///
+/// Rows are sent as soon as a packet's worth has been buffered, so the server is in the
+/// middle of the `INSERT BULK` until it is ended with [`finalize`](Self::finalize) or
+/// [`abort`](Self::abort). Dropping it before that leaves the connection unusable.
 pub struct MssqlBulkInsert<'c> {
     inner: tiberius::BulkLoadRequest<'c, SocketAdapter<Box<dyn Socket>>>,
+    /// Set by [`abort`](Self::abort) to roll back to [`BULK_INSERT_SAVEPOINT`] before the
+    /// next request; `None` outside a transaction, where there is no savepoint.
+    rollback: Option<&'c mut bool>,
 }
 
+/// The savepoint taken before a bulk insert inside a transaction, so that
+/// [`MssqlBulkInsert::abort`] can discard its rows.
+pub(crate) const BULK_INSERT_SAVEPOINT: &str = "_sqlx_bulk_insert";
+
 impl<'c> MssqlBulkInsert<'c> {
     pub(crate) fn new(
         inner: tiberius::BulkLoadRequest<'c, SocketAdapter<Box<dyn Socket>>>,
+        rollback: Option<&'c mut bool>,
     ) -> Self {
-        Self { inner }
+        Self { inner, rollback }
     }
 
     /// Send a single row to the bulk insert operation.
@@ -48,4 +70,609 @@ impl<'c> MssqlBulkInsert<'c> {
         let result = self.inner.finalize().await.map_err(tiberius_err)?;
         Ok(result.total())
     }
+
+    /// Abandon the bulk insert, leaving the connection ready for the next query.
+    ///
+    /// The TDS protocol has no way to end an `INSERT BULK` without completing it, and
+    /// tiberius cannot send the attention signal that would cancel it, so the load is ended
+    /// like [`finalize`](Self::finalize) does and its rows are then discarded:
+    ///
+    /// * Inside a transaction, [`MssqlConnection::bulk_insert`] took a savepoint before the
+    ///   load, and the connection rolls back to it before its next query. None of the rows
+    ///   are kept, and the transaction itself stays open.
+    /// * Outside a transaction there is nothing to roll back to: every row passed to
+    ///   [`send`](Self::send) is inserted, including those still buffered. Begin a
+    ///   transaction before a load you may need to abort.
+    pub async fn abort(self) -> Result<(), Error> {
+        self.inner.finalize().await.map_err(tiberius_err)?;
+
+        if let Some(rollback) = self.rollback {
+            *rollback = true;
+        }
+
+        Ok(())
+    }
+}
+
+/// Days from `0001-01-01` (the epoch of `DATE`/`DATETIME2`) to `1900-01-01` (the epoch of
+/// `DATETIME`/`SMALLDATETIME`).
+const DAYS_BEFORE_1900: i64 = 693_595;
+
+/// 100ns ticks in a day.
+const TICKS_PER_DAY: u64 = 864_000_000_000;
+
+/// A destination column of [`MssqlConnection::bulk_copy`].
+///
+/// Only the columns `INSERT BULK` expects a value for are described: identity, computed and
+/// `rowversion` columns are filled in by the server.
+#[derive(Debug)]
+pub(crate) struct BulkCopyColumn {
+    name: String,
+    type_name: String,
+    ty: BulkCopyType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BulkCopyType {
+    Bit,
+    TinyInt,
+    SmallInt,
+    Int,
+    BigInt,
+    Real,
+    Float,
+    Decimal { scale: u8 },
+    Text,
+    Binary,
+    Guid,
+    Xml,
+    Date,
+    Time { scale: u8 },
+    DateTime2,
+    DateTimeOffset { scale: u8 },
+    DateTime,
+    SmallDateTime,
+}
+
+impl BulkCopyColumn {
+    /// The query describing the columns of `SELECT * FROM <table>`, in the order
+    /// `INSERT BULK` expects them.
+    const QUERY: &'static str = "SELECT name, system_type_name \
+         FROM sys.dm_exec_describe_first_result_set(@p1, NULL, 0) \
+         WHERE is_hidden = 0 AND is_updateable = 1 \
+         ORDER BY column_ordinal";
+
+    /// Describe the columns `INSERT BULK` into `table` expects.
+    pub(crate) async fn describe(
+        conn: &mut MssqlConnection,
+        table: &str,
+    ) -> Result<Vec<Self>, Error> {
+        let columns: Vec<(String, String)> = query_as(Self::QUERY)
+            .bind(format!("SELECT * FROM {table}"))
+            .fetch_all(conn)
+            .await?;
+
+        columns
+            .into_iter()
+            .map(|(name, type_name)| Self::new(name, type_name))
+            .collect()
+    }
+
+    fn new(name: String, type_name: String) -> Result<Self, Error> {
+        let ty = BulkCopyType::parse(&type_name).ok_or_else(|| {
+            Error::InvalidArgument(format!(
+                "bulk copy does not support column {name} of type {type_name}"
+            ))
+        })?;
+
+        Ok(Self {
+            name,
+            type_name,
+            ty,
+        })
+    }
+
+    /// Convert a value read from the source query into the data `INSERT BULK` expects for
+    /// this column.
+    pub(crate) fn convert(&self, value: ColumnData<'static>) -> Result<ColumnData<'static>, Error> {
+        if is_null(&value) {
+            return Ok(self.ty.null());
+        }
+
+        let source = source_type_name(&value);
+
+        self.ty.convert(value).map_err(|reason| {
+            Error::Encode(
+                format!(
+                    "cannot copy {source} into column {} of type {}: {reason}",
+                    self.name, self.type_name
+                )
+                .into(),
+            )
+        })
+    }
+}
+
+impl BulkCopyType {
+    /// Parse a `system_type_name` such as `int`, `nvarchar(50)` or `decimal(10,2)`.
+    fn parse(type_name: &str) -> Option<Self> {
+        let type_name = type_name.to_ascii_lowercase();
+        let (base, args) = match type_name.split_once('(') {
+            Some((base, args)) => (base, args.trim_end_matches(')')),
+            None => (type_name.as_str(), ""),
+        };
+
+        // The scale is the last argument: `decimal(10,2)`, `time(7)`.
+        let scale = |default| {
+            args.rsplit(',')
+                .next()
+                .and_then(|scale| scale.trim().parse().ok())
+                .unwrap_or(default)
+        };
+
+        Some(match base.trim() {
+            "bit" => Self::Bit,
+            "tinyint" => Self::TinyInt,
+            "smallint" => Self::SmallInt,
+            "int" => Self::Int,
+            "bigint" => Self::BigInt,
+            "real" => Self::Real,
+            "float" => Self::Float,
+            "decimal" | "numeric" => Self::Decimal { scale: scale(0) },
+            "char" | "varchar" | "nchar" | "nvarchar" => Self::Text,
+            "binary" | "varbinary" => Self::Binary,
+            "uniqueidentifier" => Self::Guid,
+            "xml" => Self::Xml,
+            "date" => Self::Date,
+            "time" => Self::Time { scale: scale(7) },
+            "datetime2" => Self::DateTime2,
+            "datetimeoffset" => Self::DateTimeOffset { scale: scale(7) },
+            "datetime" => Self::DateTime,
+            "smalldatetime" => Self::SmallDateTime,
+            _ => return None,
+        })
+    }
+
+    fn null(self) -> ColumnData<'static> {
+        match self {
+            Self::Bit => ColumnData::Bit(None),
+            Self::TinyInt => ColumnData::U8(None),
+            Self::SmallInt => ColumnData::I16(None),
+            Self::Int => ColumnData::I32(None),
+            Self::BigInt => ColumnData::I64(None),
+            Self::Real => ColumnData::F32(None),
+            Self::Float => ColumnData::F64(None),
+            Self::Decimal { .. } => ColumnData::Numeric(None),
+            Self::Text => ColumnData::String(None),
+            Self::Binary => ColumnData::Binary(None),
+            Self::Guid => ColumnData::Guid(None),
+            Self::Xml => ColumnData::Xml(None),
+            Self::Date => ColumnData::Date(None),
+            Self::Time { .. } => ColumnData::Time(None),
+            Self::DateTime2 => ColumnData::DateTime2(None),
+            Self::DateTimeOffset { .. } => ColumnData::DateTimeOffset(None),
+            Self::DateTime => ColumnData::DateTime(None),
+            Self::SmallDateTime => ColumnData::SmallDateTime(None),
+        }
+    }
+
+    /// Convert a non-null value, following SQL Server's implicit conversions where they are
+    /// lossless or round predictably.
+    fn convert(self, value: ColumnData<'static>) -> Result<ColumnData<'static>, String> {
+        const MISMATCH: &str = "incompatible types";
+
+        let out_of_range = || "value out of range".to_owned();
+
+        Ok(match self {
+            Self::Bit => ColumnData::Bit(Some(integer(&value).ok_or(MISMATCH)? != 0)),
+            Self::TinyInt => ColumnData::U8(Some(
+                integer(&value)
+                    .ok_or(MISMATCH)?
+                    .try_into()
+                    .map_err(|_| out_of_range())?,
+            )),
+            Self::SmallInt => ColumnData::I16(Some(
+                integer(&value)
+                    .ok_or(MISMATCH)?
+                    .try_into()
+                    .map_err(|_| out_of_range())?,
+            )),
+            Self::Int => ColumnData::I32(Some(
+                integer(&value)
+                    .ok_or(MISMATCH)?
+                    .try_into()
+                    .map_err(|_| out_of_range())?,
+            )),
+            Self::BigInt => ColumnData::I64(Some(integer(&value).ok_or(MISMATCH)?)),
+            // Rounded to the nearest `REAL`, as in SQL Server.
+            #[allow(clippy::cast_possible_truncation)]
+            Self::Real => ColumnData::F32(Some(float(&value).ok_or(MISMATCH)? as f32)),
+            Self::Float => ColumnData::F64(Some(float(&value).ok_or(MISMATCH)?)),
+            Self::Decimal { scale } => {
+                let (mantissa, from) = numeric(&value).ok_or(MISMATCH)?;
+                let mantissa = rescale(mantissa, from, scale).ok_or_else(out_of_range)?;
+                ColumnData::Numeric(Some(Numeric::new_with_scale(mantissa, scale)))
+            }
+            Self::Text => ColumnData::String(Some(Cow::Owned(text(value).ok_or(MISMATCH)?))),
+            Self::Binary => match value {
+                ColumnData::Binary(Some(bytes)) => ColumnData::Binary(Some(bytes)),
+                _ => return Err(MISMATCH.into()),
+            },
+            Self::Guid => match value {
+                ColumnData::Guid(Some(guid)) => ColumnData::Guid(Some(guid)),
+                ColumnData::String(Some(s)) => {
+                    ColumnData::Guid(Some(Uuid::parse_str(s.trim()).map_err(|e| e.to_string())?))
+                }
+                _ => return Err(MISMATCH.into()),
+            },
+            Self::Xml => match value {
+                ColumnData::Xml(Some(xml)) => ColumnData::Xml(Some(xml)),
+                ColumnData::String(Some(s)) => {
+                    ColumnData::Xml(Some(Cow::Owned(XmlData::new(s.into_owned()))))
+                }
+                _ => return Err(MISMATCH.into()),
+            },
+            Self::Date => match date_time_parts(&value) {
+                Some((Some(days), _)) => ColumnData::Date(Some(Date::new(days))),
+                _ => return Err(MISMATCH.into()),
+            },
+            Self::Time { scale } => match date_time_parts(&value) {
+                Some((_, Some(ticks))) => ColumnData::Time(Some(time(ticks, scale))),
+                _ => return Err(MISMATCH.into()),
+            },
+            Self::DateTime2 => match date_time_parts(&value) {
+                // The time is rescaled to the column by tiberius.
+                Some((Some(days), ticks)) => ColumnData::DateTime2(Some(DateTime2::new(
+                    Date::new(days),
+                    Time::new(ticks.unwrap_or(0), 7),
+                ))),
+                _ => return Err(MISMATCH.into()),
+            },
+            Self::DateTimeOffset { scale } => {
+                let (days, ticks, offset) = match value {
+                    ColumnData::DateTimeOffset(Some(dto)) => {
+                        let dt2 = dto.datetime2();
+                        (dt2.date().days(), ticks(dt2.time()), dto.offset())
+                    }
+                    // A value without an offset is taken to be in UTC, as in SQL Server.
+                    _ => match date_time_parts(&value) {
+                        Some((Some(days), ticks)) => (days, ticks.unwrap_or(0), 0),
+                        _ => return Err(MISMATCH.into()),
+                    },
+                };
+
+                ColumnData::DateTimeOffset(Some(DateTimeOffset::new(
+                    DateTime2::new(Date::new(days), time(ticks, scale)),
+                    offset,
+                )))
+            }
+            Self::DateTime => {
+                let Some((Some(days), ticks)) = date_time_parts(&value) else {
+                    return Err(MISMATCH.into());
+                };
+
+                // Rounded to the nearest 1/300 of a second.
+                let mut days = i64::from(days) - DAYS_BEFORE_1900;
+                let mut fragments = (ticks.unwrap_or(0) * 3 + 50_000) / 100_000;
+                if fragments == TICKS_PER_DAY * 3 / 100_000 {
+                    days += 1;
+                    fragments = 0;
+                }
+
+                ColumnData::DateTime(Some(tiberius::time::DateTime::new(
+                    days.try_into().map_err(|_| out_of_range())?,
+                    fragments.try_into().map_err(|_| out_of_range())?,
+                )))
+            }
+            Self::SmallDateTime => {
+                let Some((Some(days), ticks)) = date_time_parts(&value) else {
+                    return Err(MISMATCH.into());
+                };
+
+                // Rounded to the nearest minute.
+                let mut days = i64::from(days) - DAYS_BEFORE_1900;
+                let mut minutes = (ticks.unwrap_or(0) + 300_000_000) / 600_000_000;
+                if minutes == 24 * 60 {
+                    days += 1;
+                    minutes = 0;
+                }
+
+                ColumnData::SmallDateTime(Some(SmallDateTime::new(
+                    days.try_into().map_err(|_| out_of_range())?,
+                    minutes.try_into().map_err(|_| out_of_range())?,
+                )))
+            }
+        })
+    }
+}
+
+/// Read the next row of the first result set of a [`MssqlConnection::bulk_copy`] query,
+/// checking the number of columns against the `expected` destination columns.
+pub(crate) async fn next_bulk_copy_row(
+    stream: &mut QueryStream<'_>,
+    expected: usize,
+    table: &str,
+) -> Result<Option<Row>, Error> {
+    while let Some(item) = stream.try_next().await.map_err(tiberius_err)? {
+        match item {
+            QueryItem::Row(row) => return Ok(Some(row)),
+            QueryItem::Metadata(metadata) if metadata.result_index() > 0 => break,
+            QueryItem::Metadata(metadata) if metadata.columns().len() != expected => {
+                return Err(Error::InvalidArgument(format!(
+                    "query returns {} columns, but {table} expects {expected}",
+                    metadata.columns().len()
+                )));
+            }
+            QueryItem::Metadata(_) => {}
+        }
+    }
+
+    Ok(None)
+}
+
+fn is_null(value: &ColumnData<'_>) -> bool {
+    matches!(
+        value,
+        ColumnData::Bit(None)
+            | ColumnData::U8(None)
+            | ColumnData::I16(None)
+            | ColumnData::I32(None)
+            | ColumnData::I64(None)
+            | ColumnData::F32(None)
+            | ColumnData::F64(None)
+            | ColumnData::Numeric(None)
+            | ColumnData::String(None)
+            | ColumnData::Binary(None)
+            | ColumnData::Guid(None)
+            | ColumnData::Xml(None)
+            | ColumnData::Date(None)
+            | ColumnData::Time(None)
+            | ColumnData::DateTime2(None)
+            | ColumnData::DateTimeOffset(None)
+            | ColumnData::DateTime(None)
+            | ColumnData::SmallDateTime(None)
+    )
+}
+
+fn source_type_name(value: &ColumnData<'_>) -> &'static str {
+    match value {
+        ColumnData::Bit(_) => "BIT",
+        ColumnData::U8(_) => "TINYINT",
+        ColumnData::I16(_) => "SMALLINT",
+        ColumnData::I32(_) => "INT",
+        ColumnData::I64(_) => "BIGINT",
+        ColumnData::F32(_) => "REAL",
+        ColumnData::F64(_) => "FLOAT",
+        ColumnData::Numeric(_) => "DECIMAL",
+        ColumnData::String(_) => "NVARCHAR",
+        ColumnData::Binary(_) => "VARBINARY",
+        ColumnData::Guid(_) => "UNIQUEIDENTIFIER",
+        ColumnData::Xml(_) => "XML",
+        ColumnData::Date(_) => "DATE",
+        ColumnData::Time(_) => "TIME",
+        ColumnData::DateTime2(_) => "DATETIME2",
+        ColumnData::DateTimeOffset(_) => "DATETIMEOFFSET",
+        ColumnData::DateTime(_) => "DATETIME",
+        ColumnData::SmallDateTime(_) => "SMALLDATETIME",
+    }
+}
+
+fn integer(value: &ColumnData<'_>) -> Option<i64> {
+    match *value {
+        ColumnData::Bit(Some(v)) => Some(v.into()),
+        ColumnData::U8(Some(v)) => Some(v.into()),
+        ColumnData::I16(Some(v)) => Some(v.into()),
+        ColumnData::I32(Some(v)) => Some(v.into()),
+        ColumnData::I64(Some(v)) => Some(v),
+        _ => None,
+    }
+}
+
+fn float(value: &ColumnData<'_>) -> Option<f64> {
+    match *value {
+        ColumnData::F32(Some(v)) => Some(v.into()),
+        ColumnData::F64(Some(v)) => Some(v),
+        ColumnData::Numeric(Some(v)) => Some(v.value() as f64 / 10f64.powi(v.scale().into())),
+        _ => integer(value).map(|v| v as f64),
+    }
+}
+
+/// The mantissa and scale of an exact numeric value.
+fn numeric(value: &ColumnData<'_>) -> Option<(i128, u8)> {
+    match *value {
+        ColumnData::Numeric(Some(v)) => Some((v.value(), v.scale())),
+        _ => integer(value).map(|v| (v.into(), 0)),
+    }
+}
+
+/// Rescale a decimal mantissa, rounding half away from zero like SQL Server.
+fn rescale(mantissa: i128, from: u8, to: u8) -> Option<i128> {
+    if to >= from {
+        return mantissa.checked_mul(10i128.checked_pow(u32::from(to - from))?);
+    }
+
+    let divisor = 10i128.checked_pow(u32::from(from - to))?;
+    let (quotient, remainder) = (mantissa / divisor, mantissa % divisor);
+
+    Some(if remainder.unsigned_abs() * 2 >= divisor.unsigned_abs() {
+        quotient + mantissa.signum()
+    } else {
+        quotient
+    })
+}
+
+/// Format a value the way `CAST(... AS NVARCHAR)` would.
+fn text(value: ColumnData<'static>) -> Option<String> {
+    Some(match value {
+        ColumnData::String(Some(s)) => s.into_owned(),
+        ColumnData::Xml(Some(xml)) => xml.into_owned().into_string(),
+        ColumnData::Guid(Some(guid)) => guid.to_string().to_uppercase(),
+        ColumnData::F32(Some(v)) => v.to_string(),
+        ColumnData::F64(Some(v)) => v.to_string(),
+        ColumnData::Numeric(Some(v)) => {
+            let scale = usize::from(v.scale());
+            let digits = v.value().unsigned_abs().to_string();
+            let digits = format!("{digits:0>width$}", width = scale + 1);
+            let (int, frac) = digits.split_at(digits.len() - scale);
+            let sign = if v.value() < 0 { "-" } else { "" };
+
+            if frac.is_empty() {
+                format!("{sign}{int}")
+            } else {
+                format!("{sign}{int}.{frac}")
+            }
+        }
+        _ => integer(&value)?.to_string(),
+    })
+}
+
+/// 100ns ticks since midnight of a `TIME` value.
+fn ticks(time: Time) -> u64 {
+    time.increments() * 10u64.pow(7 - u32::from(time.scale()))
+}
+
+/// A `TIME` with the given scale from 100ns ticks since midnight, truncating extra digits.
+fn time(ticks: u64, scale: u8) -> Time {
+    Time::new(ticks / 10u64.pow(7 - u32::from(scale)), scale)
+}
+
+/// Split a date/time value into days since `0001-01-01` and 100ns ticks since midnight.
+///
+/// The date is `None` for `TIME` values and the time is `None` for `DATE` values.
+fn date_time_parts(value: &ColumnData<'_>) -> Option<(Option<u32>, Option<u64>)> {
+    Some(match *value {
+        ColumnData::Date(Some(date)) => (Some(date.days()), None),
+        ColumnData::Time(Some(t)) => (None, Some(ticks(t))),
+        ColumnData::DateTime2(Some(dt2)) => (Some(dt2.date().days()), Some(ticks(dt2.time()))),
+        ColumnData::DateTime(Some(dt)) => (
+            u32::try_from(i64::from(dt.days()) + DAYS_BEFORE_1900).ok(),
+            Some((u64::from(dt.seconds_fragments()) * 100_000 + 1) / 3),
+        ),
+        ColumnData::SmallDateTime(Some(dt)) => (
+            u32::try_from(i64::from(dt.days()) + DAYS_BEFORE_1900).ok(),
+            Some(u64::from(dt.seconds_fragments()) * 600_000_000),
+        ),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(type_name: &str) -> BulkCopyColumn {
+        BulkCopyColumn::new("col".to_owned(), type_name.to_owned()).unwrap()
+    }
+
+    #[test]
+    fn it_parses_destination_types() {
+        assert_eq!(column("int").ty, BulkCopyType::Int);
+        assert_eq!(column("nvarchar(max)").ty, BulkCopyType::Text);
+        assert_eq!(
+            column("DECIMAL(10,2)").ty,
+            BulkCopyType::Decimal { scale: 2 }
+        );
+        assert_eq!(column("time(3)").ty, BulkCopyType::Time { scale: 3 });
+        assert_eq!(
+            column("datetimeoffset(7)").ty,
+            BulkCopyType::DateTimeOffset { scale: 7 }
+        );
+
+        for unsupported in ["money", "sql_variant", "ntext", "geography"] {
+            let err = BulkCopyColumn::new("col".to_owned(), unsupported.to_owned()).unwrap_err();
+            assert!(matches!(err, Error::InvalidArgument(_)), "{err}");
+        }
+    }
+
+    #[test]
+    fn it_converts_integers_with_range_checks() {
+        let value = column("tinyint")
+            .convert(ColumnData::I32(Some(200)))
+            .unwrap();
+        assert!(matches!(value, ColumnData::U8(Some(200))));
+
+        let value = column("bigint").convert(ColumnData::I16(Some(-5))).unwrap();
+        assert!(matches!(value, ColumnData::I64(Some(-5))));
+
+        let err = column("smallint")
+            .convert(ColumnData::I64(Some(100_000)))
+            .unwrap_err();
+        assert!(matches!(err, Error::Encode(_)), "{err}");
+
+        let err = column("int")
+            .convert(ColumnData::String(Some("1".into())))
+            .unwrap_err();
+        assert!(err.to_string().contains("NVARCHAR"), "{err}");
+    }
+
+    #[test]
+    fn it_keeps_nulls_typed_for_the_destination() {
+        let value = column("decimal(10,2)")
+            .convert(ColumnData::I32(None))
+            .unwrap();
+        assert!(matches!(value, ColumnData::Numeric(None)));
+    }
+
+    #[test]
+    fn it_rescales_decimals_half_away_from_zero() {
+        assert_eq!(rescale(1005, 3, 2), Some(101));
+        assert_eq!(rescale(-1005, 3, 2), Some(-101));
+        assert_eq!(rescale(1004, 3, 2), Some(100));
+        assert_eq!(rescale(25, 1, 3), Some(2500));
+        assert_eq!(rescale(i128::MAX, 0, 1), None);
+
+        let value = column("decimal(10,2)")
+            .convert(ColumnData::I32(Some(7)))
+            .unwrap();
+        let ColumnData::Numeric(Some(numeric)) = value else {
+            panic!("expected a numeric, got {value:?}");
+        };
+        assert_eq!((numeric.value(), numeric.scale()), (700, 2));
+    }
+
+    #[test]
+    fn it_formats_values_as_text() {
+        let numeric = |value, scale| {
+            text(ColumnData::Numeric(Some(Numeric::new_with_scale(
+                value, scale,
+            ))))
+        };
+
+        assert_eq!(numeric(12345, 2).as_deref(), Some("123.45"));
+        assert_eq!(numeric(-5, 3).as_deref(), Some("-0.005"));
+        assert_eq!(numeric(42, 0).as_deref(), Some("42"));
+        assert_eq!(text(ColumnData::Bit(Some(true))).as_deref(), Some("1"));
+        assert_eq!(text(ColumnData::Binary(Some(vec![1].into()))), None);
+    }
+
+    #[test]
+    fn it_rounds_datetime2_to_datetime() {
+        let days = 739_000;
+        let convert = |ticks| {
+            let value =
+                ColumnData::DateTime2(Some(DateTime2::new(Date::new(days), Time::new(ticks, 7))));
+            match column("datetime").convert(value).unwrap() {
+                ColumnData::DateTime(Some(dt)) => (dt.days(), dt.seconds_fragments()),
+                other => panic!("expected a datetime, got {other:?}"),
+            }
+        };
+
+        let base = i32::try_from(i64::from(days) - DAYS_BEFORE_1900).unwrap();
+
+        // 00:00:00.678 is 203.4 fragments of 1/300s
+        assert_eq!(convert(6_780_000), (base, 203));
+        // 23:59:59.999 rounds up to midnight of the next day
+        assert_eq!(convert(TICKS_PER_DAY - 10_000), (base + 1, 0));
+    }
+
+    #[test]
+    fn it_rescales_time_to_the_destination() {
+        let value = ColumnData::Time(Some(Time::new(1_234_567, 7)));
+        match column("time(3)").convert(value).unwrap() {
+            ColumnData::Time(Some(time)) => {
+                assert_eq!((time.increments(), time.scale()), (123, 3));
+            }
+            other => panic!("expected a time, got {other:?}"),
+        }
+    }
 }
@@ -0,0 +1,132 @@
+use crate::error::Error;
+
+/// Features supported by the connected server, returned by
+/// [`MssqlConnection::server_capabilities`][crate::MssqlConnection::server_capabilities].
+///
+/// Derived from the server's major version, so applications can branch on what the server
+/// supports instead of parsing version strings. Azure SQL Database and Azure SQL Managed
+/// Instance report a fixed version number but always run the latest engine, so every
+/// capability is reported as supported for them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MssqlServerCapabilities {
+    major_version: u16,
+    azure: bool,
+}
+
+impl MssqlServerCapabilities {
+    /// The query reading the version and engine edition of the server.
+    pub(crate) const QUERY: &'static str =
+        "SELECT CAST(SERVERPROPERTY('ProductVersion') AS NVARCHAR(128)), \
+         CAST(SERVERPROPERTY('EngineEdition') AS INT)";
+
+    /// Build the capabilities from `SERVERPROPERTY('ProductVersion')` (e.g. `16.0.1000.6`)
+    /// and `SERVERPROPERTY('EngineEdition')`.
+    pub(crate) fn new(product_version: &str, engine_edition: i32) -> Result<Self, Error> {
+        let major_version = product_version
+            .split('.')
+            .next()
+            .and_then(|major| major.trim().parse().ok())
+            .ok_or_else(|| {
+                Error::Protocol(format!(
+                    "unexpected server product version {product_version:?}"
+                ))
+            })?;
+
+        Ok(Self {
+            major_version,
+            // 5 = Azure SQL Database, 8 = Azure SQL Managed Instance
+            azure: matches!(engine_edition, 5 | 8),
+        })
+    }
+
+    fn at_least(&self, major_version: u16) -> bool {
+        self.azure || self.major_version >= major_version
+    }
+
+    /// The major version of the server, e.g. `16` for SQL Server 2022.
+    pub fn major_version(&self) -> u16 {
+        self.major_version
+    }
+
+    /// Whether the server is Azure SQL Database or Azure SQL Managed Instance.
+    pub fn is_azure(&self) -> bool {
+        self.azure
+    }
+
+    /// `OFFSET ... FETCH` paging (SQL Server 2012+).
+    pub fn supports_offset_fetch(&self) -> bool {
+        self.at_least(11)
+    }
+
+    /// Native JSON functions such as `OPENJSON`, `JSON_VALUE` and `FOR JSON` (SQL Server
+    /// 2016+).
+    pub fn supports_json(&self) -> bool {
+        self.at_least(13)
+    }
+
+    /// The `STRING_AGG` aggregate (SQL Server 2017+).
+    pub fn supports_string_agg(&self) -> bool {
+        self.at_least(14)
+    }
+
+    /// The `DATETRUNC` function (SQL Server 2022+).
+    pub fn supports_datetrunc(&self) -> bool {
+        self.at_least(16)
+    }
+
+    /// TDS 8.0 strict encryption, where TLS is negotiated before PRELOGIN (SQL Server 2022+).
+    ///
+    /// This reports server support only; the driver itself connects with TDS 7.4.
+    pub fn supports_strict_encryption(&self) -> bool {
+        self.at_least(16)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_derives_capabilities_from_the_version() {
+        // SQL Server 2016
+        let caps = MssqlServerCapabilities::new("13.0.5026.0", 3).unwrap();
+        assert_eq!(caps.major_version(), 13);
+        assert!(caps.supports_offset_fetch());
+        assert!(caps.supports_json());
+        assert!(!caps.supports_string_agg());
+        assert!(!caps.supports_datetrunc());
+        assert!(!caps.supports_strict_encryption());
+
+        // SQL Server 2022
+        let caps = MssqlServerCapabilities::new("16.0.1000.6", 2).unwrap();
+        assert!(caps.supports_string_agg());
+        assert!(caps.supports_datetrunc());
+        assert!(caps.supports_strict_encryption());
+
+        // SQL Server 2008 R2
+        let caps = MssqlServerCapabilities::new("10.50.6000.34", 3).unwrap();
+        assert!(!caps.supports_offset_fetch());
+        assert!(!caps.supports_json());
+    }
+
+    #[test]
+    fn it_treats_azure_as_supporting_everything() {
+        let caps = MssqlServerCapabilities::new("12.0.2000.8", 5).unwrap();
+        assert!(caps.is_azure());
+        assert!(caps.supports_json());
+        assert!(caps.supports_string_agg());
+        assert!(caps.supports_strict_encryption());
+    }
+
+    #[test]
+    fn it_rejects_malformed_versions() {
+        assert!(matches!(
+            MssqlServerCapabilities::new("", 2),
+            Err(Error::Protocol(_))
+        ));
+        assert!(matches!(
+            MssqlServerCapabilities::new("vNext", 2),
+            Err(Error::Protocol(_))
+        ));
+    }
+}
@@ -9,6 +9,20 @@ pub struct MssqlColumn {
     pub(crate) name: UStr,
     pub(crate) type_info: MssqlTypeInfo,
     pub(crate) origin: ColumnOrigin,
+    pub(crate) collation: Option<UStr>,
+}
+
+impl MssqlColumn {
+    /// The collation of a character column, e.g. `SQL_Latin1_General_CP1_CI_AS`, as reported
+    /// by `sp_describe_first_result_set`.
+    ///
+    /// Tells e.g. whether comparisons on the column are case-sensitive (`_CS_`) or not
+    /// (`_CI_`). Only known for the columns of a prepared or described statement; the
+    /// columns of an executed query's rows don't carry it, and it is `None` for columns
+    /// that aren't `CHAR`, `VARCHAR`, `NCHAR`, `NVARCHAR`, `TEXT` or `NTEXT`.
+    pub fn collation(&self) -> Option<&str> {
+        self.collation.as_deref()
+    }
 }
 
 impl Column for MssqlColumn {
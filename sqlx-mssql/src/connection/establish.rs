@@ -1,40 +1,237 @@
 use crate::common::StatementCache;
 use crate::connection::MssqlConnectionInner;
 use crate::error::{tiberius_err, Error};
+use crate::executor::Executor;
 use crate::io::SocketAdapter;
-use crate::{MssqlConnectOptions, MssqlConnection};
+use crate::query_as::query_as;
+use crate::query_scalar::query_scalar;
+use crate::{MssqlConnectOptions, MssqlConnection, MssqlSslMode};
 use sqlx_core::net::{Socket, WithSocket};
+use sqlx_core::sql_str::AssertSqlSafe;
+use tracing::Level;
+
+/// Target of the structured handshake events emitted while establishing a connection.
+///
+/// Enable `DEBUG` for this target to trace each phase of the PRELOGIN/LOGIN negotiation.
+const HANDSHAKE_TARGET: &str = "sqlx::mssql::handshake";
+
+/// The `SET` options required for indexed views and indexes on computed columns, see
+/// [`MssqlConnectOptions::required_set_options`].
+const REQUIRED_SET_OPTIONS: &str = "SET ANSI_NULLS, ANSI_PADDING, ANSI_WARNINGS, ARITHABORT, \
+     CONCAT_NULL_YIELDS_NULL, QUOTED_IDENTIFIER ON; SET NUMERIC_ROUNDABORT OFF;";
 
 impl MssqlConnection {
     pub(crate) async fn establish(options: &MssqlConnectOptions) -> Result<Self, Error> {
+        options.validate()?;
+
+        let mut attempt = 0;
+
+        loop {
+            let error = match Self::establish_once(options).await {
+                Ok(conn) => return Ok(conn),
+                Err(error) => error,
+            };
+
+            let Some(delay) = options.connect_retry_delay(attempt, &error) else {
+                return Err(error);
+            };
+
+            attempt += 1;
+
+            tracing::debug!(
+                target: HANDSHAKE_TARGET,
+                phase = "retry",
+                attempt,
+                ?delay,
+                %error,
+                "retrying connection after transient error"
+            );
+
+            sqlx_core::rt::sleep(delay).await;
+        }
+    }
+
+    async fn establish_once(options: &MssqlConnectOptions) -> Result<Self, Error> {
         let config = options.to_tiberius_config();
         let log_settings = options.log_settings.clone();
         let cache_capacity = options.statement_cache_capacity;
+        let database = options.database.clone();
+
+        tracing::debug!(
+            target: HANDSHAKE_TARGET,
+            phase = "connect",
+            host = %options.host,
+            port = options.port,
+            instance = options.instance.as_deref(),
+            "opening TCP connection"
+        );
+
+        let handler = EstablishHandler {
+            config,
+            ssl_mode: options.effective_ssl_mode(),
+            trust_server_certificate: options.trust_server_certificate,
+            auth: options.auth_method(),
+            database: options.database.clone(),
+            app_name: options.get_program_name(),
+            read_only: options.application_intent_read_only,
+        };
 
-        let handler = EstablishHandler { config };
-
-        crate::net::connect_tcp(&options.host, options.port, handler)
-            .await?
-            .map(|client| MssqlConnection {
-                inner: Box::new(MssqlConnectionInner {
-                    client,
-                    transaction_depth: 0,
-                    pending_rollback: false,
-                    log_settings,
-                    cache_statement: StatementCache::new(cache_capacity),
+        let handshake = crate::net::connect_tcp(&options.host, options.port, handler);
+        let client = match options.connect_timeout {
+            Some(timeout) => sqlx_core::rt::timeout(timeout, handshake)
+                .await
+                .unwrap_or_else(|_| {
+                    Err(Error::Io(std::io::Error::new(
+                        std::io::ErrorKind::TimedOut,
+                        format!("connecting to the server timed out after {timeout:?}"),
+                    )))
                 }),
-            })
+            None => handshake.await,
+        }
+        .and_then(|client| client)
+        .inspect_err(|error| {
+            tracing::debug!(
+                target: HANDSHAKE_TARGET,
+                phase = "failed",
+                %error,
+                "connection handshake failed"
+            );
+        })?;
+
+        let mut conn = MssqlConnection {
+            inner: Box::new(MssqlConnectionInner {
+                client,
+                transaction_depth: 0,
+                pending_rollback: false,
+                rolled_back_guards: 0,
+                savepoints: Vec::new(),
+                transaction_settings_reset: None,
+                settings_reset_pending: false,
+                pending_bulk_insert_rollback: false,
+                log_settings,
+                cache_statement: StatementCache::new(cache_capacity),
+                database,
+                host: options.host.clone(),
+                server_capabilities: None,
+                encryption_level: None,
+                session_id: None,
+                capture_last_insert_id: options.capture_last_insert_id,
+                captured_rowcount: None,
+                command_timeout: options.command_timeout,
+                timed_out: false,
+            }),
+        };
+
+        if tracing::enabled!(target: HANDSHAKE_TARGET, Level::DEBUG) {
+            log_negotiated_settings(&mut conn).await;
+        }
+
+        // Session settings are sent as a single batch to spend at most one round-trip.
+        let mut settings = String::new();
+
+        if options.required_set_options {
+            settings.push_str(REQUIRED_SET_OPTIONS);
+        }
+
+        if let Some(priority) = options.deadlock_priority {
+            settings.push_str(&format!("SET DEADLOCK_PRIORITY {priority};"));
+        }
+
+        if options.no_count {
+            settings.push_str("SET NOCOUNT ON;");
+        }
+
+        if !settings.is_empty() {
+            conn.execute(AssertSqlSafe(settings)).await?;
+        }
+
+        if let Some(expected) = &options.expected_collation {
+            check_collation(&mut conn, expected).await?;
+        }
+
+        Ok(conn)
+    }
+}
+
+/// Fail unless the server collation is `expected`, see
+/// [`MssqlConnectOptions::expected_collation`].
+async fn check_collation(conn: &mut MssqlConnection, expected: &str) -> Result<(), Error> {
+    let actual: Option<String> =
+        query_scalar("SELECT CAST(SERVERPROPERTY('Collation') AS NVARCHAR(128))")
+            .fetch_one(&mut *conn)
+            .await?;
+
+    match actual {
+        Some(actual) if actual.eq_ignore_ascii_case(expected) => Ok(()),
+        actual => Err(Error::Configuration(
+            format!("server collation is {actual:?}, expected {expected:?}").into(),
+        )),
+    }
+}
+
+/// Report what the server agreed to during the handshake. Only runs when handshake tracing
+/// is enabled, since it costs a round-trip.
+async fn log_negotiated_settings(conn: &mut MssqlConnection) {
+    let settings: Result<(String, i32, String, String, String), Error> = query_as(
+        "SELECT CAST(SERVERPROPERTY('ProductVersion') AS NVARCHAR(128)), net_packet_size, \
+         encrypt_option, auth_scheme, net_transport \
+         FROM sys.dm_exec_connections WHERE session_id = @@SPID",
+    )
+    .fetch_one(&mut *conn)
+    .await;
+
+    match settings {
+        Ok((server_version, packet_size, encrypt_option, auth_scheme, transport)) => {
+            tracing::debug!(
+                target: HANDSHAKE_TARGET,
+                phase = "established",
+                %server_version,
+                packet_size,
+                encrypted = encrypt_option.eq_ignore_ascii_case("TRUE"),
+                %auth_scheme,
+                %transport,
+                "connection established"
+            );
+        }
+        Err(error) => {
+            tracing::debug!(
+                target: HANDSHAKE_TARGET,
+                phase = "established",
+                %error,
+                "connection established; negotiated settings unavailable"
+            );
+        }
     }
 }
 
 struct EstablishHandler {
     config: tiberius::Config,
+    ssl_mode: MssqlSslMode,
+    trust_server_certificate: bool,
+    auth: &'static str,
+    database: Option<String>,
+    app_name: String,
+    read_only: bool,
 }
 
 impl WithSocket for EstablishHandler {
     type Output = Result<tiberius::Client<SocketAdapter<Box<dyn Socket>>>, Error>;
 
     async fn with_socket<S: Socket>(self, socket: S) -> Self::Output {
+        // PRELOGIN, TLS and LOGIN7 all run inside `Client::connect`, so they can't be
+        // reported separately; its outcome is the `established` or `failed` event.
+        tracing::debug!(
+            target: HANDSHAKE_TARGET,
+            phase = "handshake",
+            ssl_mode = ?self.ssl_mode,
+            trust_server_certificate = self.trust_server_certificate,
+            auth = self.auth,
+            database = self.database.as_deref(),
+            app_name = %self.app_name,
+            read_only = self.read_only,
+            "TCP connected; starting prelogin, encryption and login"
+        );
+
         let boxed: Box<dyn Socket> = Box::new(socket);
         let adapter = SocketAdapter::new(boxed);
         tiberius::Client::connect(self.config, adapter)
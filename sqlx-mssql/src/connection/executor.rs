@@ -1,10 +1,12 @@
 use crate::database::MssqlArgumentValue;
-use crate::error::{tiberius_err, Error};
+use crate::error::{tiberius_err, Error, MssqlDatabaseError};
 use crate::executor::{Execute, Executor};
 use crate::ext::ustr::UStr;
 use crate::logger::QueryLogger;
 use crate::statement::{MssqlStatement, MssqlStatementMetadata};
 use crate::type_info::{type_name_for_tiberius, MssqlTypeInfo};
+#[cfg(any(feature = "chrono", feature = "time"))]
+use crate::types::scaled::MAX_TIME_SCALE;
 use crate::value::{column_data_to_mssql_data, MssqlData};
 use crate::HashMap;
 use crate::{Mssql, MssqlArguments, MssqlColumn, MssqlConnection, MssqlQueryResult, MssqlRow};
@@ -13,8 +15,12 @@ use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_util::TryStreamExt;
 use sqlx_core::column::{ColumnOrigin, TableColumn};
+use sqlx_core::ext::async_stream::TryAsyncStream;
 use sqlx_core::sql_str::{AssertSqlSafe, SqlSafeStr as _, SqlStr};
+use std::future::Future;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::Instrument;
 
 /// Newtype wrapper to bridge `tiberius::ColumnData` into `tiberius::IntoSql`.
 ///
@@ -32,13 +38,14 @@ impl<'a> tiberius::IntoSql<'a> for ColumnDataWrapper<'a> {
     }
 }
 
-/// Maximum days-since-epoch (0001-01-01) that fits in the 3-byte TDS date
-/// encoding. `tiberius::time::Date::new()` panics if `days > 0x00FFFFFF`.
+/// Days-since-epoch (0001-01-01) of 9999-12-31, the last date SQL Server's date types
+/// hold. Well within the 3-byte TDS date encoding, past which
+/// `tiberius::time::Date::new()` panics.
 #[cfg(any(feature = "chrono", feature = "time"))]
-const MAX_DAYS: u32 = 0x00FF_FFFF;
+const MAX_DAYS: u32 = 3_652_058;
 
 /// Convert a signed days-since-epoch count to `u32`, returning
-/// `Error::Encode` if negative or exceeding the TDS 3-byte limit.
+/// `Error::Encode` if the date is before 0001-01-01 or after 9999-12-31.
 #[cfg(any(feature = "chrono", feature = "time"))]
 fn days_since_epoch_to_u32(days: i64) -> Result<u32, Error> {
     u32::try_from(days)
@@ -47,8 +54,8 @@ fn days_since_epoch_to_u32(days: i64) -> Result<u32, Error> {
         .ok_or_else(|| {
             Error::Encode(
                 format!(
-                    "date out of range for SQL Server: {days} days since epoch \
-                     (must be 0..={MAX_DAYS})"
+                    "date out of range for SQL Server: {days} days since 0001-01-01 \
+                     (must be 0..={MAX_DAYS}, i.e. 0001-01-01 to 9999-12-31)"
                 )
                 .into(),
             )
@@ -76,6 +83,117 @@ fn offset_minutes_to_i16(offset_minutes: i32) -> Result<i16, Error> {
     }
 }
 
+/// Nanoseconds since midnight as a TDS time with `scale` fractional digits, dropping the
+/// digits beyond it. This is a single division, so nothing is lost to an intermediate
+/// rounding step.
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn tds_time(nanoseconds: u64, scale: u8) -> tiberius::time::Time {
+    let increments = nanoseconds / 10u64.pow(9 - u32::from(scale));
+    tiberius::time::Time::new(increments, scale)
+}
+
+/// Nanoseconds since midnight; a leap second is folded into the second before it.
+#[cfg(feature = "chrono")]
+fn chrono_nanoseconds(time: chrono::NaiveTime) -> u64 {
+    use chrono::Timelike as _;
+
+    u64::from(time.num_seconds_from_midnight()) * 1_000_000_000
+        + u64::from(time.nanosecond()) % 1_000_000_000
+}
+
+#[cfg(feature = "chrono")]
+fn chrono_tds_date(date: chrono::NaiveDate) -> Result<tiberius::time::Date, Error> {
+    let epoch = chrono::NaiveDate::from_ymd_opt(1, 1, 1).expect("epoch 0001-01-01 is always valid");
+    let days = days_since_epoch_to_u32((date - epoch).num_days())?;
+    Ok(tiberius::time::Date::new(days))
+}
+
+#[cfg(feature = "time")]
+fn time_nanoseconds(time: time::Time) -> u64 {
+    let (h, m, s, ns) = time.as_hms_nano();
+    u64::from(h) * 3_600_000_000_000
+        + u64::from(m) * 60_000_000_000
+        + u64::from(s) * 1_000_000_000
+        + u64::from(ns)
+}
+
+#[cfg(feature = "time")]
+fn time_tds_date(date: time::Date) -> Result<tiberius::time::Date, Error> {
+    let epoch = time::Date::from_ordinal_date(1, 1).expect("epoch 0001-01-01 is always valid");
+    let days = days_since_epoch_to_u32((date - epoch).whole_days())?;
+    Ok(tiberius::time::Date::new(days))
+}
+
+/// Convert a `TIME`, `DATETIME2` or `DATETIMEOFFSET` argument to TDS with `scale`
+/// fractional-second digits.
+///
+/// Returns `Error::Encode` for a scale above 7 or an argument without a time of day.
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn temporal_column_data(
+    value: &MssqlArgumentValue,
+    scale: u8,
+) -> Result<tiberius::ColumnData<'static>, Error> {
+    use tiberius::time::{DateTime2, DateTimeOffset};
+    use tiberius::ColumnData;
+
+    if scale > MAX_TIME_SCALE {
+        return Err(Error::Encode(
+            format!(
+                "fractional-second scale {scale} is out of range (must be 0..={MAX_TIME_SCALE})"
+            )
+            .into(),
+        ));
+    }
+
+    let datetime2 = |date, nanoseconds| DateTime2::new(date, tds_time(nanoseconds, scale));
+
+    Ok(match value {
+        #[cfg(feature = "chrono")]
+        MssqlArgumentValue::NaiveTime(v) => {
+            ColumnData::Time(Some(tds_time(chrono_nanoseconds(*v), scale)))
+        }
+        #[cfg(feature = "chrono")]
+        MssqlArgumentValue::NaiveDateTime(v) => ColumnData::DateTime2(Some(datetime2(
+            chrono_tds_date(v.date())?,
+            chrono_nanoseconds(v.time()),
+        ))),
+        #[cfg(feature = "chrono")]
+        MssqlArgumentValue::DateTimeFixedOffset(v) => {
+            let naive = v.naive_local();
+            let dt2 = datetime2(
+                chrono_tds_date(naive.date())?,
+                chrono_nanoseconds(naive.time()),
+            );
+            let offset = offset_minutes_to_i16(v.offset().local_minus_utc() / 60)?;
+            ColumnData::DateTimeOffset(Some(DateTimeOffset::new(dt2, offset)))
+        }
+        #[cfg(feature = "time")]
+        MssqlArgumentValue::TimeTime(v) => {
+            ColumnData::Time(Some(tds_time(time_nanoseconds(*v), scale)))
+        }
+        #[cfg(feature = "time")]
+        MssqlArgumentValue::TimePrimitiveDateTime(v) => ColumnData::DateTime2(Some(datetime2(
+            time_tds_date(v.date())?,
+            time_nanoseconds(v.time()),
+        ))),
+        #[cfg(feature = "time")]
+        MssqlArgumentValue::TimeOffsetDateTime(v) => {
+            let dt2 = datetime2(time_tds_date(v.date())?, time_nanoseconds(v.time()));
+            let offset = offset_minutes_to_i16(v.offset().whole_seconds() / 60)?;
+            ColumnData::DateTimeOffset(Some(DateTimeOffset::new(dt2, offset)))
+        }
+        other => {
+            return Err(Error::Encode(
+                format!(
+                    "cannot set the fractional-second scale of {other:?}; \
+                     only TIME, DATETIME2 and DATETIMEOFFSET values have one"
+                )
+                .into(),
+            ))
+        }
+    })
+}
+
 /// Convert a `BigDecimal` into the `(i128, u8)` pair that
 /// `tiberius::numeric::Numeric::new_with_scale` expects.
 ///
@@ -111,240 +229,424 @@ fn bigdecimal_to_numeric(v: &bigdecimal::BigDecimal) -> Result<(i128, u8), Error
     Ok((value, scale))
 }
 
+/// An item of [`MssqlConnection::run_stream_with_columns`]: the columns of a new result set,
+/// or a row or query result as yielded by [`MssqlConnection::run_stream`].
+pub(crate) type ColumnsOrStep = Either<Arc<Vec<MssqlColumn>>, Either<MssqlQueryResult, MssqlRow>>;
+
 impl MssqlConnection {
-    /// Execute a query, eagerly collecting all results.
+    /// Execute a query, yielding its rows and per-result-set query results as they arrive
+    /// from the server.
     ///
-    /// We collect eagerly because `tiberius::QueryStream` borrows `&mut Client`,
-    /// which prevents us from holding it across yield points alongside `&mut self`.
-    pub(crate) async fn run(
+    /// The stream drives the `tiberius::QueryStream`, which borrows the client, so nothing
+    /// is buffered beyond the packet being decoded. If the stream is dropped before the end,
+    /// tiberius discards the rest of the response before the next request on the connection.
+    ///
+    /// The query runs in a span carrying the connection's OpenTelemetry attributes, so the
+    /// query log and any events emitted while it runs can be correlated in distributed traces.
+    pub(crate) fn run_stream(
         &mut self,
-        sql: &str,
+        sql: SqlStr,
         arguments: Option<MssqlArguments>,
-    ) -> Result<Vec<Either<MssqlQueryResult, MssqlRow>>, Error> {
-        // Resolve any pending rollback first
-        crate::transaction::resolve_pending_rollback(self).await?;
-
-        let mut logger = QueryLogger::new(
-            AssertSqlSafe(sql).into_sql_str(),
-            self.inner.log_settings.clone(),
-        );
-
-        let mut results = Vec::new();
+    ) -> BoxStream<'_, Result<Either<MssqlQueryResult, MssqlRow>, Error>> {
+        Box::pin(
+            self.run_stream_with_columns(sql, arguments)
+                .try_filter_map(|step| futures_util::future::ok(step.right())),
+        )
+    }
 
-        if let Some(args) = arguments {
-            // Parameterized query using tiberius::Query
-            let mut query = tiberius::Query::new(sql);
+    /// Like [`run_stream`](Self::run_stream), but also yielding the columns of each result
+    /// set as soon as its metadata arrives, before any of its rows.
+    pub(crate) fn run_stream_with_columns(
+        &mut self,
+        sql: SqlStr,
+        arguments: Option<MssqlArguments>,
+    ) -> BoxStream<'_, Result<ColumnsOrStep, Error>> {
+        let span = self.query_span();
+
+        Box::pin(TryAsyncStream::new(move |yielder| {
+            async move {
+                self.check_timed_out()?;
+                crate::transaction::resolve_pending_rollback(self).await?;
+
+                let logger = QueryLogger::new(sql.clone(), self.inner.log_settings.clone());
+                let mut sets = ResultSets::new(logger);
+
+                let sql = if self.captures_last_insert_id(sql.as_str()) {
+                    AssertSqlSafe(format!("{}\n;{LAST_INSERT_ID_QUERY}", sql.as_str()))
+                        .into_sql_str()
+                } else {
+                    sql
+                };
 
-            for arg in &args.values {
-                match arg {
-                    MssqlArgumentValue::Null => {
-                        query.bind(Option::<&str>::None);
-                    }
-                    MssqlArgumentValue::Bool(v) => {
-                        query.bind(*v);
-                    }
-                    MssqlArgumentValue::U8(v) => {
-                        query.bind(*v);
-                    }
-                    MssqlArgumentValue::I16(v) => {
-                        query.bind(*v);
-                    }
-                    MssqlArgumentValue::I32(v) => {
-                        query.bind(*v);
-                    }
-                    MssqlArgumentValue::I64(v) => {
-                        query.bind(*v);
-                    }
-                    MssqlArgumentValue::F32(v) => {
-                        query.bind(*v);
-                    }
-                    MssqlArgumentValue::F64(v) => {
-                        query.bind(*v);
-                    }
-                    MssqlArgumentValue::String(v) => {
-                        query.bind(v.as_str());
-                    }
-                    MssqlArgumentValue::Binary(v) => {
-                        query.bind(v.as_slice());
-                    }
-                    #[cfg(feature = "chrono")]
-                    MssqlArgumentValue::NaiveDateTime(v) => {
-                        query.bind(*v);
-                    }
-                    #[cfg(feature = "chrono")]
-                    MssqlArgumentValue::NaiveDate(v) => {
-                        query.bind(*v);
-                    }
-                    #[cfg(feature = "chrono")]
-                    MssqlArgumentValue::NaiveTime(v) => {
-                        query.bind(*v);
-                    }
-                    #[cfg(feature = "chrono")]
-                    MssqlArgumentValue::DateTimeFixedOffset(v) => {
-                        use chrono::Timelike as _;
-                        let epoch = chrono::NaiveDate::from_ymd_opt(1, 1, 1)
-                            .expect("epoch 0001-01-01 is always valid");
-                        let naive = v.naive_local();
-                        let days = days_since_epoch_to_u32((naive.date() - epoch).num_days())?;
-                        let time = naive.time();
-                        let total_ns = u64::from(time.num_seconds_from_midnight()) * 1_000_000_000
-                            + (u64::from(time.nanosecond()) % 1_000_000_000);
-                        let increments = total_ns / 100;
-                        let offset_minutes = v.offset().local_minus_utc() / 60;
-                        let dt2 = tiberius::time::DateTime2::new(
-                            tiberius::time::Date::new(days),
-                            tiberius::time::Time::new(increments, 7),
-                        );
-                        let cd = tiberius::ColumnData::DateTimeOffset(Some(
-                            tiberius::time::DateTimeOffset::new(
-                                dt2,
-                                offset_minutes_to_i16(offset_minutes)?,
-                            ),
-                        ));
-                        query.bind(ColumnDataWrapper(cd));
-                    }
-                    #[cfg(feature = "uuid")]
-                    MssqlArgumentValue::Uuid(v) => {
-                        query.bind(v);
-                    }
-                    #[cfg(feature = "rust_decimal")]
-                    MssqlArgumentValue::Decimal(v) => {
-                        let unpacked = v.unpack();
-                        // SAFETY: rust_decimal mantissa is ≤96 bits (hi:mid:lo are u32s), fits in i128.
-                        #[allow(clippy::cast_possible_wrap)]
-                        let mut value = (((unpacked.hi as u128) << 64)
-                            + ((unpacked.mid as u128) << 32)
-                            + unpacked.lo as u128) as i128;
-                        if v.is_sign_negative() {
-                            value = -value;
-                        }
-                        let scale = v.scale();
-                        if scale > 37 {
-                            return Err(Error::Encode(
-                                format!(
-                                    "rust_decimal scale {scale} exceeds SQL Server maximum of 37"
-                                )
-                                .into(),
-                            ));
-                        }
-                        // SAFETY: guarded by `scale > 37` check above; 0..=37 fits in u8.
-                        #[allow(clippy::cast_possible_truncation)]
-                        let scale_u8 = scale as u8;
-                        query.bind(tiberius::numeric::Numeric::new_with_scale(value, scale_u8));
-                    }
-                    #[cfg(feature = "time")]
-                    MssqlArgumentValue::TimeDate(v) => {
-                        let epoch = time::Date::from_ordinal_date(1, 1)
-                            .expect("epoch 0001-01-01 is always valid");
-                        let days = days_since_epoch_to_u32((*v - epoch).whole_days())?;
-                        let cd = tiberius::ColumnData::Date(Some(tiberius::time::Date::new(days)));
-                        query.bind(ColumnDataWrapper(cd));
+                let mut timer =
+                    CommandTimer::new(self.inner.command_timeout, &mut self.inner.timed_out);
+                let mut stream = match &arguments {
+                    Some(args) => {
+                        let mut query = tiberius::Query::new(sql.as_str());
+                        bind_arguments(&mut query, args)?;
+                        timer.wait(query.query(&mut self.inner.client)).await?
                     }
-                    #[cfg(feature = "time")]
-                    MssqlArgumentValue::TimeTime(v) => {
-                        let (h, m, s, ns) = v.as_hms_nano();
-                        let total_ns = u64::from(h) * 3_600_000_000_000
-                            + u64::from(m) * 60_000_000_000
-                            + u64::from(s) * 1_000_000_000
-                            + u64::from(ns);
-                        // Scale 7 = 100ns increments
-                        let increments = total_ns / 100;
-                        let cd = tiberius::ColumnData::Time(Some(tiberius::time::Time::new(
-                            increments, 7,
-                        )));
-                        query.bind(ColumnDataWrapper(cd));
+                    None => {
+                        timer
+                            .wait(self.inner.client.simple_query(sql.as_str()))
+                            .await?
                     }
-                    #[cfg(feature = "time")]
-                    MssqlArgumentValue::TimePrimitiveDateTime(v) => {
-                        let date = v.date();
-                        let time = v.time();
-                        let epoch = time::Date::from_ordinal_date(1, 1)
-                            .expect("epoch 0001-01-01 is always valid");
-                        let days = days_since_epoch_to_u32((date - epoch).whole_days())?;
-                        let (h, m, s, ns) = time.as_hms_nano();
-                        let total_ns = u64::from(h) * 3_600_000_000_000
-                            + u64::from(m) * 60_000_000_000
-                            + u64::from(s) * 1_000_000_000
-                            + u64::from(ns);
-                        let increments = total_ns / 100;
-                        let cd =
-                            tiberius::ColumnData::DateTime2(Some(tiberius::time::DateTime2::new(
-                                tiberius::time::Date::new(days),
-                                tiberius::time::Time::new(increments, 7),
-                            )));
-                        query.bind(ColumnDataWrapper(cd));
-                    }
-                    #[cfg(feature = "time")]
-                    MssqlArgumentValue::TimeOffsetDateTime(v) => {
-                        let epoch = time::Date::from_ordinal_date(1, 1)
-                            .expect("epoch 0001-01-01 is always valid");
-                        let offset_minutes = v.offset().whole_seconds() / 60;
-                        let date = v.date();
-                        let time = v.time();
-                        let days = days_since_epoch_to_u32((date - epoch).whole_days())?;
-                        let (h, m, s, ns) = time.as_hms_nano();
-                        let total_ns = u64::from(h) * 3_600_000_000_000
-                            + u64::from(m) * 60_000_000_000
-                            + u64::from(s) * 1_000_000_000
-                            + u64::from(ns);
-                        let increments = total_ns / 100;
-                        let dt2 = tiberius::time::DateTime2::new(
-                            tiberius::time::Date::new(days),
-                            tiberius::time::Time::new(increments, 7),
-                        );
-                        let cd = tiberius::ColumnData::DateTimeOffset(Some(
-                            tiberius::time::DateTimeOffset::new(
-                                dt2,
-                                offset_minutes_to_i16(offset_minutes)?,
-                            ),
-                        ));
-                        query.bind(ColumnDataWrapper(cd));
+                };
+
+                while let Some(item) = timer.wait(stream.try_next()).await? {
+                    let is_metadata = matches!(item, tiberius::QueryItem::Metadata(_));
+
+                    if let Some(step) = sets.push(item)? {
+                        yielder.r#yield(Either::Right(step)).await;
                     }
-                    #[cfg(feature = "bigdecimal")]
-                    MssqlArgumentValue::BigDecimal(v) => {
-                        let (value, scale) = bigdecimal_to_numeric(v)?;
-                        let cd = tiberius::ColumnData::Numeric(Some(
-                            tiberius::numeric::Numeric::new_with_scale(value, scale),
-                        ));
-                        query.bind(ColumnDataWrapper(cd));
+
+                    // `None` for the result set of `LAST_INSERT_ID_QUERY`
+                    if let Some(columns) = sets.columns.as_ref().filter(|_| is_metadata) {
+                        yielder.r#yield(Either::Left(Arc::clone(columns))).await;
                     }
                 }
+
+                self.inner.captured_rowcount = sets.last_rowcount;
+                yielder.r#yield(Either::Right(sets.finish())).await;
+
+                Ok(())
             }
+            .instrument(span)
+        }))
+    }
 
-            let stream = query
-                .query(&mut self.inner.client)
-                .await
-                .map_err(tiberius_err)?;
-            collect_results(stream, &mut results, &mut logger).await?;
-        } else {
-            // Simple query (no parameters)
-            let stream = self
-                .inner
-                .client
-                .simple_query(sql)
-                .await
-                .map_err(tiberius_err)?;
-            collect_results(stream, &mut results, &mut logger).await?;
+    /// Execute a parameterized query for the row counts of its statements, discarding any
+    /// rows it returns.
+    ///
+    /// Unlike [`run_stream`](Self::run_stream), this sees the DONE token of every statement,
+    /// so a batch of several DML statements yields one `MssqlQueryResult` per statement.
+    /// tiberius only reports DONE tokens for RPC requests (`sp_executesql`), not for plain
+    /// SQL batches, so unparameterized queries have to go through `run_stream`.
+    pub(crate) async fn run_execute(
+        &mut self,
+        sql: &str,
+        arguments: MssqlArguments,
+    ) -> Result<Vec<MssqlQueryResult>, Error> {
+        let span = self.query_span();
+
+        async move {
+            self.check_timed_out()?;
+            crate::transaction::resolve_pending_rollback(self).await?;
+
+            let mut logger = QueryLogger::new(
+                AssertSqlSafe(sql).into_sql_str(),
+                self.inner.log_settings.clone(),
+            );
+
+            let mut query = tiberius::Query::new(sql);
+            bind_arguments(&mut query, &arguments)?;
+
+            let result = CommandTimer::new(self.inner.command_timeout, &mut self.inner.timed_out)
+                .wait(query.execute(&mut self.inner.client))
+                .await?;
+
+            Ok(result
+                .rows_affected()
+                .iter()
+                .map(|&rows_affected| {
+                    logger.increase_rows_affected(rows_affected);
+                    MssqlQueryResult {
+                        rows_affected,
+                        last_insert_id: None,
+                    }
+                })
+                .collect())
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Fail if an earlier query on this connection exceeded the command timeout.
+    ///
+    /// tiberius can't cancel a request, so the abandoned one may still be running on the
+    /// server, and every later request would first wait for the rest of its response. The
+    /// connection is therefore unusable from then on: `ping` fails, so a pool closes it, and
+    /// closing the socket makes the server abort the request.
+    pub(crate) fn check_timed_out(&self) -> Result<(), Error> {
+        if self.inner.timed_out {
+            return Err(Error::Io(std::io::Error::new(
+                std::io::ErrorKind::NotConnected,
+                "connection is unusable: an earlier query exceeded the command timeout",
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Whether [`run_stream`](Self::run_stream) appends [`LAST_INSERT_ID_QUERY`] to `sql`.
+    ///
+    /// Only `INSERT` statements are extended, so batches that must stand alone (`CREATE
+    /// PROCEDURE`, `CREATE VIEW`) are left untouched.
+    pub(crate) fn captures_last_insert_id(&self, sql: &str) -> bool {
+        self.inner.capture_last_insert_id
+            && sql
+                .trim_start()
+                .get(..6)
+                .is_some_and(|keyword| keyword.eq_ignore_ascii_case("INSERT"))
+    }
+
+    /// The span a query runs in, carrying the connection's OpenTelemetry attributes.
+    fn query_span(&self) -> tracing::Span {
+        tracing::info_span!(
+            target: "sqlx::query",
+            "mssql.query",
+            db.system = "mssql",
+            db.name = self.inner.database.as_deref(),
+            net.peer.name = %self.inner.host,
+        )
+    }
+
+    /// Describe the first result set of each of `sqls` with a single query, see
+    /// [`MssqlConnection::prepare_many`]. `sqls` must fit in one `VALUES` clause.
+    ///
+    /// Unlike `sp_describe_first_result_set`, the function form reports a statement it can't
+    /// describe as a row with an `error_number`, so one bad statement doesn't fail the rest.
+    pub(super) async fn describe_first_result_sets(
+        &mut self,
+        sqls: &[&SqlStr],
+    ) -> Result<Vec<Result<MssqlStatementMetadata, Error>>, Error> {
+        self.inner.captured_rowcount = None;
+
+        let mut sql = String::from(
+            "SELECT v.n, d.name, d.system_type_name, d.is_nullable, d.source_table, \
+             d.source_schema, d.source_column, d.collation_name, d.error_number, d.error_severity, \
+             d.error_state, d.error_message FROM (VALUES ",
+        );
+        for i in 0..sqls.len() {
+            if i > 0 {
+                sql.push_str(", ");
+            }
+            sql.push_str(&format!("({i}, @P{})", i + 1));
+        }
+        sql.push_str(
+            ") AS v(n, tsql) \
+             CROSS APPLY sys.dm_exec_describe_first_result_set(v.tsql, NULL, 0) AS d \
+             ORDER BY v.n, d.column_ordinal",
+        );
+
+        let mut query = tiberius::Query::new(sql);
+        for sql in sqls {
+            query.bind(sql.as_str());
+        }
+
+        let stream = query
+            .query(&mut self.inner.client)
+            .await
+            .map_err(tiberius_err)?;
+        let rows = stream.into_first_result().await.map_err(tiberius_err)?;
+
+        let mut described: Vec<Vec<tiberius::Row>> = sqls.iter().map(|_| Vec::new()).collect();
+        for row in rows {
+            let statement = row
+                .get::<i32, _>("n")
+                .and_then(|n| usize::try_from(n).ok())
+                .and_then(|n| described.get_mut(n))
+                .ok_or_else(|| Error::Protocol("describe row without statement index".into()))?;
+            statement.push(row);
         }
 
-        Ok(results)
+        Ok(described
+            .into_iter()
+            .map(|rows| match rows.first().map(describe_error) {
+                Some(Some(error)) => Err(error),
+                _ => Ok(statement_metadata(&rows)),
+            })
+            .collect())
     }
 }
 
-/// Collect all results from a tiberius QueryStream into a Vec.
-async fn collect_results(
-    mut stream: tiberius::QueryStream<'_>,
-    results: &mut Vec<Either<MssqlQueryResult, MssqlRow>>,
-    logger: &mut QueryLogger,
+/// Bind sqlx arguments to a tiberius query, in order.
+pub(super) fn bind_arguments<'a>(
+    query: &mut tiberius::Query<'a>,
+    args: &'a MssqlArguments,
 ) -> Result<(), Error> {
-    // Process all result sets
-    let mut columns: Option<Arc<Vec<MssqlColumn>>> = None;
-    let mut column_names: Option<Arc<HashMap<UStr, usize>>> = None;
-    let mut rows_affected: u64 = 0;
+    args.check_parameter_limit()?;
 
-    while let Some(item) = stream.try_next().await.map_err(tiberius_err)? {
+    for arg in &args.values {
+        match arg {
+            MssqlArgumentValue::Null => {
+                query.bind(Option::<&str>::None);
+            }
+            MssqlArgumentValue::Bool(v) => {
+                query.bind(*v);
+            }
+            MssqlArgumentValue::U8(v) => {
+                query.bind(*v);
+            }
+            MssqlArgumentValue::I16(v) => {
+                query.bind(*v);
+            }
+            MssqlArgumentValue::I32(v) => {
+                query.bind(*v);
+            }
+            MssqlArgumentValue::I64(v) => {
+                query.bind(*v);
+            }
+            MssqlArgumentValue::U64(v) => match i64::try_from(*v) {
+                Ok(v) => query.bind(v),
+                Err(_) => query.bind(tiberius::numeric::Numeric::new_with_scale(
+                    i128::from(*v),
+                    0,
+                )),
+            },
+            MssqlArgumentValue::I128(v) => {
+                query.bind(tiberius::numeric::Numeric::new_with_scale(*v, 0));
+            }
+            MssqlArgumentValue::Money(v) => {
+                query.bind(tiberius::numeric::Numeric::new_with_scale(
+                    i128::from(*v),
+                    4,
+                ));
+            }
+            MssqlArgumentValue::F32(v) => {
+                query.bind(*v);
+            }
+            MssqlArgumentValue::F64(v) => {
+                query.bind(*v);
+            }
+            MssqlArgumentValue::String(v) => {
+                query.bind(v.as_str());
+            }
+            MssqlArgumentValue::Binary(v) => {
+                query.bind(v.as_slice());
+            }
+            #[cfg(feature = "chrono")]
+            MssqlArgumentValue::NaiveDateTime(_)
+            | MssqlArgumentValue::NaiveTime(_)
+            | MssqlArgumentValue::DateTimeFixedOffset(_) => {
+                let cd = temporal_column_data(arg, MAX_TIME_SCALE)?;
+                query.bind(ColumnDataWrapper(cd));
+            }
+            #[cfg(feature = "chrono")]
+            MssqlArgumentValue::NaiveDate(v) => {
+                // tiberius truncates the day count to `u32` and panics past the 3-byte
+                // limit, so dates before 0001-01-01 are range-checked here instead.
+                let cd = tiberius::ColumnData::Date(Some(chrono_tds_date(*v)?));
+                query.bind(ColumnDataWrapper(cd));
+            }
+            #[cfg(feature = "uuid")]
+            MssqlArgumentValue::Uuid(v) => {
+                query.bind(v);
+            }
+            #[cfg(feature = "rust_decimal")]
+            MssqlArgumentValue::Decimal(v) => {
+                let unpacked = v.unpack();
+                // SAFETY: rust_decimal mantissa is ≤96 bits (hi:mid:lo are u32s), fits in i128.
+                #[allow(clippy::cast_possible_wrap)]
+                let mut value = (((unpacked.hi as u128) << 64)
+                    + ((unpacked.mid as u128) << 32)
+                    + unpacked.lo as u128) as i128;
+                if v.is_sign_negative() {
+                    value = -value;
+                }
+                let scale = v.scale();
+                if scale > 37 {
+                    return Err(Error::Encode(
+                        format!("rust_decimal scale {scale} exceeds SQL Server maximum of 37")
+                            .into(),
+                    ));
+                }
+                // SAFETY: guarded by `scale > 37` check above; 0..=37 fits in u8.
+                #[allow(clippy::cast_possible_truncation)]
+                let scale_u8 = scale as u8;
+                query.bind(tiberius::numeric::Numeric::new_with_scale(value, scale_u8));
+            }
+            #[cfg(feature = "time")]
+            MssqlArgumentValue::TimeDate(v) => {
+                let cd = tiberius::ColumnData::Date(Some(time_tds_date(*v)?));
+                query.bind(ColumnDataWrapper(cd));
+            }
+            #[cfg(feature = "time")]
+            MssqlArgumentValue::TimeTime(_)
+            | MssqlArgumentValue::TimePrimitiveDateTime(_)
+            | MssqlArgumentValue::TimeOffsetDateTime(_) => {
+                let cd = temporal_column_data(arg, MAX_TIME_SCALE)?;
+                query.bind(ColumnDataWrapper(cd));
+            }
+            #[cfg(any(feature = "chrono", feature = "time"))]
+            MssqlArgumentValue::Scaled(value, scale) => {
+                let cd = temporal_column_data(value, *scale)?;
+                query.bind(ColumnDataWrapper(cd));
+            }
+            #[cfg(feature = "bigdecimal")]
+            MssqlArgumentValue::BigDecimal(v) => {
+                let (value, scale) = bigdecimal_to_numeric(v)?;
+                let cd = tiberius::ColumnData::Numeric(Some(
+                    tiberius::numeric::Numeric::new_with_scale(value, scale),
+                ));
+                query.bind(ColumnDataWrapper(cd));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Turns the items of a `tiberius::QueryStream` into rows and query results.
+///
+/// Rows are followed by one `MssqlQueryResult` per result set, so consecutive result sets of
+/// a batch are separated by an `Either::Left`.
+///
+/// Statements without a result set (`SET`, `PRINT`, `DECLARE`, control flow) send neither
+/// metadata nor rows, so they don't start a result set of their own.
+///
+/// The result set of [`LAST_INSERT_ID_QUERY`] is consumed rather than reported: its values
+/// go into the final `MssqlQueryResult`.
+struct ResultSets {
+    logger: QueryLogger,
+    /// The columns of the first result set, if any.
+    first_columns: Option<Arc<Vec<MssqlColumn>>>,
+    columns: Option<Arc<Vec<MssqlColumn>>>,
+    column_names: Option<Arc<HashMap<UStr, usize>>>,
+    rows_affected: u64,
+    /// Whether the current result set is the one of [`LAST_INSERT_ID_QUERY`].
+    in_last_insert_id: bool,
+    last_insert_id: Option<i64>,
+    /// The row count of the batch's last statement, as read by [`LAST_INSERT_ID_QUERY`].
+    last_rowcount: Option<i64>,
+}
+
+impl ResultSets {
+    fn new(logger: QueryLogger) -> Self {
+        Self {
+            logger,
+            first_columns: None,
+            columns: None,
+            column_names: None,
+            rows_affected: 0,
+            in_last_insert_id: false,
+            last_insert_id: None,
+            last_rowcount: None,
+        }
+    }
+
+    /// Handle the next item of the stream, returning what it completes, if anything.
+    fn push(
+        &mut self,
+        item: tiberius::QueryItem,
+    ) -> Result<Option<Either<MssqlQueryResult, MssqlRow>>, Error> {
         match item {
             tiberius::QueryItem::Metadata(meta) => {
+                // A new result set ends the previous one; report it separately so callers
+                // can tell the result sets of a batch apart.
+                let done = self.columns.is_some().then(|| self.finish());
+
+                if meta
+                    .columns()
+                    .first()
+                    .is_some_and(|col| col.name() == LAST_INSERT_ID_COLUMN)
+                {
+                    self.in_last_insert_id = true;
+                    self.columns = None;
+                    self.column_names = None;
+                    return Ok(done);
+                }
+
                 // Build column info from metadata
                 let cols: Vec<MssqlColumn> = meta
                     .columns()
@@ -359,6 +661,7 @@ async fn collect_results(
                             name,
                             type_info,
                             origin: ColumnOrigin::Unknown,
+                            collation: None,
                         }
                     })
                     .collect();
@@ -369,14 +672,33 @@ async fn collect_results(
                     .map(|(i, col)| (col.name.clone(), i))
                     .collect();
 
-                columns = Some(Arc::new(cols));
-                column_names = Some(Arc::new(names));
+                let cols = Arc::new(cols);
+                self.first_columns.get_or_insert_with(|| Arc::clone(&cols));
+                self.columns = Some(cols);
+                self.column_names = Some(Arc::new(names));
+
+                Ok(done)
+            }
+            tiberius::QueryItem::Row(row) if self.in_last_insert_id => {
+                // `SCOPE_IDENTITY()` is NULL when the batch inserted no identity value.
+                self.last_insert_id = row.get(0);
+                self.last_rowcount = row.get(1);
+
+                // The batch returned no rows, so its count is that of its last statement
+                if self.first_columns.is_none() {
+                    let rows_affected = self.last_rowcount.unwrap_or_default();
+                    self.rows_affected = u64::try_from(rows_affected).unwrap_or_default();
+                }
+
+                Ok(None)
             }
             tiberius::QueryItem::Row(row) => {
-                let cols = columns
+                let cols = self
+                    .columns
                     .as_ref()
                     .ok_or_else(|| Error::Protocol("row received before metadata".into()))?;
-                let names = column_names
+                let names = self
+                    .column_names
                     .as_ref()
                     .ok_or_else(|| Error::Protocol("row received before metadata".into()))?;
 
@@ -386,22 +708,119 @@ async fn collect_results(
                     .map(column_data_to_mssql_data)
                     .collect::<Result<Vec<_>, _>>()?;
 
-                rows_affected += 1;
-                logger.increment_rows_returned();
-                results.push(Either::Right(MssqlRow {
+                self.rows_affected += 1;
+                self.logger.increment_rows_returned();
+
+                Ok(Some(Either::Right(MssqlRow {
                     values,
                     columns: Arc::clone(cols),
                     column_names: Arc::clone(names),
-                }));
+                })))
             }
         }
     }
 
-    // Report query result with total rows
-    logger.increase_rows_affected(rows_affected);
-    results.push(Either::Left(MssqlQueryResult { rows_affected }));
+    /// End the current result set, returning its query result.
+    fn finish(&mut self) -> Either<MssqlQueryResult, MssqlRow> {
+        let rows_affected = std::mem::take(&mut self.rows_affected);
+        self.logger.increase_rows_affected(rows_affected);
+        Either::Left(MssqlQueryResult {
+            rows_affected,
+            last_insert_id: self.last_insert_id.take(),
+        })
+    }
+}
+
+/// Enforces [`MssqlConnectOptions::command_timeout`][crate::MssqlConnectOptions::command_timeout]
+/// on a query.
+///
+/// Only the time spent waiting on the server counts, not the time the caller takes to consume
+/// the rows of a stream. When the timeout expires, the pending read is abandoned and the
+/// connection is marked as timed out, see [`MssqlConnection::check_timed_out`].
+struct CommandTimer<'a> {
+    timeout: Option<Duration>,
+    remaining: Duration,
+    timed_out: &'a mut bool,
+}
+
+impl<'a> CommandTimer<'a> {
+    fn new(timeout: Option<Duration>, timed_out: &'a mut bool) -> Self {
+        Self {
+            timeout,
+            remaining: timeout.unwrap_or(Duration::MAX),
+            timed_out,
+        }
+    }
+
+    /// Wait for `request`, failing with [`std::io::ErrorKind::TimedOut`] if the remaining
+    /// time runs out first.
+    async fn wait<T>(
+        &mut self,
+        request: impl Future<Output = tiberius::Result<T>>,
+    ) -> Result<T, Error> {
+        let Some(timeout) = self.timeout else {
+            return request.await.map_err(tiberius_err);
+        };
 
-    Ok(())
+        let start = Instant::now();
+        let result = sqlx_core::rt::timeout(self.remaining, request)
+            .await
+            .map_err(|_| {
+                *self.timed_out = true;
+                Error::Io(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    format!("query exceeded the command timeout of {timeout:?}"),
+                ))
+            })?;
+        self.remaining = self.remaining.saturating_sub(start.elapsed());
+
+        result.map_err(tiberius_err)
+    }
+}
+
+/// The name of the first column of [`LAST_INSERT_ID_QUERY`], which identifies its result set.
+const LAST_INSERT_ID_COLUMN: &str = "__sqlx_last_insert_id";
+
+/// Appended to `INSERT` statements when
+/// [`MssqlConnectOptions::capture_last_insert_id`][crate::MssqlConnectOptions::capture_last_insert_id]
+/// is enabled. It runs in the same scope as the insert, so `SCOPE_IDENTITY()` sees its
+/// identity value and `ROWCOUNT_BIG()` its row count.
+const LAST_INSERT_ID_QUERY: &str =
+    "SELECT CAST(SCOPE_IDENTITY() AS BIGINT) AS __sqlx_last_insert_id, \
+     ROWCOUNT_BIG() AS __sqlx_rows_affected";
+
+/// The error `sys.dm_exec_describe_first_result_set` reported in `row` instead of a column.
+fn describe_error(row: &tiberius::Row) -> Option<Error> {
+    let number = row.get::<i32, _>("error_number")?;
+
+    Some(Error::Database(Box::new(MssqlDatabaseError {
+        number: u32::try_from(number).unwrap_or_default(),
+        state: row
+            .get::<i32, _>("error_state")
+            .and_then(|state| u8::try_from(state).ok())
+            .unwrap_or_default(),
+        class: row
+            .get::<i32, _>("error_severity")
+            .and_then(|class| u8::try_from(class).ok())
+            .unwrap_or(16),
+        message: row
+            .get::<&str, _>("error_message")
+            .unwrap_or_default()
+            .to_owned(),
+        server: None,
+        procedure: None,
+    })))
+}
+
+/// Build the metadata of a prepared statement from its describe rows.
+fn statement_metadata(rows: &[tiberius::Row]) -> MssqlStatementMetadata {
+    let (columns, column_names, _nullable) = build_columns_from_describe_rows(rows);
+
+    MssqlStatementMetadata {
+        columns: Arc::new(columns),
+        column_names: Arc::new(column_names),
+        parameters: 0,
+    }
 }
 
 /// Build column metadata from `sp_describe_first_result_set` result rows.
@@ -439,6 +858,8 @@ fn build_columns_from_describe_rows(
             _ => ColumnOrigin::Expression,
         };
 
+        let collation: Option<&str> = row.get("collation_name");
+
         let ustr_name = UStr::new(name);
         column_names.insert(ustr_name.clone(), ordinal);
         columns.push(MssqlColumn {
@@ -446,6 +867,7 @@ fn build_columns_from_describe_rows(
             name: ustr_name,
             type_info,
             origin,
+            collation: collation.map(UStr::new),
         });
         nullable.push(is_nullable);
     }
@@ -473,15 +895,39 @@ impl<'c> Executor<'c> for &'c mut MssqlConnection {
         let _persistent = query.persistent();
         let sql = query.sql();
 
-        Box::pin(
-            futures_util::stream::once(async move {
-                let arguments = arguments?;
-                let results = self.run(sql.as_str(), arguments).await?;
-                Ok::<_, Error>(results)
-            })
-            .map_ok(|results| futures_util::stream::iter(results.into_iter().map(Ok)))
-            .try_flatten(),
-        )
+        match arguments {
+            Ok(arguments) => self.run_stream(sql, arguments),
+            Err(error) => Box::pin(futures_util::stream::once(async { Err(error) })),
+        }
+    }
+
+    fn execute_many<'e, 'q, E>(self, mut query: E) -> BoxStream<'e, Result<MssqlQueryResult, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+        'q: 'e,
+        E: 'q,
+    {
+        let arguments = query.take_arguments().map_err(Error::Encode);
+        let sql = query.sql();
+
+        match arguments {
+            // Parameterized queries report the count of each statement
+            Ok(Some(arguments)) if !self.captures_last_insert_id(sql.as_str()) => Box::pin(
+                futures_util::stream::once(async move {
+                    self.run_execute(sql.as_str(), arguments).await
+                })
+                .map_ok(|results| futures_util::stream::iter(results.into_iter().map(Ok)))
+                .try_flatten(),
+            ),
+            // Plain batches only report one count per result set; skip the rows as they
+            // arrive rather than buffering them
+            Ok(arguments) => Box::pin(
+                self.run_stream(sql, arguments)
+                    .try_filter_map(|step| futures_util::future::ok(step.left())),
+            ),
+            Err(error) => Box::pin(futures_util::stream::once(async { Err(error) })),
+        }
     }
 
     fn fetch_optional<'e, 'q, E>(self, query: E) -> BoxFuture<'e, Result<Option<MssqlRow>, Error>>
@@ -494,13 +940,17 @@ impl<'c> Executor<'c> for &'c mut MssqlConnection {
         let mut s = self.fetch_many(query);
 
         Box::pin(async move {
+            let mut first = None;
+
+            // Read the response to the end, so an error later in the batch is still
+            // reported and the connection is left ready for the next query.
             while let Some(v) = s.try_next().await? {
                 if let Either::Right(r) = v {
-                    return Ok(Some(r));
+                    first.get_or_insert(r);
                 }
             }
 
-            Ok(None)
+            Ok(first)
         })
     }
 
@@ -513,6 +963,16 @@ impl<'c> Executor<'c> for &'c mut MssqlConnection {
         'c: 'e,
     {
         Box::pin(async move {
+            self.check_timed_out()?;
+
+            if let Some(metadata) = self.inner.cache_statement.get_mut(sql.as_str()) {
+                return Ok(MssqlStatement {
+                    sql,
+                    metadata: metadata.clone(),
+                });
+            }
+
+            self.inner.captured_rowcount = None;
             let mut describe_query =
                 tiberius::Query::new("EXEC sp_describe_first_result_set @tsql = @p1");
             describe_query.bind(sql.as_str());
@@ -524,16 +984,15 @@ impl<'c> Executor<'c> for &'c mut MssqlConnection {
 
             let rows: Vec<tiberius::Row> =
                 stream.into_first_result().await.map_err(tiberius_err)?;
-            let (columns, column_names, _nullable) = build_columns_from_describe_rows(&rows);
-
-            Ok(MssqlStatement {
-                sql,
-                metadata: MssqlStatementMetadata {
-                    columns: Arc::new(columns),
-                    column_names: Arc::new(column_names),
-                    parameters: 0,
-                },
-            })
+            let metadata = statement_metadata(&rows);
+
+            if self.inner.cache_statement.is_enabled() {
+                self.inner
+                    .cache_statement
+                    .insert(sql.as_str(), metadata.clone());
+            }
+
+            Ok(MssqlStatement { sql, metadata })
         })
     }
 
@@ -547,6 +1006,9 @@ impl<'c> Executor<'c> for &'c mut MssqlConnection {
         'c: 'e,
     {
         Box::pin(async move {
+            self.check_timed_out()?;
+
+            self.inner.captured_rowcount = None;
             let mut describe_query =
                 tiberius::Query::new("EXEC sp_describe_first_result_set @tsql = @p1");
             describe_query.bind(sql.as_str());
@@ -591,6 +1053,101 @@ impl<'c> Executor<'c> for &'c mut MssqlConnection {
 mod tests {
     use super::*;
 
+    fn bind_one(value: MssqlArgumentValue) -> Result<(), Error> {
+        let arguments = MssqlArguments {
+            values: vec![value],
+        };
+        let mut query = tiberius::Query::new("SELECT @P1");
+        bind_arguments(&mut query, &arguments)
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_date_bounds() {
+        use chrono::NaiveDate;
+
+        for date in [
+            NaiveDate::from_ymd_opt(1, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(9999, 12, 31).unwrap(),
+        ] {
+            assert!(bind_one(MssqlArgumentValue::NaiveDate(date)).is_ok());
+        }
+
+        for date in [
+            NaiveDate::from_ymd_opt(0, 12, 31).unwrap(),
+            NaiveDate::from_ymd_opt(10000, 1, 1).unwrap(),
+            NaiveDate::MIN,
+            NaiveDate::MAX,
+        ] {
+            let err = bind_one(MssqlArgumentValue::NaiveDate(date)).unwrap_err();
+            assert!(matches!(err, Error::Encode(_)));
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_date_time_bounds() {
+        use chrono::{FixedOffset, NaiveDate, TimeZone};
+
+        let at = |year| {
+            NaiveDate::from_ymd_opt(year, 6, 1)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap()
+        };
+        let offset = FixedOffset::east_opt(3600).unwrap();
+
+        for year in [1, 9999] {
+            assert!(bind_one(MssqlArgumentValue::NaiveDateTime(at(year))).is_ok());
+            let v = offset.from_local_datetime(&at(year)).unwrap();
+            assert!(bind_one(MssqlArgumentValue::DateTimeFixedOffset(v)).is_ok());
+        }
+
+        for year in [0, 10000, 200_000] {
+            let err = bind_one(MssqlArgumentValue::NaiveDateTime(at(year))).unwrap_err();
+            assert!(matches!(err, Error::Encode(_)), "{year}");
+
+            let v = offset.from_local_datetime(&at(year)).unwrap();
+            let err = bind_one(MssqlArgumentValue::DateTimeFixedOffset(v)).unwrap_err();
+            assert!(matches!(err, Error::Encode(_)), "{year}");
+        }
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_date_bounds() {
+        use time::macros::date;
+
+        for date in [date!(0001 - 01 - 01), date!(9999 - 12 - 31)] {
+            assert!(bind_one(MssqlArgumentValue::TimeDate(date)).is_ok());
+        }
+
+        let err = bind_one(MssqlArgumentValue::TimeDate(date!(0000 - 12 - 31))).unwrap_err();
+        assert!(matches!(err, Error::Encode(_)));
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_date_time_bounds() {
+        use time::macros::{datetime, offset};
+
+        for v in [datetime!(0001-01-01 0:00), datetime!(9999-12-31 23:59:59)] {
+            assert!(bind_one(MssqlArgumentValue::TimePrimitiveDateTime(v)).is_ok());
+            let v = v.assume_offset(offset!(+1));
+            assert!(bind_one(MssqlArgumentValue::TimeOffsetDateTime(v)).is_ok());
+        }
+
+        // without the `large-dates` feature, `time` stops at 9999-12-31 as SQL Server does
+        let v = datetime!(0000-12-31 23:59:59);
+        let err = bind_one(MssqlArgumentValue::TimePrimitiveDateTime(v)).unwrap_err();
+        assert!(matches!(err, Error::Encode(_)));
+        let err = bind_one(MssqlArgumentValue::TimeOffsetDateTime(
+            v.assume_offset(offset!(+1)),
+        ))
+        .unwrap_err();
+        assert!(matches!(err, Error::Encode(_)));
+    }
+
     #[test]
     fn days_since_epoch_zero() {
         assert_eq!(days_since_epoch_to_u32(0).unwrap(), 0);
@@ -612,6 +1169,10 @@ mod tests {
     fn days_since_epoch_overflow() {
         let err = days_since_epoch_to_u32(i64::from(MAX_DAYS) + 1).unwrap_err();
         assert!(matches!(err, Error::Encode(_)));
+
+        // within the 3-byte TDS encoding, but past 9999-12-31
+        let err = days_since_epoch_to_u32(0x00FF_FFFF).unwrap_err();
+        assert!(matches!(err, Error::Encode(_)));
     }
 
     #[test]
@@ -622,6 +1183,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tds_time_scales() {
+        // 12:34:56.123456789
+        let nanoseconds = 45_296_123_456_789;
+        for (scale, increments) in [
+            (7, 452_961_234_567),
+            (3, 45_296_123),
+            (1, 452_961),
+            (0, 45_296),
+        ] {
+            let time = tds_time(nanoseconds, scale);
+            assert_eq!((time.increments(), time.scale()), (increments, scale));
+        }
+
+        // the last 100 ns of the day still fits at scale 7
+        let time = tds_time(86_399_999_999_999, 7);
+        assert_eq!(time.increments(), 863_999_999_999);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_scaled_binds() {
+        use chrono::{NaiveDate, NaiveTime};
+
+        let time = NaiveTime::from_hms_nano_opt(12, 0, 0, 123_900_000).unwrap();
+        match temporal_column_data(&MssqlArgumentValue::NaiveTime(time), 3).unwrap() {
+            tiberius::ColumnData::Time(Some(t)) => {
+                assert_eq!((t.increments(), t.scale()), (43_200_123, 3));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+
+        let date_time = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap().and_time(time);
+        assert!(bind_one(MssqlArgumentValue::Scaled(
+            Box::new(MssqlArgumentValue::NaiveDateTime(date_time)),
+            0
+        ))
+        .is_ok());
+
+        for (value, scale) in [
+            (MssqlArgumentValue::NaiveTime(time), 8),
+            (
+                MssqlArgumentValue::NaiveDate(NaiveDate::from_ymd_opt(2024, 1, 2).unwrap()),
+                3,
+            ),
+        ] {
+            let err = bind_one(MssqlArgumentValue::Scaled(Box::new(value), scale)).unwrap_err();
+            assert!(matches!(err, Error::Encode(_)), "{err:?}");
+        }
+    }
+
     #[test]
     fn offset_minutes_zero() {
         assert_eq!(offset_minutes_to_i16(0).unwrap(), 0);
@@ -1,22 +1,58 @@
+use std::collections::{HashMap, HashSet};
 use std::fmt::{self, Debug, Formatter};
+use std::hash::Hash;
+use std::ops::{Range, RangeInclusive};
+use std::panic::AssertUnwindSafe;
+use std::time::{Duration, Instant};
+
+use either::Either;
+use futures_core::future::BoxFuture;
+use futures_core::stream::BoxStream;
+use futures_util::io::{AsyncRead, AsyncReadExt};
+use futures_util::{FutureExt, TryStreamExt};
 
 pub(crate) use sqlx_core::connection::*;
+use sqlx_core::ext::async_stream::TryAsyncStream;
 use sqlx_core::net::Socket;
-use sqlx_core::sql_str::{AssertSqlSafe, SqlSafeStr};
+use sqlx_core::sql_str::{AssertSqlSafe, SqlSafeStr, SqlStr};
 
-use crate::bulk_insert::MssqlBulkInsert;
+use crate::arguments::MAX_PARAMETERS;
+use crate::bulk_insert::{
+    next_bulk_copy_row, BulkCopyColumn, MssqlBulkInsert, BULK_INSERT_SAVEPOINT,
+};
+use crate::capabilities::MssqlServerCapabilities;
 use crate::common::StatementCache;
+use crate::decode::Decode;
+use crate::encode::Encode;
 use crate::error::{tiberius_err, Error};
-use crate::executor::Executor;
+use crate::executor::{Execute, Executor};
+use crate::from_row::FromRow;
+use crate::ident::{quote_identifier, quote_object_name};
 use crate::io::SocketAdapter;
 use crate::isolation_level::MssqlIsolationLevel;
-use crate::statement::MssqlStatementMetadata;
-use crate::transaction::{resolve_pending_rollback, Transaction};
-use crate::{Mssql, MssqlConnectOptions};
+use crate::options::ssl_mode::MssqlEncryptionLevel;
+use crate::procedure::{MssqlProcedureParams, MssqlProcedureResult};
+use crate::query::{query, query_with, query_with_result};
+use crate::query_as::query_as;
+use crate::query_builder::{QueryBuilder, Separated};
+use crate::query_builder_ext::{shift_placeholders, values_chunk_size, MAX_VALUES_ROWS};
+use crate::query_scalar::{query_scalar, query_scalar_with};
+use crate::request_info::{MssqlRequestInfo, RequestInfoRow};
+use crate::row::Row;
+use crate::statement::{MssqlStatement, MssqlStatementMetadata};
+use crate::stats::{MssqlStats, SessionCounters};
+use crate::transaction::{
+    forget_savepoints, resolve_pending_rollback, restore_transaction_settings,
+    validate_savepoint_name, Transaction,
+};
+use crate::types::Type;
+use crate::{Mssql, MssqlArguments, MssqlColumn, MssqlConnectOptions, MssqlRow, MssqlXml};
 
 mod establish;
 mod executor;
 
+use executor::bind_arguments;
+
 /// A connection to a MSSQL database.
 pub struct MssqlConnection {
     pub(crate) inner: Box<MssqlConnectionInner>,
@@ -26,8 +62,41 @@ pub(crate) struct MssqlConnectionInner {
     pub(crate) client: tiberius::Client<SocketAdapter<Box<dyn Socket>>>,
     pub(crate) transaction_depth: usize,
     pub(crate) pending_rollback: bool,
+    /// `Transaction` guards still alive whose transaction `rollback_all` rolled back.
+    pub(crate) rolled_back_guards: usize,
+    /// Savepoints taken with `savepoint()`, oldest first, with the transaction depth each
+    /// was taken at.
+    pub(crate) savepoints: Vec<(String, usize)>,
+    /// Statements restoring the session settings changed by `begin_with_options`, run once
+    /// that transaction ends.
+    pub(crate) transaction_settings_reset: Option<String>,
+    /// Whether the transaction of `transaction_settings_reset` has ended, so the reset runs
+    /// before the next request.
+    pub(crate) settings_reset_pending: bool,
     pub(crate) log_settings: LogSettings,
     pub(crate) cache_statement: StatementCache<MssqlStatementMetadata>,
+    /// The database this connection is expected to be using, if one was configured.
+    pub(crate) database: Option<String>,
+    /// The host this connection was opened to, as configured.
+    pub(crate) host: String,
+    /// The server's capabilities, read on first use.
+    pub(crate) server_capabilities: Option<MssqlServerCapabilities>,
+    /// The encryption negotiated for this connection, read on first use.
+    pub(crate) encryption_level: Option<MssqlEncryptionLevel>,
+    /// The server process id (`@@SPID`) of this session, read on first use.
+    pub(crate) session_id: Option<i16>,
+    /// Whether an aborted bulk insert must be rolled back before the next request.
+    pub(crate) pending_bulk_insert_rollback: bool,
+    /// Whether `INSERT` statements report `SCOPE_IDENTITY()`.
+    pub(crate) capture_last_insert_id: bool,
+    /// The `@@ROWCOUNT` read by the `SELECT` appended for `capture_last_insert_id` in the
+    /// last request, which that `SELECT` then overwrote. Cleared by every other request.
+    pub(crate) captured_rowcount: Option<i64>,
+    /// How long a query may wait on the server.
+    pub(crate) command_timeout: Option<Duration>,
+    /// Whether a query exceeded the command timeout, leaving its request running on the
+    /// server.
+    pub(crate) timed_out: bool,
 }
 
 impl Debug for MssqlConnection {
@@ -143,11 +212,471 @@ impl MssqlConnection {
         Transaction::begin(self, Some(sql.into_sql_str()))
     }
 
+    /// Begin a transaction with its own isolation level, lock timeout and deadlock priority,
+    /// each left unchanged if `None`.
+    ///
+    /// The settings are issued before `BEGIN TRANSACTION`:
+    ///
+    /// ```sql
+    /// SET TRANSACTION ISOLATION LEVEL <level>; SET LOCK_TIMEOUT <ms>;
+    /// SET DEADLOCK_PRIORITY <priority>; BEGIN TRANSACTION
+    /// ```
+    ///
+    /// `SET` statements last for the session, so the previous values are read first and
+    /// restored when the transaction commits or rolls back, including a rollback after the
+    /// guard is dropped. Unlike with [`begin_with_isolation`](Self::begin_with_isolation), a
+    /// pooled connection therefore doesn't keep the settings for its next user. This also
+    /// leaves the connection-wide [`deadlock_priority`](crate::MssqlConnectOptions::deadlock_priority)
+    /// in place for later transactions.
+    /// If restoring them fails, the commit or rollback still succeeds, and the restore is
+    /// retried before each later request until it succeeds.
+    ///
+    /// `lock_timeout` is how long a statement waits for a lock before failing with error
+    /// 1222, in whole milliseconds; [`Duration::ZERO`] fails at once. `deadlock_priority` is
+    /// clamped to `-10..=10` (`LOW` = `-5`, `NORMAL` = `0`, `HIGH` = `5`).
+    ///
+    /// Returns [`Error::InvalidArgument`] if `lock_timeout` exceeds `i32::MAX` milliseconds,
+    /// and [`Error::InvalidSavePointStatement`] if a transaction is already open, as the
+    /// settings cannot apply to a nested transaction alone.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// use std::time::Duration;
+    /// use sqlx::mssql::MssqlIsolationLevel;
+    ///
+    /// // a background job that gives up on locks quickly and yields in deadlocks
+    /// let mut tx = conn
+    ///     .begin_with_options(
+    ///         Some(MssqlIsolationLevel::Snapshot),
+    ///         Some(Duration::from_secs(2)),
+    ///         Some(-5),
+    ///     )
+    ///     .await?;
+    /// // ... use tx ...
+    /// tx.commit().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn begin_with_options(
+        &mut self,
+        isolation: Option<MssqlIsolationLevel>,
+        lock_timeout: Option<Duration>,
+        deadlock_priority: Option<i8>,
+    ) -> Result<Transaction<'_, Mssql>, Error> {
+        let lock_timeout = lock_timeout
+            .map(|timeout| {
+                i32::try_from(timeout.as_millis()).map_err(|_| {
+                    Error::InvalidArgument(format!("lock timeout {timeout:?} is out of range"))
+                })
+            })
+            .transpose()?;
+        let deadlock_priority = deadlock_priority.map(|priority| priority.clamp(-10, 10));
+
+        resolve_pending_rollback(self).await?;
+        if self.inner.transaction_depth > 0 {
+            return Err(Error::InvalidSavePointStatement);
+        }
+
+        let settings = TransactionSettings {
+            isolation,
+            lock_timeout,
+            deadlock_priority: deadlock_priority.map(i32::from),
+        };
+
+        if settings.is_empty() {
+            return Transaction::begin(self, None).await;
+        }
+
+        // A reset left over from a failed restore still holds the original values, and now
+        // runs when this transaction ends.
+        self.inner.settings_reset_pending = false;
+        if self.inner.transaction_settings_reset.is_none() {
+            let (lock_timeout, deadlock_priority, isolation): (i32, i32, i16) = query_as(
+                "SELECT @@LOCK_TIMEOUT, deadlock_priority, transaction_isolation_level \
+                 FROM sys.dm_exec_sessions WHERE session_id = @@SPID",
+            )
+            .fetch_one(&mut *self)
+            .await?;
+
+            let previous = TransactionSettings {
+                isolation: Some(MssqlIsolationLevel::from_session_level(isolation)),
+                lock_timeout: Some(lock_timeout),
+                deadlock_priority: Some(deadlock_priority),
+            };
+            self.inner.transaction_settings_reset = Some(previous.restoring(&settings).to_sql());
+        }
+
+        let mut sql = settings.to_sql();
+        sql.push_str("BEGIN TRANSACTION");
+
+        // On failure, `begin` restores the settings again.
+        Transaction::begin(self, Some(AssertSqlSafe(sql).into_sql_str())).await
+    }
+
+    /// Begin a transaction for reading only, e.g. for reports or on a read replica.
+    ///
+    /// The outermost transaction uses `SNAPSHOT` isolation if the current database has
+    /// `ALLOW_SNAPSHOT_ISOLATION` on, so its reads see one consistent state of the database
+    /// without taking shared locks, and `READ COMMITTED` otherwise. As with
+    /// [`begin_with_options`](Self::begin_with_options), the previous isolation level is
+    /// restored when the transaction ends. Inside a transaction this takes a savepoint like
+    /// [`begin`](Connection::begin) does, and the outer transaction's isolation level stays
+    /// in effect.
+    ///
+    /// SQL Server has no read-only transaction mode, so the intent is not enforced by the
+    /// transaction itself: writes fail with error 3906 (see
+    /// [`MssqlDatabaseError::is_read_only`](crate::MssqlDatabaseError::is_read_only)) on a
+    /// readable secondary, e.g. one reached with
+    /// [`application_intent_read_only`](crate::MssqlConnectOptions::application_intent_read_only),
+    /// or on a `READ_ONLY` database. Elsewhere they succeed, and under `SNAPSHOT` a write to
+    /// a row changed since the transaction started fails with update conflict error 3960.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// let mut tx = conn.begin_read_only().await?;
+    ///
+    /// let orders: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM orders")
+    ///     .fetch_one(&mut *tx)
+    ///     .await?;
+    /// let lines: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM order_lines")
+    ///     .fetch_one(&mut *tx)
+    ///     .await?;
+    ///
+    /// tx.commit().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn begin_read_only(&mut self) -> Result<Transaction<'_, Mssql>, Error> {
+        resolve_pending_rollback(self).await?;
+        if self.inner.transaction_depth > 0 {
+            return Transaction::begin(self, None).await;
+        }
+
+        let snapshot: Option<u8> = query_scalar(
+            "SELECT snapshot_isolation_state FROM sys.databases WHERE database_id = DB_ID()",
+        )
+        .fetch_optional(&mut *self)
+        .await?;
+
+        let isolation = if snapshot == Some(1) {
+            MssqlIsolationLevel::Snapshot
+        } else {
+            MssqlIsolationLevel::ReadCommitted
+        };
+
+        self.begin_with_options(Some(isolation), None, None).await
+    }
+
+    /// Begin a distributed transaction coordinated by MSDTC.
+    ///
+    /// Issues `BEGIN DISTRIBUTED TRANSACTION`. The returned [`Transaction`] tracks depth like
+    /// any other transaction, so nested `begin()` calls create savepoints and `commit` /
+    /// `rollback` behave as usual.
+    ///
+    /// ### Limitations
+    /// * The Microsoft Distributed Transaction Coordinator must be enabled on the server
+    ///   (on Linux via `mssql-conf set distributedtransaction.servertcpport`), otherwise
+    ///   this fails.
+    /// * The driver does not act as a resource manager: it can neither enlist in an existing
+    ///   distributed transaction by id nor take part in two-phase commit coordination.
+    ///   SQL Server promotes the transaction itself when it touches linked servers.
+    /// * Databases on the same instance do not need a distributed transaction; a regular
+    ///   transaction already spans them.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// use sqlx::Executor;
+    ///
+    /// let mut tx = conn.begin_distributed().await?;
+    /// tx.execute("UPDATE Sales.dbo.orders SET shipped = 1 WHERE id = 42").await?;
+    /// tx.execute("INSERT INTO Audit.dbo.events (kind) VALUES ('shipped')").await?;
+    /// tx.commit().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn begin_distributed(
+        &mut self,
+    ) -> impl std::future::Future<Output = Result<Transaction<'_, Mssql>, Error>> + Send + '_ {
+        Transaction::begin(
+            self,
+            Some(SqlStr::from_static("BEGIN DISTRIBUTED TRANSACTION")),
+        )
+    }
+
+    /// Run `callback` with `SET IDENTITY_INSERT <table> ON`, so it can insert explicit values
+    /// into the table's identity column, e.g. when migrating data with preserved ids.
+    ///
+    /// `table` may be qualified (`dbo.users`, `[my db].dbo.users`) and is quoted before use;
+    /// malformed names are rejected with [`Error::InvalidArgument`]. `IDENTITY_INSERT` is
+    /// switched off again whether the callback succeeds, fails or panics (the panic is
+    /// resumed afterwards).
+    ///
+    /// ### Note
+    /// SQL Server allows `IDENTITY_INSERT` to be on for only one table per session, so this
+    /// fails if it is already on for another table, and calls cannot be nested.
+    ///
+    /// If the returned future is dropped before it completes, `IDENTITY_INSERT` is never
+    /// switched off and stays on for the table. Close such a connection rather than
+    /// returning it to a pool.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// conn.with_identity_insert("dbo.users", |conn| {
+    ///     Box::pin(async move {
+    ///         sqlx::query("INSERT INTO dbo.users (id, name) VALUES (@p1, @p2)")
+    ///             .bind(42i32)
+    ///             .bind("alice")
+    ///             .execute(&mut *conn)
+    ///             .await
+    ///     })
+    /// })
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_identity_insert<F, R, E>(&mut self, table: &str, callback: F) -> Result<R, E>
+    where
+        for<'c> F: FnOnce(&'c mut MssqlConnection) -> BoxFuture<'c, Result<R, E>> + Send,
+        R: Send,
+        E: From<Error> + Send,
+    {
+        let table = quote_object_name(table)?;
+
+        self.execute(AssertSqlSafe(format!("SET IDENTITY_INSERT {table} ON")))
+            .await?;
+
+        let ret = AssertUnwindSafe(callback(self)).catch_unwind().await;
+
+        let off = self
+            .execute(AssertSqlSafe(format!("SET IDENTITY_INSERT {table} OFF")))
+            .await;
+
+        let ret = match ret {
+            Ok(ret) => ret?,
+            Err(panic) => std::panic::resume_unwind(panic),
+        };
+        off?;
+
+        Ok(ret)
+    }
+
+    /// Run `callback` impersonating the database user `user`, reverting afterwards.
+    ///
+    /// Issues `EXECUTE AS USER = N'<user>'` before the callback and `REVERT` after it, so
+    /// queries made by the callback see `USER_NAME()` and the permissions and row-level
+    /// security predicates of `user`. This is useful for testing RLS policies and for
+    /// running a piece of work with least privilege. The user name is validated and
+    /// escaped before use; invalid names are rejected with [`Error::InvalidArgument`].
+    ///
+    /// `REVERT` runs whether the callback succeeds, fails or panics (the panic is resumed
+    /// afterwards). Calls can be nested: each `REVERT` undoes only its own `EXECUTE AS`, as
+    /// long as the callback reverts any impersonation it starts itself.
+    ///
+    /// ### Note
+    /// If the returned future is dropped before it completes, `REVERT` is never sent and the
+    /// connection keeps the impersonated context. Close such a connection rather than
+    /// returning it to a pool.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// let visible: i32 = conn
+    ///     .execute_as("tenant_reader", |conn| {
+    ///         Box::pin(async move {
+    ///             sqlx::query_scalar("SELECT COUNT(*) FROM dbo.orders")
+    ///                 .fetch_one(&mut *conn)
+    ///                 .await
+    ///         })
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn execute_as<F, R, E>(&mut self, user: &str, callback: F) -> Result<R, E>
+    where
+        for<'c> F: FnOnce(&'c mut MssqlConnection) -> BoxFuture<'c, Result<R, E>> + Send,
+        R: Send,
+        E: From<Error> + Send,
+    {
+        let statement = execute_as_statement(user)?;
+
+        self.execute(AssertSqlSafe(statement)).await?;
+
+        let ret = AssertUnwindSafe(callback(self)).catch_unwind().await;
+
+        let revert = self.execute("REVERT").await;
+
+        let ret = match ret {
+            Ok(ret) => ret?,
+            Err(panic) => std::panic::resume_unwind(panic),
+        };
+        revert?;
+
+        Ok(ret)
+    }
+
+    /// Roll back the entire transaction, including every nested savepoint, with a single
+    /// `ROLLBACK`.
+    ///
+    /// In SQL Server a bare `ROLLBACK` always discards the outermost transaction and all of
+    /// its savepoints; only `ROLLBACK TRANSACTION <savepoint>` undoes a single level. This
+    /// issues that bare `ROLLBACK` and resets the transaction depth to zero. Dropping or
+    /// rolling back a [`Transaction`] guard that is still alive then does nothing, and
+    /// committing one fails with [`Error::Protocol`], as its changes are gone.
+    ///
+    /// Does nothing if no transaction is open.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// use sqlx::Connection;
+    ///
+    /// let mut tx = conn.begin().await?;
+    /// let mut nested = tx.begin().await?;
+    ///
+    /// // abandon everything, not just `nested`
+    /// nested.rollback_all().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rollback_all(&mut self) -> Result<(), Error> {
+        // A pending rollback from a dropped guard is subsumed by the full rollback.
+        let pending = std::mem::take(&mut self.inner.pending_rollback);
+
+        if self.inner.transaction_depth == 0 && !pending {
+            return Ok(());
+        }
+
+        self.execute("ROLLBACK").await?;
+        self.inner.rolled_back_guards += self.inner.transaction_depth;
+        self.inner.transaction_depth = 0;
+        forget_savepoints(self);
+        // The rollback happened either way; a failed reset is retried before the next request.
+        let _ = restore_transaction_settings(self).await;
+
+        Ok(())
+    }
+
+    /// Take a savepoint with the given name in the current transaction, with
+    /// `SAVE TRANSACTION [name]`, to roll back to later with
+    /// [`rollback_to_savepoint`](Self::rollback_to_savepoint).
+    ///
+    /// Nested [`Transaction`]s already take savepoints implicitly; named ones allow undoing
+    /// part of a transaction, possibly more than once, without opening a nested transaction.
+    /// A name may be reused, in which case rolling back goes to the most recent savepoint
+    /// with that name.
+    ///
+    /// A savepoint belongs to the (nested) transaction it was taken in, and is forgotten
+    /// when that transaction commits or rolls back.
+    ///
+    /// Returns [`Error::InvalidArgument`] if no transaction is open, or if the name is empty,
+    /// longer than 32 characters (the limit SQL Server places on savepoint names), contains
+    /// control characters or starts with `_sqlx_`, which is reserved for savepoints taken by
+    /// SQLx itself.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// use sqlx::{Connection, Executor};
+    ///
+    /// let mut tx = conn.begin().await?;
+    /// tx.execute("INSERT INTO orders (id) VALUES (1)").await?;
+    ///
+    /// tx.savepoint("before_items").await?;
+    /// if tx.execute("INSERT INTO order_items (order_id, sku) VALUES (1, 'A')").await.is_err() {
+    ///     // keep the order, drop the partial items
+    ///     tx.rollback_to_savepoint("before_items").await?;
+    /// }
+    ///
+    /// tx.commit().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn savepoint(&mut self, name: &str) -> Result<(), Error> {
+        let name = validate_savepoint_name(name)?;
+
+        resolve_pending_rollback(self).await?;
+        let depth = self.inner.transaction_depth;
+        if depth == 0 {
+            return Err(Error::InvalidArgument(format!(
+                "cannot take savepoint {name:?} outside a transaction"
+            )));
+        }
+
+        self.execute(AssertSqlSafe(format!(
+            "SAVE TRANSACTION {}",
+            quote_identifier(name)
+        )))
+        .await?;
+        self.inner.savepoints.push((name.to_owned(), depth));
+
+        Ok(())
+    }
+
+    /// Roll back to a savepoint taken with [`savepoint`](Self::savepoint), with
+    /// `ROLLBACK TRANSACTION [name]`.
+    ///
+    /// Undoes the changes made since the most recent savepoint of that name. The transaction
+    /// stays open and the savepoint remains, so it can be rolled back to again; savepoints
+    /// taken after it are forgotten. The transaction depth, and so the behavior of
+    /// `commit()` and `rollback()` on the open [`Transaction`]s, is unchanged.
+    ///
+    /// Returns [`Error::InvalidArgument`] if the current transaction has no savepoint with
+    /// that name, including when it was taken before the current nested transaction began:
+    /// rolling back past the start of a nested transaction would leave its guard pointing at
+    /// a savepoint that no longer exists, so roll back the nested transaction first.
+    pub async fn rollback_to_savepoint(&mut self, name: &str) -> Result<(), Error> {
+        let name = validate_savepoint_name(name)?;
+
+        resolve_pending_rollback(self).await?;
+        let depth = self.inner.transaction_depth;
+        let Some(index) = self
+            .inner
+            .savepoints
+            .iter()
+            .rposition(|(savepoint, _)| savepoint == name)
+        else {
+            return Err(Error::InvalidArgument(format!(
+                "no savepoint named {name:?} in the current transaction"
+            )));
+        };
+
+        if self.inner.savepoints[index].1 != depth {
+            return Err(Error::InvalidArgument(format!(
+                "savepoint {name:?} was taken before the current nested transaction began; \
+                 roll back the nested transaction first"
+            )));
+        }
+
+        self.execute(AssertSqlSafe(format!(
+            "ROLLBACK TRANSACTION {}",
+            quote_identifier(name)
+        )))
+        .await?;
+        self.inner.savepoints.truncate(index + 1);
+
+        Ok(())
+    }
+
     /// Start a bulk insert operation for high-performance data loading.
     ///
     /// The table must already exist. Tiberius executes `SELECT TOP 0 * FROM <table>`
     /// to discover column metadata, then uses the TDS `INSERT BULK` protocol.
     ///
+    /// Inside a transaction, a savepoint is taken first so that
+    /// [`MssqlBulkInsert::abort`] can discard the rows.
+    ///
     /// # Example
     ///
     /// ```rust,no_run
@@ -165,12 +694,1665 @@ impl MssqlConnection {
         table: &'c str,
     ) -> Result<MssqlBulkInsert<'c>, Error> {
         resolve_pending_rollback(self).await?;
-        let req = self
-            .inner
+
+        let in_transaction = self.inner.transaction_depth > 0;
+        if in_transaction {
+            self.execute(AssertSqlSafe(format!(
+                "SAVE TRANSACTION {BULK_INSERT_SAVEPOINT}"
+            )))
+            .await?;
+        }
+
+        let inner = &mut *self.inner;
+        let req = inner
             .client
             .bulk_insert(table)
             .await
             .map_err(tiberius_err)?;
-        Ok(MssqlBulkInsert::new(req))
+        let rollback = in_transaction.then_some(&mut inner.pending_bulk_insert_rollback);
+
+        Ok(MssqlBulkInsert::new(req, rollback))
+    }
+
+    /// Compute the identity values assigned by a completed bulk insert.
+    ///
+    /// The TDS `INSERT BULK` protocol does not report generated identity values, so this
+    /// reads `IDENT_CURRENT` and `IDENT_INCR` for `table` after the load and derives the range
+    /// covered by the last `rows` inserted rows. Pass the count returned by
+    /// [`MssqlBulkInsert::finalize`].
+    ///
+    /// Returns `None` if `rows` is zero or `table` has no identity column.
+    ///
+    /// ### Note
+    /// `IDENT_CURRENT` is not scoped to this session, so the range is only accurate if no other
+    /// session inserted into `table` concurrently with the load (e.g. run both in a transaction
+    /// that holds a `TABLOCKX` lock on the table).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// use sqlx::mssql::IntoRow;
+    ///
+    /// let mut bulk = conn.bulk_insert("users").await?;
+    /// bulk.send("alice".into_row()).await?;
+    /// bulk.send("bob".into_row()).await?;
+    /// let total = bulk.finalize().await?;
+    ///
+    /// if let Some(ids) = conn.bulk_insert_identity_range("users", total).await? {
+    ///     println!("assigned ids {} through {}", ids.start(), ids.end());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn bulk_insert_identity_range(
+        &mut self,
+        table: &str,
+        rows: u64,
+    ) -> Result<Option<RangeInclusive<i64>>, Error> {
+        if rows == 0 {
+            return Ok(None);
+        }
+
+        // Temporary tables only resolve through `tempdb` in `IDENT_CURRENT`.
+        let table = if table.starts_with('#') {
+            format!("tempdb..{table}")
+        } else {
+            table.to_owned()
+        };
+
+        let (current, increment): (Option<i64>, Option<i64>) =
+            query_as("SELECT CAST(IDENT_CURRENT(@p1) AS BIGINT), CAST(IDENT_INCR(@p1) AS BIGINT)")
+                .bind(&table)
+                .fetch_one(&mut *self)
+                .await?;
+
+        let (Some(last), Some(increment)) = (current, increment) else {
+            return Ok(None);
+        };
+
+        let span = i64::try_from(rows - 1)
+            .ok()
+            .and_then(|n| n.checked_mul(increment))
+            .ok_or_else(|| {
+                Error::Protocol(format!(
+                    "identity range of {rows} rows with increment {increment} overflows BIGINT"
+                ))
+            })?;
+
+        let first = last.checked_sub(span).ok_or_else(|| {
+            Error::Protocol(format!(
+                "identity range of {rows} rows ending at {last} underflows BIGINT"
+            ))
+        })?;
+
+        // With a negative increment the first row received the largest value; keep the
+        // range ascending.
+        Ok(Some(if increment < 0 {
+            last..=first
+        } else {
+            first..=last
+        }))
+    }
+
+    /// Copy the rows of a query on this connection into `table` on `destination`.
+    ///
+    /// Rows are read from the server as they arrive and sent on with the TDS `INSERT BULK`
+    /// protocol (see [`bulk_insert`](Self::bulk_insert)), so only a packet's worth of rows is
+    /// buffered on either side, however large the result. Returns the number of rows copied.
+    ///
+    /// The query's columns map to the destination columns in order, skipping identity,
+    /// computed and `rowversion` columns, which the server fills in. Values are converted to
+    /// the destination column types: exact matches always work, as do integers of any width
+    /// (range-checked), exact numerics rescaled to the column's scale (rounding half away
+    /// from zero), numbers into floating point columns, strings into `UNIQUEIDENTIFIER` and
+    /// `XML`, most values into character columns, and conversions between the date/time
+    /// types. Anything else fails with [`Error::Encode`]. Only the first result set of the
+    /// query is copied.
+    ///
+    /// ### Note
+    /// The column count is checked before the load starts, but a value that fails to
+    /// convert, or an error reading the source, ends the load midway with
+    /// [`MssqlBulkInsert::abort`]. Run the copy in a transaction on `destination` so that
+    /// the rows already sent are discarded; outside a transaction they are kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(
+    /// #     source: &mut sqlx::mssql::MssqlConnection,
+    /// #     destination: &mut sqlx::mssql::MssqlConnection,
+    /// # ) -> sqlx::Result<()> {
+    /// let copied = source
+    ///     .bulk_copy("SELECT id, name FROM users", destination, "users_archive")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn bulk_copy<'q, E>(
+        &mut self,
+        mut query: E,
+        destination: &mut MssqlConnection,
+        table: &str,
+    ) -> Result<u64, Error>
+    where
+        E: Execute<'q, Mssql> + 'q,
+    {
+        let arguments = query.take_arguments().map_err(Error::Encode)?;
+        let sql = query.sql();
+
+        let columns = BulkCopyColumn::describe(destination, table).await?;
+
+        resolve_pending_rollback(self).await?;
+
+        let mut stream = match &arguments {
+            Some(args) => {
+                let mut query = tiberius::Query::new(sql.as_str());
+                bind_arguments(&mut query, args)?;
+                query.query(&mut self.inner.client).await
+            }
+            None => self.inner.client.simple_query(sql.as_str()).await,
+        }
+        .map_err(tiberius_err)?;
+
+        // Read up to the first row, so a failing query leaves `destination` untouched.
+        let Some(mut row) = next_bulk_copy_row(&mut stream, columns.len(), table).await? else {
+            return Ok(0);
+        };
+
+        let mut bulk = destination.bulk_insert(table).await?;
+
+        loop {
+            let mut values = tiberius::TokenRow::with_capacity(columns.len());
+            for (value, column) in row.into_iter().zip(&columns) {
+                match column.convert(value) {
+                    Ok(value) => values.push(value),
+                    Err(error) => return abort_bulk_copy(bulk, error).await,
+                }
+            }
+            bulk.send(values).await?;
+
+            match next_bulk_copy_row(&mut stream, columns.len(), table).await {
+                Ok(Some(next)) => row = next,
+                Ok(None) => break,
+                Err(error) => return abort_bulk_copy(bulk, error).await,
+            }
+        }
+
+        bulk.finalize().await
+    }
+
+    /// Insert any number of rows with parameterized `INSERT ... VALUES` statements, inside
+    /// a transaction.
+    ///
+    /// `insert` is the statement up to the `VALUES` keyword, e.g.
+    /// `INSERT INTO users (id, name) `, and `push_row` binds the values of one row, as with
+    /// [`QueryBuilder::push_values`]. `push_row` is called exactly once per row, in order, and
+    /// rows may bind different numbers of values. The rows are split into as many statements
+    /// as needed to stay within SQL Server's limits of 1000 rows per `VALUES` clause and 2100
+    /// parameters per request: a statement is sent as soon as the next row would exceed
+    /// either. Returns the total number of rows inserted.
+    ///
+    /// All statements run in one transaction (a savepoint if one is already open), so if
+    /// any of them fails, none of the rows are kept.
+    ///
+    /// For very large loads, [`bulk_insert`](Self::bulk_insert) avoids the per-statement
+    /// overhead entirely.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// let users: Vec<(i32, String)> = (0..100_000).map(|i| (i, format!("user {i}"))).collect();
+    ///
+    /// let inserted = conn
+    ///     .insert_chunked("INSERT INTO users (id, name) ", &users, |mut b, (id, name)| {
+    ///         b.push_bind(*id).push_bind(name.clone());
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn insert_chunked<T, F>(
+        &mut self,
+        insert: &str,
+        rows: &[T],
+        mut push_row: F,
+    ) -> Result<u64, Error>
+    where
+        F: FnMut(Separated<'_, Mssql, &'static str>, &T),
+    {
+        if rows.is_empty() {
+            return Ok(0);
+        }
+
+        let mut tx: Transaction<'_, Mssql> = Transaction::begin(self, None).await?;
+        let mut total = 0;
+
+        // The rows of the statement being filled, and the values they bind
+        let mut values = Vec::new();
+        let mut arguments = MssqlArguments::default();
+
+        for row in rows {
+            if values.len() == MAX_VALUES_ROWS {
+                let arguments = std::mem::take(&mut arguments);
+                total += insert_values(&mut tx, insert, &values, arguments).await?;
+                values.clear();
+            }
+
+            // Rendered after the values bound so far, so its placeholders continue from them
+            let bound = arguments.values.len();
+            let mut qb = QueryBuilder::with_arguments("(", arguments);
+            push_row(qb.separated(", "), row);
+            qb.push(")");
+            arguments = qb
+                .build()
+                .take_arguments()
+                .map_err(Error::Encode)?
+                .unwrap_or_default();
+            let row = qb.into_string();
+
+            if arguments.values.len() > MAX_PARAMETERS && !values.is_empty() {
+                let row_arguments = MssqlArguments {
+                    values: arguments.values.split_off(bound),
+                };
+                total += insert_values(&mut tx, insert, &values, arguments).await?;
+                values.clear();
+
+                values.push(shift_placeholders(&row, bound));
+                arguments = row_arguments;
+            } else {
+                values.push(row);
+            }
+        }
+
+        total += insert_values(&mut tx, insert, &values, arguments).await?;
+
+        tx.commit().await?;
+
+        Ok(total)
+    }
+
+    /// Write the bytes of `reader` into the `VARBINARY(MAX)` column `column` of the row of
+    /// `table` whose `key_column` equals `key`, without holding the whole value in memory.
+    ///
+    /// Parameters are always sent in one piece, so a bound `Vec<u8>` has to be complete
+    /// before the query starts. This instead sets the column to an empty value and appends
+    /// the data 1 MiB at a time with `UPDATE ... SET column.WRITE(@chunk, NULL, NULL)`,
+    /// which SQL Server logs minimally for large values. The length of the data doesn't
+    /// need to be known up front. Returns the number of bytes written.
+    ///
+    /// All statements run in one transaction (a savepoint if one is already open), so if
+    /// reading or writing fails, the column keeps its previous value.
+    ///
+    /// `table` may be qualified and is quoted like `column` and `key_column`. Returns
+    /// [`Error::RowNotFound`] if no row has the key, and [`Error::InvalidArgument`] if
+    /// several do.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// # let file: &[u8] = &[];
+    /// // `file` is any `futures::io::AsyncRead`, e.g. a `tokio::fs::File` behind
+    /// // `tokio_util::compat`
+    /// let written = conn
+    ///     .write_binary_stream("dbo.documents", "content", "id", 42i32, file)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_binary_stream<K, R>(
+        &mut self,
+        table: &str,
+        column: &str,
+        key_column: &str,
+        key: K,
+        reader: R,
+    ) -> Result<u64, Error>
+    where
+        K: for<'q> Encode<'q, Mssql> + Type<Mssql> + Clone + Send,
+        R: AsyncRead + Unpin + Send,
+    {
+        self.write_stream(table, column, key_column, key, reader, false)
+            .await
+    }
+
+    /// Like [`write_binary_stream`](Self::write_binary_stream), but for UTF-8 text written
+    /// into an `NVARCHAR(MAX)` or `VARCHAR(MAX)` column.
+    ///
+    /// Chunks are split between characters. Text that isn't valid UTF-8 fails with an
+    /// [`Error::Io`] of kind [`InvalidData`](std::io::ErrorKind::InvalidData), and the
+    /// column keeps its previous value.
+    pub async fn write_text_stream<K, R>(
+        &mut self,
+        table: &str,
+        column: &str,
+        key_column: &str,
+        key: K,
+        reader: R,
+    ) -> Result<u64, Error>
+    where
+        K: for<'q> Encode<'q, Mssql> + Type<Mssql> + Clone + Send,
+        R: AsyncRead + Unpin + Send,
+    {
+        self.write_stream(table, column, key_column, key, reader, true)
+            .await
+    }
+
+    async fn write_stream<K, R>(
+        &mut self,
+        table: &str,
+        column: &str,
+        key_column: &str,
+        key: K,
+        mut reader: R,
+        text: bool,
+    ) -> Result<u64, Error>
+    where
+        K: for<'q> Encode<'q, Mssql> + Type<Mssql> + Clone + Send,
+        R: AsyncRead + Unpin + Send,
+    {
+        let table = quote_object_name(table)?;
+        let column = quote_identifier(column);
+        let key_column = quote_identifier(key_column);
+
+        // `.WRITE` can't append to NULL, so start from an empty value.
+        let empty = if text { "N''" } else { "0x" };
+        let clear = format!(
+            "UPDATE {table} SET {column} = {empty} WHERE {key_column} = @p1; SELECT @@ROWCOUNT"
+        );
+        let append =
+            format!("UPDATE {table} SET {column}.WRITE(@p1, NULL, NULL) WHERE {key_column} = @p2");
+
+        let mut tx: Transaction<'_, Mssql> = Transaction::begin(self, None).await?;
+
+        let rows: i32 = query_scalar(AssertSqlSafe(clear))
+            .bind(key.clone())
+            .fetch_one(&mut *tx)
+            .await?;
+        match rows {
+            0 => return Err(Error::RowNotFound),
+            1 => {}
+            rows => {
+                return Err(Error::InvalidArgument(format!(
+                    "{rows} rows of {table} have the given {key_column}"
+                )))
+            }
+        }
+
+        let mut buf = vec![0; LOB_CHUNK_SIZE];
+        let mut pending = 0;
+        let mut total = 0;
+
+        loop {
+            let mut filled = pending;
+            let mut eof = false;
+            while filled < buf.len() {
+                let n = reader.read(&mut buf[filled..]).await?;
+                if n == 0 {
+                    eof = true;
+                    break;
+                }
+                filled += n;
+            }
+            total += (filled - pending) as u64;
+
+            let len = if text {
+                utf8_chunk_len(&buf[..filled], eof)?
+            } else {
+                filled
+            };
+
+            if len > 0 {
+                let query = query(AssertSqlSafe(append.clone()));
+                let query = if text {
+                    let chunk = String::from_utf8(buf[..len].to_vec()).map_err(|error| {
+                        std::io::Error::new(std::io::ErrorKind::InvalidData, error)
+                    })?;
+                    query.bind(chunk)
+                } else {
+                    query.bind(buf[..len].to_vec())
+                };
+                query.bind(key.clone()).execute(&mut *tx).await?;
+            }
+
+            if eof {
+                break;
+            }
+
+            buf.copy_within(len..filled, 0);
+            pending = filled - len;
+        }
+
+        tx.commit().await?;
+
+        Ok(total)
+    }
+
+    /// Execute a query or batch, discarding any rows it returns, and return the total
+    /// number of rows affected.
+    ///
+    /// Meant for setup scripts and DDL batches whose results don't matter. Rows are read
+    /// off the wire and dropped as they arrive, so a stray `SELECT` in the batch costs
+    /// network time but no memory. The count is aggregated as described for
+    /// [`execute_many`][Executor::execute_many]: per statement for parameterized queries,
+    /// per result set for plain batches.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// conn.execute_discard(
+    ///     "CREATE TABLE #staging (id INT NOT NULL); \
+    ///      INSERT INTO #staging (id) SELECT object_id FROM sys.objects; \
+    ///      SELECT * FROM #staging;",
+    /// )
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn execute_discard<'q, E>(&mut self, query: E) -> Result<u64, Error>
+    where
+        E: Execute<'q, Mssql> + 'q,
+    {
+        self.execute_many(query)
+            .try_fold(0, |total, result| async move {
+                Ok(total + result.rows_affected())
+            })
+            .await
+    }
+
+    /// Count the rows a `SELECT` returns, e.g. the total for a paginated listing, by running
+    /// it as `SELECT COUNT_BIG(*) FROM (<sql>) AS _c` with the same `arguments`.
+    ///
+    /// A trailing `ORDER BY` is removed, since SQL Server rejects it in a derived table and
+    /// it doesn't change the count. It is kept if it has `OFFSET ... FETCH`, or if the query
+    /// starts with `SELECT TOP`, as both limit the rows counted. A trailing `;` is ignored,
+    /// an `OPTION (...)` clause is moved to the outer query, and a leading `WITH` clause
+    /// (common table expressions) stays in front of it.
+    ///
+    /// Like any derived table, every column of `sql` must have a unique name, so alias
+    /// expressions and columns selected from several tables with the same name.
+    ///
+    /// Returns [`Error::InvalidArgument`] if `sql` is empty or holds more than one statement.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// use sqlx::mssql::MssqlArguments;
+    /// use sqlx::Arguments;
+    ///
+    /// let sql = "SELECT id, name FROM users WHERE active = @p1 ORDER BY name";
+    ///
+    /// let mut arguments = MssqlArguments::default();
+    /// arguments.add(true).map_err(sqlx::Error::Encode)?;
+    /// let total = conn.count_query(sql, arguments.clone()).await?;
+    ///
+    /// let page = sqlx::query_with(
+    ///     sqlx::AssertSqlSafe(format!("{sql} OFFSET 0 ROWS FETCH NEXT 20 ROWS ONLY")),
+    ///     arguments,
+    /// )
+    /// .fetch_all(&mut *conn)
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn count_query(
+        &mut self,
+        sql: impl SqlSafeStr,
+        arguments: MssqlArguments,
+    ) -> Result<i64, Error> {
+        let sql = count_statement(sql.into_sql_str().as_str())?;
+
+        query_scalar_with(AssertSqlSafe(sql), arguments)
+            .fetch_one(&mut *self)
+            .await
+    }
+
+    /// Check that the session is still using the database it was configured with.
+    ///
+    /// Issues `SELECT DB_NAME()` and compares the result (case-insensitively) against the
+    /// database from [`MssqlConnectOptions::database`], or the last database switched to with
+    /// [`use_database`](Self::use_database). Returns an error if they differ, e.g. because
+    /// a stray `USE OtherDb` leaked into the session.
+    ///
+    /// If no database was configured, the login's default database is in use and there is
+    /// nothing to compare against, so this always succeeds.
+    pub async fn verify_database(&mut self) -> Result<(), Error> {
+        let Some(expected) = self.inner.database.clone() else {
+            return Ok(());
+        };
+
+        let actual: Option<String> = query_scalar("SELECT DB_NAME()")
+            .fetch_one(&mut *self)
+            .await?;
+
+        match actual {
+            Some(actual) if actual.eq_ignore_ascii_case(&expected) => Ok(()),
+            actual => Err(Error::Protocol(format!(
+                "connection is using database {actual:?}, expected {expected:?}"
+            ))),
+        }
+    }
+
+    /// Execute `sql` and return its rows together with the server's execution statistics.
+    ///
+    /// The statistics are the logical and physical reads and the CPU time reported by
+    /// `SET STATISTICS IO` / `SET STATISTICS TIME`. The driver does not receive the
+    /// informational messages those settings produce, so they are instead computed from this
+    /// session's counters in `sys.dm_exec_sessions`, sampled before and after the query. The
+    /// sampling queries themselves contribute a negligible amount of CPU time.
+    ///
+    /// The server's elapsed time is not available that way; [`MssqlStats::wall_time`] is the
+    /// time the client waited for the query and its rows instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// let (rows, stats) = conn.fetch_with_stats("SELECT * FROM users").await?;
+    ///
+    /// println!(
+    ///     "{} rows, {} logical reads, {:?} CPU",
+    ///     rows.len(),
+    ///     stats.logical_reads(),
+    ///     stats.cpu_time()
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_with_stats(
+        &mut self,
+        sql: impl SqlSafeStr,
+    ) -> Result<(Vec<MssqlRow>, MssqlStats), Error> {
+        let before: SessionCounters = query_as::<_, (i64, i64, i64)>(SessionCounters::QUERY)
+            .fetch_one(&mut *self)
+            .await?
+            .into();
+
+        let start = Instant::now();
+        let rows = self.fetch_all(sql.into_sql_str()).await?;
+        let wall_time = start.elapsed();
+
+        let after: SessionCounters = query_as::<_, (i64, i64, i64)>(SessionCounters::QUERY)
+            .fetch_one(&mut *self)
+            .await?
+            .into();
+
+        Ok((rows, after.since(&before, wall_time)))
+    }
+
+    /// Execute a query or batch and return the rows of each of its result sets separately.
+    ///
+    /// A batch or stored procedure can return several result sets in one round-trip, e.g.
+    /// `SELECT * FROM users; SELECT * FROM orders`. Result sets without rows are kept as
+    /// empty `Vec`s, so indices match the order of the `SELECT`s. A batch that returns no
+    /// result set at all yields a single empty set.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// let sets = conn
+    ///     .fetch_result_sets(sqlx::query("EXEC get_user_with_orders @p1").bind(42i32))
+    ///     .await?;
+    ///
+    /// let (users, orders) = (&sets[0], &sets[1]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_result_sets<'q, E>(&mut self, query: E) -> Result<Vec<Vec<MssqlRow>>, Error>
+    where
+        E: Execute<'q, Mssql> + 'q,
+    {
+        let mut sets = Vec::new();
+        let mut current = Vec::new();
+
+        let mut results = self.fetch_many(query);
+
+        while let Some(result) = results.try_next().await? {
+            match result {
+                Either::Left(_) => sets.push(std::mem::take(&mut current)),
+                Either::Right(row) => current.push(row),
+            }
+        }
+
+        Ok(sets)
+    }
+
+    /// Call a stored procedure, returning its result sets, the values of its `OUTPUT`
+    /// parameters and its return code.
+    ///
+    /// The call runs as one batch: the `OUTPUT` parameters are declared as variables,
+    /// passed to `EXEC @return = procedure ...`, and selected along with the return code
+    /// once the procedure finishes. That last result set is not part of
+    /// [`result_sets`][MssqlProcedureResult::result_sets].
+    ///
+    /// See [`MssqlProcedureParams`] for an example.
+    pub async fn call_procedure(
+        &mut self,
+        name: &str,
+        params: MssqlProcedureParams,
+    ) -> Result<MssqlProcedureResult, Error> {
+        let (sql, arguments) = params.into_batch(name)?;
+
+        let mut result_sets = self
+            .fetch_result_sets(query_with_result(AssertSqlSafe(sql), arguments))
+            .await?;
+
+        let outputs = result_sets
+            .pop()
+            .and_then(|mut set| set.pop())
+            .ok_or_else(|| {
+                Error::Protocol("stored procedure call returned no output values".into())
+            })?;
+        let return_value = outputs.try_get(0)?;
+
+        Ok(MssqlProcedureResult {
+            result_sets,
+            return_value,
+            outputs,
+        })
+    }
+
+    /// Execute a query or batch returning two result sets and map them to `A` and `B`.
+    ///
+    /// Typical for stored procedures returning related data in one round-trip. Returns
+    /// [`Error::Protocol`] if fewer than two result sets are returned; any further result
+    /// sets are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// // (id, name) and (id, user_id); any `FromRow` types work, e.g. derived structs
+    /// let (users, orders) = conn
+    ///     .fetch_two::<(i32, String), (i32, i32), _>(
+    ///         "SELECT id, name FROM users; SELECT id, user_id FROM orders",
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_two<'q, A, B, E>(&mut self, query: E) -> Result<(Vec<A>, Vec<B>), Error>
+    where
+        A: for<'r> FromRow<'r, MssqlRow>,
+        B: for<'r> FromRow<'r, MssqlRow>,
+        E: Execute<'q, Mssql> + 'q,
+    {
+        let sets = self.fetch_result_sets(query).await?;
+        let count = sets.len();
+        let mut sets = sets.into_iter();
+
+        let (Some(first), Some(second)) = (sets.next(), sets.next()) else {
+            return Err(Error::Protocol(format!(
+                "expected 2 result sets, but the query returned {count}"
+            )));
+        };
+
+        let first = first.iter().map(A::from_row).collect::<Result<_, _>>()?;
+        let second = second.iter().map(B::from_row).collect::<Result<_, _>>()?;
+
+        Ok((first, second))
+    }
+
+    /// Execute a query and collect its rows into a map from the first column to the second.
+    ///
+    /// Meant for loading lookup tables, e.g. to cache reference data. Further columns are
+    /// ignored. A key that appears in more than one row fails with [`Error::Decode`] rather
+    /// than silently keeping one of the values; deduplicate in SQL (`GROUP BY`, `DISTINCT`)
+    /// if the data can contain repeated keys.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// use std::collections::HashMap;
+    ///
+    /// let countries: HashMap<i32, String> =
+    ///     conn.fetch_map("SELECT id, name FROM countries").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_map<'q, K, V, E>(&mut self, query: E) -> Result<HashMap<K, V>, Error>
+    where
+        K: for<'r> Decode<'r, Mssql> + Type<Mssql> + Eq + Hash,
+        V: for<'r> Decode<'r, Mssql> + Type<Mssql>,
+        E: Execute<'q, Mssql> + 'q,
+    {
+        let mut map = HashMap::new();
+        let mut rows = self.fetch(query);
+
+        while let Some(row) = rows.try_next().await? {
+            let key: K = row.try_get(0)?;
+            let value: V = row.try_get(1)?;
+
+            if map.insert(key, value).is_some() {
+                return Err(Error::Decode(
+                    format!("duplicate key in row {} of fetch_map", map.len() + 1).into(),
+                ));
+            }
+        }
+
+        Ok(map)
+    }
+
+    /// Execute a query and collect the first column of its rows into a set.
+    ///
+    /// Further columns are ignored, and repeated values are kept once.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// use std::collections::HashSet;
+    ///
+    /// let blocked: HashSet<String> = conn.fetch_set("SELECT email FROM blocked_users").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_set<'q, T, E>(&mut self, query: E) -> Result<HashSet<T>, Error>
+    where
+        T: for<'r> Decode<'r, Mssql> + Type<Mssql> + Eq + Hash,
+        E: Execute<'q, Mssql> + 'q,
+    {
+        let mut set = HashSet::new();
+        let mut rows = self.fetch(query);
+
+        while let Some(row) = rows.try_next().await? {
+            set.insert(row.try_get(0)?);
+        }
+
+        Ok(set)
+    }
+
+    /// Execute a query and return the columns of its first result set along with a stream
+    /// of that result set's rows.
+    ///
+    /// The columns are read from the result set's metadata, so they are available before
+    /// the first row is polled and even when the query returns no rows, e.g. to render the
+    /// headers of a result grid right away. Unlike [`describe`][Executor::describe], this
+    /// runs the actual query instead of `sp_describe_first_result_set`. If the query
+    /// returns no result set, the columns are empty. Rows of any further result sets are
+    /// not included, but the row stream only ends once they were read, and fails if a later
+    /// statement does.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// use futures_util::TryStreamExt;
+    /// use sqlx::Column;
+    ///
+    /// let (columns, mut rows) = conn.fetch_with_columns("SELECT id, name FROM users").await?;
+    ///
+    /// let headers: Vec<&str> = columns.iter().map(|c| c.name()).collect();
+    ///
+    /// while let Some(row) = rows.try_next().await? {
+    ///     // ...
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_with_columns<'e, 'q, E>(
+        &'e mut self,
+        mut query: E,
+    ) -> Result<(Vec<MssqlColumn>, BoxStream<'e, Result<MssqlRow, Error>>), Error>
+    where
+        E: Execute<'q, Mssql> + 'q,
+    {
+        let arguments = query.take_arguments().map_err(Error::Encode)?;
+        let sql = query.sql();
+
+        let mut stream = self.run_stream_with_columns(sql, arguments);
+
+        // Only the query results of statements without a result set can come first
+        let mut columns = Vec::new();
+        while let Some(step) = stream.try_next().await? {
+            if let Either::Left(first) = step {
+                columns = first.to_vec();
+                break;
+            }
+        }
+
+        let rows = TryAsyncStream::new(move |yielder| async move {
+            // The rows of the first result set end with its query result or the next columns
+            while let Some(Either::Right(Either::Right(row))) = stream.try_next().await? {
+                yielder.r#yield(row).await;
+            }
+
+            // The statements after it still run; their rows are skipped but not their errors
+            while stream.try_next().await?.is_some() {}
+
+            Ok(())
+        });
+
+        Ok((columns, Box::pin(rows)))
+    }
+
+    /// Return the features supported by the connected server.
+    ///
+    /// The server version is queried on the first call and cached for the lifetime of the
+    /// connection.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// let sql = if conn.server_capabilities().await?.supports_string_agg() {
+    ///     "SELECT STRING_AGG(name, ',') FROM users"
+    /// } else {
+    ///     "SELECT STUFF((SELECT ',' + name FROM users FOR XML PATH('')), 1, 1, '')"
+    /// };
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn server_capabilities(&mut self) -> Result<MssqlServerCapabilities, Error> {
+        if let Some(capabilities) = self.inner.server_capabilities {
+            return Ok(capabilities);
+        }
+
+        let (product_version, engine_edition): (String, i32) =
+            query_as(MssqlServerCapabilities::QUERY)
+                .fetch_one(&mut *self)
+                .await?;
+
+        let capabilities = MssqlServerCapabilities::new(&product_version, engine_edition)?;
+        self.inner.server_capabilities = Some(capabilities);
+
+        Ok(capabilities)
+    }
+
+    /// Return the encryption actually negotiated for this connection.
+    ///
+    /// This may be weaker than the requested [`ssl_mode`][MssqlConnectOptions::ssl_mode]:
+    /// with `Preferred` the session is unencrypted if the server does not support TLS. The
+    /// level is read from `CONNECTIONPROPERTY('encrypt_option')` on the first call and cached
+    /// for the lifetime of the connection.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// use sqlx::mssql::MssqlEncryptionLevel;
+    ///
+    /// if conn.encryption_level().await? != MssqlEncryptionLevel::Encrypted {
+    ///     panic!("refusing to send credentials over an unencrypted connection");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn encryption_level(&mut self) -> Result<MssqlEncryptionLevel, Error> {
+        if let Some(level) = self.inner.encryption_level {
+            return Ok(level);
+        }
+
+        let encrypt_option: String =
+            query_scalar("SELECT CAST(CONNECTIONPROPERTY('encrypt_option') AS NVARCHAR(10))")
+                .fetch_one(&mut *self)
+                .await?;
+
+        let level = if encrypt_option.eq_ignore_ascii_case("TRUE") {
+            MssqlEncryptionLevel::Encrypted
+        } else {
+            MssqlEncryptionLevel::NotEncrypted
+        };
+        self.inner.encryption_level = Some(level);
+
+        Ok(level)
+    }
+
+    /// Return how long a query on this connection may wait on the server, if limited.
+    pub fn command_timeout(&self) -> Option<Duration> {
+        self.inner.command_timeout
+    }
+
+    /// Override [`MssqlConnectOptions::command_timeout`] for the following queries on this
+    /// connection; `None` removes the limit.
+    ///
+    /// The setting stays with the connection, also when it is returned to a pool, so restore
+    /// the previous value after a query that needs a different limit.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// use std::time::Duration;
+    /// use sqlx::Executor;
+    ///
+    /// let previous = conn.command_timeout();
+    /// conn.set_command_timeout(Some(Duration::from_secs(600)));
+    /// let result = conn.execute("EXEC dbo.nightly_rollup").await;
+    /// conn.set_command_timeout(previous);
+    /// result?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_command_timeout(&mut self, timeout: Option<Duration>) {
+        self.inner.command_timeout = timeout.filter(|timeout| !timeout.is_zero());
+    }
+
+    /// Return the server process id (`@@SPID`) of this connection's session.
+    ///
+    /// Read on the first call and cached for the lifetime of the connection. Pass it to
+    /// [`current_request_info`](Self::current_request_info) on another connection to see what
+    /// this one is running.
+    pub async fn session_id(&mut self) -> Result<i16, Error> {
+        if let Some(session_id) = self.inner.session_id {
+            return Ok(session_id);
+        }
+
+        let session_id: i16 = query_scalar("SELECT @@SPID").fetch_one(&mut *self).await?;
+        self.inner.session_id = Some(session_id);
+
+        Ok(session_id)
+    }
+
+    /// Return the request session `session_id` is currently running, or `None` if the session
+    /// is idle or does not exist.
+    ///
+    /// Reads `sys.dm_exec_requests` and the request's SQL text, including what it waits on
+    /// and which session blocks it. A connection can only run one request at a time, so this
+    /// is meant to be called from a separate diagnostic connection, with the id a busy
+    /// connection reported through [`session_id`](Self::session_id); called with its own
+    /// session id, a connection only sees this query. Seeing other sessions requires the
+    /// `VIEW SERVER STATE` permission (`VIEW SERVER PERFORMANCE STATE` on SQL Server 2022).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(pool: &sqlx::mssql::MssqlPool) -> sqlx::Result<()> {
+    /// let mut worker = pool.acquire().await?;
+    /// let session_id = worker.session_id().await?;
+    ///
+    /// // ... while `worker` runs a slow query:
+    /// let mut diagnostics = pool.acquire().await?;
+    /// if let Some(request) = diagnostics.current_request_info(session_id).await? {
+    ///     if let Some(blocker) = request.blocking_session_id() {
+    ///         eprintln!(
+    ///             "session {session_id} waits on {:?} held by session {blocker}: {:?}",
+    ///             request.wait_type(),
+    ///             request.statement(),
+    ///         );
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn current_request_info(
+        &mut self,
+        session_id: i16,
+    ) -> Result<Option<MssqlRequestInfo>, Error> {
+        let row: Option<RequestInfoRow> = query_as(MssqlRequestInfo::QUERY)
+            .bind(session_id)
+            .fetch_optional(&mut *self)
+            .await?;
+
+        Ok(row.map(MssqlRequestInfo::from))
+    }
+
+    /// Return the graph of the most recent deadlock the server recorded, or `None` if it has
+    /// none.
+    ///
+    /// Reads the `xml_deadlock_report` events of the built-in `system_health` extended-events
+    /// session from its ring buffer, and returns the `<deadlock>` element. The element lists
+    /// the victim, the processes with their statements, and the locks they held and waited
+    /// for. The session delivers events to the ring buffer asynchronously, so a deadlock
+    /// that just happened can take a while to show up. The ring buffer only keeps recent
+    /// events. Azure SQL Database has no `system_health` session, so there it returns `None`.
+    ///
+    /// Requires the `VIEW SERVER STATE` permission (`VIEW SERVER PERFORMANCE STATE` on SQL
+    /// Server 2022). Without it the server refuses the query with error 300; check for it with
+    /// [`MssqlDatabaseError::is_permission_denied`][crate::MssqlDatabaseError::is_permission_denied].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// if let Some(graph) = conn.last_deadlock_graph().await? {
+    ///     eprintln!("last deadlock: {}", graph.as_ref());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn last_deadlock_graph(&mut self) -> Result<Option<MssqlXml>, Error> {
+        query_scalar(DEADLOCK_GRAPH_QUERY)
+            .fetch_optional(&mut *self)
+            .await
+    }
+
+    /// Prepare several statements at once, returning one result per statement in the same
+    /// order.
+    ///
+    /// [`prepare`][Executor::prepare] describes each statement's result columns with its own
+    /// `sp_describe_first_result_set` call. This describes up to 1000 statements per
+    /// round-trip through `sys.dm_exec_describe_first_result_set` instead, which reports a
+    /// statement it can't describe, e.g. one naming a missing table, as an error for that
+    /// statement alone; the others are still prepared.
+    ///
+    /// The statements are added to the connection's statement cache (see
+    /// [`MssqlConnectOptions::statement_cache_capacity`]), so a later `prepare` of the same
+    /// SQL needs no round-trip, and statements already cached aren't described again. Like
+    /// `prepare`, only the result columns are described.
+    ///
+    /// The outer `Result` is an error only if the describe query itself fails, e.g. because
+    /// the connection was lost.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// use sqlx::SqlSafeStr;
+    ///
+    /// let statements = conn
+    ///     .prepare_many(&[
+    ///         "SELECT id, name FROM users WHERE id = @p1".into_sql_str(),
+    ///         "SELECT id, total FROM orders WHERE user_id = @p1".into_sql_str(),
+    ///     ])
+    ///     .await?;
+    ///
+    /// for statement in statements {
+    ///     let statement = statement?;
+    ///     // ...
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn prepare_many(
+        &mut self,
+        sqls: &[SqlStr],
+    ) -> Result<Vec<Result<MssqlStatement, Error>>, Error> {
+        let mut results: Vec<Option<Result<MssqlStatementMetadata, Error>>> = sqls
+            .iter()
+            .map(|sql| {
+                self.inner
+                    .cache_statement
+                    .get_mut(sql.as_str())
+                    .cloned()
+                    .map(Ok)
+            })
+            .collect();
+
+        let uncached: Vec<usize> = (0..sqls.len()).filter(|&i| results[i].is_none()).collect();
+
+        for chunk in uncached.chunks(values_chunk_size(1)) {
+            let chunk_sqls: Vec<&SqlStr> = chunk.iter().map(|&i| &sqls[i]).collect();
+            let described = self.describe_first_result_sets(&chunk_sqls).await?;
+
+            for (&i, metadata) in chunk.iter().zip(described) {
+                if let Ok(metadata) = &metadata {
+                    if self.inner.cache_statement.is_enabled() {
+                        self.inner
+                            .cache_statement
+                            .insert(sqls[i].as_str(), metadata.clone());
+                    }
+                }
+                results[i] = Some(metadata);
+            }
+        }
+
+        // every statement was either cached or described above
+        Ok(sqls
+            .iter()
+            .zip(results.into_iter().flatten())
+            .map(|(sql, metadata)| {
+                metadata.map(|metadata| MssqlStatement {
+                    sql: sql.clone(),
+                    metadata,
+                })
+            })
+            .collect())
+    }
+
+    /// Return `@@ROWCOUNT` as left by the last statement executed on this connection.
+    ///
+    /// This is the row count of the *last statement* of the previous query or batch, unlike
+    /// [`MssqlQueryResult::rows_affected`][crate::MssqlQueryResult::rows_affected], which
+    /// aggregates the whole batch. For example, after
+    /// `INSERT INTO t VALUES (1), (2), (3); DELETE FROM t WHERE id = 1` this returns `1`.
+    ///
+    /// To act on the count of a statement in the middle of a batch, read `@@ROWCOUNT`
+    /// directly after that statement within the batch instead (e.g.
+    /// `UPDATE ...; IF @@ROWCOUNT = 0 INSERT ...`), since every statement, including
+    /// `SET` and `IF`, resets it.
+    ///
+    /// With [`capture_last_insert_id`][crate::MssqlConnectOptions::capture_last_insert_id]
+    /// enabled, the count is the one read by the `SELECT` appended to an `INSERT`, not that
+    /// of the `SELECT` itself. The `ROLLBACK` of a dropped transaction runs after this
+    /// reads `@@ROWCOUNT`, so it doesn't reset the count either.
+    pub async fn last_rowcount(&mut self) -> Result<i64, Error> {
+        if let Some(rowcount) = self.inner.captured_rowcount {
+            return Ok(rowcount);
+        }
+
+        self.check_timed_out()?;
+
+        // Sent directly, ahead of `resolve_pending_rollback`, and as a plain batch so no
+        // parameterized wrapper runs before it.
+        let row = self
+            .inner
+            .client
+            .simple_query("SELECT ROWCOUNT_BIG()")
+            .await
+            .map_err(tiberius_err)?
+            .into_row()
+            .await
+            .map_err(tiberius_err)?;
+
+        row.and_then(|row| row.get(0))
+            .ok_or_else(|| Error::Protocol("SELECT ROWCOUNT_BIG() returned no value".into()))
+    }
+
+    /// Run a `DBCC` command and return the rows it produces.
+    ///
+    /// `command` is everything after the `DBCC` keyword, e.g. `"SQLPERF(LOGSPACE)"` or
+    /// `"SHOW_STATISTICS ('dbo.orders', ix_orders_customer)"`; a leading `DBCC` is accepted
+    /// too. `NO_INFOMSGS` is added to the command's `WITH` options (or a `WITH` clause is
+    /// appended) to suppress the informational "DBCC execution completed" messages.
+    ///
+    /// The output format differs per command, so rows are returned as-is for the caller to
+    /// interpret. Commands that only print messages return no rows, and commands returning
+    /// several result sets (like `SHOW_STATISTICS`) have them concatenated; use
+    /// [`fetch_result_sets`](Self::fetch_result_sets) to keep them apart.
+    ///
+    /// Returns [`Error::InvalidArgument`] if `command` is empty or contains a statement
+    /// terminator or comment.
+    ///
+    /// ### Note
+    /// The command is executed as raw SQL. Never build it from untrusted input.
+    pub async fn dbcc(&mut self, command: &str) -> Result<Vec<MssqlRow>, Error> {
+        let sql = dbcc_statement(command)?;
+        self.fetch_all(AssertSqlSafe(sql)).await
+    }
+
+    /// Drop all local temporary (`#temp`) tables created by this session.
+    ///
+    /// Global `##temp` tables are not touched.
+    pub async fn drop_temp_tables(&mut self) -> Result<(), Error> {
+        // Local temp tables are stored in tempdb under their name padded with `_` and a
+        // 12-character suffix; `OBJECT_ID` on the short name only resolves this session's
+        // table. A name may end in `_` itself, so each length between the name without any
+        // trailing `_` and the full padded name is tried. Table variables and cached temp
+        // objects have short names without a suffix and match none of them.
+        self.execute(
+            "DECLARE @sql NVARCHAR(MAX) = N''; \
+             SELECT @sql += N'DROP TABLE ' + QUOTENAME(c.short_name) + N';' \
+             FROM tempdb.sys.tables AS t \
+             CROSS APPLY (SELECT LEFT(t.name, \
+                 IIF(LEN(t.name) > 12, LEN(t.name) - 12, 0)) AS padded) AS p \
+             CROSS APPLY (SELECT TOP (1) s.short_name \
+                 FROM (SELECT TOP (116) ROW_NUMBER() OVER (ORDER BY (SELECT NULL)) - 1 AS n \
+                     FROM sys.all_columns) AS k \
+                 CROSS APPLY (SELECT LEFT(p.padded, \
+                     IIF(LEN(p.padded) > k.n, LEN(p.padded) - k.n, 0)) AS short_name) AS s \
+                 WHERE k.n < PATINDEX(N'%[^_]%', REVERSE(p.padded)) \
+                 AND OBJECT_ID(N'tempdb..' + QUOTENAME(s.short_name)) = t.object_id \
+                 ORDER BY k.n DESC) AS c \
+             WHERE t.name LIKE N'#%' AND t.name NOT LIKE N'##%'; \
+             EXEC sp_executesql @sql",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Switch the session to another database with `USE`.
+    ///
+    /// Unlike executing `USE` directly, this also updates the database that
+    /// [`verify_database`](Self::verify_database) expects, so intentionally switched
+    /// connections are not treated as leaked session state.
+    pub async fn use_database(&mut self, database: &str) -> Result<(), Error> {
+        let sql = format!("USE {}", quote_identifier(database));
+        self.execute(AssertSqlSafe(sql)).await?;
+        self.inner.database = Some(database.to_owned());
+        Ok(())
+    }
+}
+
+/// The newest `xml_deadlock_report` in the ring buffer of the `system_health` session, see
+/// [`MssqlConnection::last_deadlock_graph`].
+const DEADLOCK_GRAPH_QUERY: &str = "SELECT TOP (1) \
+     CAST(e.event.query('(data[@name=\"xml_report\"]/value/deadlock)[1]') AS NVARCHAR(MAX)) \
+     FROM (SELECT CAST(t.target_data AS XML) AS target_data \
+         FROM sys.dm_xe_session_targets t \
+         JOIN sys.dm_xe_sessions s ON s.address = t.event_session_address \
+         WHERE s.name = N'system_health' AND t.target_name = N'ring_buffer') AS b \
+     CROSS APPLY b.target_data.nodes('RingBufferTarget/event[@name=\"xml_deadlock_report\"]') \
+         AS e(event) \
+     ORDER BY e.event.value('@timestamp', 'datetimeoffset') DESC";
+
+/// End a [`MssqlConnection::bulk_copy`] that failed midway, so `destination` stays usable,
+/// and return the `error` that stopped it.
+/// Run one `INSERT` of [`MssqlConnection::insert_chunked`], returning the rows it inserted.
+async fn insert_values(
+    conn: &mut MssqlConnection,
+    insert: &str,
+    values: &[String],
+    arguments: MssqlArguments,
+) -> Result<u64, Error> {
+    let sql = format!("{insert}VALUES {}", values.join(", "));
+    let result = query_with(AssertSqlSafe(sql), arguments)
+        .execute(conn)
+        .await?;
+
+    Ok(result.rows_affected())
+}
+
+async fn abort_bulk_copy(bulk: MssqlBulkInsert<'_>, error: Error) -> Result<u64, Error> {
+    // If ending the load fails too, the connection is broken and its next use reports that;
+    // the original error is the more useful one here.
+    let _ = bulk.abort().await;
+    Err(error)
+}
+
+/// How much of a stream [`MssqlConnection::write_binary_stream`] and
+/// [`MssqlConnection::write_text_stream`] append per statement.
+const LOB_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// The length of the longest prefix of `bytes` that ends on a character boundary. At the
+/// end of the stream all of `bytes` must be valid.
+fn utf8_chunk_len(bytes: &[u8], eof: bool) -> Result<usize, Error> {
+    match std::str::from_utf8(bytes) {
+        Ok(_) => Ok(bytes.len()),
+        // a character split by the chunk boundary, completed by the next chunk
+        Err(error) if error.error_len().is_none() && !eof => Ok(error.valid_up_to()),
+        Err(error) => Err(Error::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            error,
+        ))),
+    }
+}
+
+/// Session settings for [`MssqlConnection::begin_with_options`]; `None` leaves a setting
+/// unchanged.
+struct TransactionSettings {
+    isolation: Option<MssqlIsolationLevel>,
+    lock_timeout: Option<i32>,
+    deadlock_priority: Option<i32>,
+}
+
+impl TransactionSettings {
+    fn is_empty(&self) -> bool {
+        self.isolation.is_none() && self.lock_timeout.is_none() && self.deadlock_priority.is_none()
+    }
+
+    /// The values of `self` for just the settings that `changes` sets.
+    fn restoring(&self, changes: &Self) -> Self {
+        Self {
+            isolation: changes.isolation.and(self.isolation),
+            lock_timeout: changes.lock_timeout.and(self.lock_timeout),
+            deadlock_priority: changes.deadlock_priority.and(self.deadlock_priority),
+        }
+    }
+
+    fn to_sql(&self) -> String {
+        let mut sql = String::new();
+        if let Some(level) = self.isolation {
+            sql.push_str(&format!("SET TRANSACTION ISOLATION LEVEL {level}; "));
+        }
+        if let Some(timeout) = self.lock_timeout {
+            sql.push_str(&format!("SET LOCK_TIMEOUT {timeout}; "));
+        }
+        if let Some(priority) = self.deadlock_priority {
+            sql.push_str(&format!("SET DEADLOCK_PRIORITY {priority}; "));
+        }
+        sql
+    }
+}
+
+/// Build the statement for [`MssqlConnection::count_query`].
+fn count_statement(sql: &str) -> Result<String, Error> {
+    let sql = sql.trim_end().trim_end_matches(';').trim_end();
+    let (words, semicolon) = top_level_words(sql);
+
+    if words.is_empty() {
+        return Err(Error::InvalidArgument("count_query SQL is empty".into()));
+    }
+    if semicolon {
+        return Err(Error::InvalidArgument(
+            "count_query SQL must be a single statement".into(),
+        ));
+    }
+
+    let is = |i: usize, keyword: &str| {
+        words
+            .get(i)
+            .is_some_and(|word| sql[word.clone()].eq_ignore_ascii_case(keyword))
+    };
+
+    // Common table expressions must precede the outer `SELECT`, and their bodies are
+    // parenthesized, so the first top-level `SELECT` after `WITH` starts the query.
+    let mut first = 0;
+    if is(0, "WITH") {
+        first = (1..words.len())
+            .find(|&i| is(i, "SELECT"))
+            .ok_or_else(|| Error::InvalidArgument("count_query SQL has no SELECT".into()))?;
+    }
+
+    let mut end = words.len();
+    let mut body_end = sql.len();
+    let mut option = "";
+
+    if let Some(i) = (first..end).rev().find(|&i| is(i, "OPTION")) {
+        option = &sql[words[i].start..];
+        body_end = words[i].start;
+        end = i;
+    }
+
+    if let Some(i) = (first..end)
+        .rev()
+        .find(|&i| is(i, "ORDER") && is(i + 1, "BY"))
+    {
+        let offset = (i..end).any(|i| is(i, "OFFSET"));
+        let set_operation =
+            (first..i).any(|i| is(i, "UNION") || is(i, "EXCEPT") || is(i, "INTERSECT"));
+        let top = !set_operation
+            && (is(first + 1, "TOP")
+                || ((is(first + 1, "DISTINCT") || is(first + 1, "ALL")) && is(first + 2, "TOP")));
+
+        if !offset && !top {
+            body_end = words[i].start;
+        }
+    }
+
+    let prefix = &sql[..words[first].start];
+    let mut body = sql[words[first].start..body_end].trim_end().to_owned();
+    // Keep the closing parenthesis out of a trailing line comment.
+    if body.lines().last().is_some_and(|line| line.contains("--")) {
+        body.push('\n');
+    }
+    let option = if option.is_empty() {
+        String::new()
+    } else {
+        format!(" {option}")
+    };
+
+    Ok(format!(
+        "{prefix}SELECT COUNT_BIG(*) FROM ({body}) AS _c{option}"
+    ))
+}
+
+/// The byte ranges of the words in `sql` outside parentheses, string literals, quoted
+/// identifiers and comments, and whether a `;` appears outside them.
+fn top_level_words(sql: &str) -> (Vec<Range<usize>>, bool) {
+    let bytes = sql.as_bytes();
+    let is_word = |b: u8| b.is_ascii_alphanumeric() || b >= 0x80 || b"_@#$".contains(&b);
+
+    let mut words = Vec::new();
+    let mut semicolon = false;
+    let mut depth = 0usize;
+    let mut i = 0;
+
+    // Skip past the next `close` that isn't doubled, as in `'it''s'` or `[a]]b]`.
+    let skip_quoted = |mut i: usize, close: u8| {
+        while i < bytes.len() {
+            if bytes[i] == close {
+                if bytes.get(i + 1) != Some(&close) {
+                    return i + 1;
+                }
+                i += 1;
+            }
+            i += 1;
+        }
+        bytes.len()
+    };
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' => i = skip_quoted(i + 1, b'\''),
+            b'"' => i = skip_quoted(i + 1, b'"'),
+            b'[' => i = skip_quoted(i + 1, b']'),
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = sql[i..].find('\n').map_or(bytes.len(), |n| i + n + 1);
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                // Block comments nest in T-SQL.
+                let mut nesting = 0usize;
+                while i < bytes.len() {
+                    if bytes[i..].starts_with(b"/*") {
+                        nesting += 1;
+                        i += 2;
+                    } else if bytes[i..].starts_with(b"*/") {
+                        nesting -= 1;
+                        i += 2;
+                        if nesting == 0 {
+                            break;
+                        }
+                    } else {
+                        i += 1;
+                    }
+                }
+            }
+            b'(' => {
+                depth += 1;
+                i += 1;
+            }
+            b')' => {
+                depth = depth.saturating_sub(1);
+                i += 1;
+            }
+            b';' => {
+                semicolon |= depth == 0;
+                i += 1;
+            }
+            b if is_word(b) => {
+                let start = i;
+                while i < bytes.len() && is_word(bytes[i]) {
+                    i += 1;
+                }
+                if depth == 0 {
+                    words.push(start..i);
+                }
+            }
+            _ => i += 1,
+        }
+    }
+
+    (words, semicolon)
+}
+
+/// Build the `EXECUTE AS USER` statement for [`MssqlConnection::execute_as`].
+///
+/// The statement must be sent as a plain batch: a context switch made inside
+/// `sp_executesql` is reverted as soon as that call returns.
+fn execute_as_statement(user: &str) -> Result<String, Error> {
+    if user.is_empty() || user.chars().count() > 128 || user.chars().any(char::is_control) {
+        return Err(Error::InvalidArgument(format!(
+            "invalid user name {user:?}: must be 1 to 128 characters without control characters"
+        )));
+    }
+
+    Ok(format!("EXECUTE AS USER = N'{}'", user.replace('\'', "''")))
+}
+
+/// Build the `DBCC` statement for [`MssqlConnection::dbcc`], adding `NO_INFOMSGS`.
+fn dbcc_statement(command: &str) -> Result<String, Error> {
+    let mut command = command.trim();
+
+    if let Some((keyword, rest)) = command.split_once(char::is_whitespace) {
+        if keyword.eq_ignore_ascii_case("DBCC") {
+            command = rest.trim_start();
+        }
+    } else if command.eq_ignore_ascii_case("DBCC") {
+        command = "";
+    }
+
+    if command.is_empty() {
+        return Err(Error::InvalidArgument("DBCC command is empty".into()));
+    }
+
+    if command.contains(';') || command.contains("--") || command.contains("/*") {
+        return Err(Error::InvalidArgument(format!(
+            "DBCC command {command:?} must not contain `;` or comments"
+        )));
+    }
+
+    // `WITH` options always come last, after any parenthesized arguments.
+    let options_start = command.rfind(')').map_or(0, |i| i + 1);
+    let options = command[options_start..].to_ascii_uppercase();
+
+    if options.contains("NO_INFOMSGS") {
+        Ok(format!("DBCC {command}"))
+    } else if options.split_whitespace().any(|word| word == "WITH") {
+        Ok(format!("DBCC {command}, NO_INFOMSGS"))
+    } else {
+        Ok(format!("DBCC {command} WITH NO_INFOMSGS"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_adds_no_infomsgs_to_dbcc_commands() {
+        for (command, expected) in [
+            (
+                "SQLPERF(LOGSPACE)",
+                "DBCC SQLPERF(LOGSPACE) WITH NO_INFOMSGS",
+            ),
+            (
+                "dbcc SQLPERF(LOGSPACE)",
+                "DBCC SQLPERF(LOGSPACE) WITH NO_INFOMSGS",
+            ),
+            ("OPENTRAN", "DBCC OPENTRAN WITH NO_INFOMSGS"),
+            (
+                "SHOW_STATISTICS ('dbo.t', ix) WITH STAT_HEADER",
+                "DBCC SHOW_STATISTICS ('dbo.t', ix) WITH STAT_HEADER, NO_INFOMSGS",
+            ),
+            (
+                "CHECKDB WITH TABLERESULTS",
+                "DBCC CHECKDB WITH TABLERESULTS, NO_INFOMSGS",
+            ),
+            (
+                "CHECKDB WITH NO_INFOMSGS, TABLERESULTS",
+                "DBCC CHECKDB WITH NO_INFOMSGS, TABLERESULTS",
+            ),
+        ] {
+            assert_eq!(dbcc_statement(command).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn it_restores_only_the_changed_transaction_settings() {
+        let changes = TransactionSettings {
+            isolation: Some(MssqlIsolationLevel::Serializable),
+            lock_timeout: Some(2000),
+            deadlock_priority: None,
+        };
+        assert_eq!(
+            changes.to_sql(),
+            "SET TRANSACTION ISOLATION LEVEL SERIALIZABLE; SET LOCK_TIMEOUT 2000; "
+        );
+
+        let previous = TransactionSettings {
+            isolation: Some(MssqlIsolationLevel::from_session_level(2)),
+            lock_timeout: Some(-1),
+            deadlock_priority: Some(5),
+        };
+        assert_eq!(
+            previous.restoring(&changes).to_sql(),
+            "SET TRANSACTION ISOLATION LEVEL READ COMMITTED; SET LOCK_TIMEOUT -1; "
+        );
+    }
+
+    #[test]
+    fn it_wraps_selects_in_a_count() {
+        for (sql, expected) in [
+            (
+                "SELECT * FROM users WHERE active = @p1",
+                "SELECT COUNT_BIG(*) FROM (SELECT * FROM users WHERE active = @p1) AS _c",
+            ),
+            (
+                "SELECT id, name FROM users ORDER BY name DESC, id;",
+                "SELECT COUNT_BIG(*) FROM (SELECT id, name FROM users) AS _c",
+            ),
+            (
+                "SELECT id, ROW_NUMBER() OVER (ORDER BY id) AS n FROM t -- ORDER BY\n",
+                "SELECT COUNT_BIG(*) FROM (SELECT id, ROW_NUMBER() OVER (ORDER BY id) AS n \
+                 FROM t -- ORDER BY\n) AS _c",
+            ),
+            (
+                "SELECT name FROM t WHERE name <> 'x ORDER BY y' AND [order by] = 1",
+                "SELECT COUNT_BIG(*) FROM (SELECT name FROM t WHERE name <> 'x ORDER BY y' \
+                 AND [order by] = 1) AS _c",
+            ),
+            (
+                "SELECT a FROM t UNION SELECT TOP 1 a FROM u ORDER BY a",
+                "SELECT COUNT_BIG(*) FROM (SELECT a FROM t UNION SELECT TOP 1 a FROM u) AS _c",
+            ),
+            (
+                "SELECT id FROM t ORDER BY id OFFSET 20 ROWS FETCH NEXT 10 ROWS ONLY",
+                "SELECT COUNT_BIG(*) FROM (SELECT id FROM t ORDER BY id OFFSET 20 ROWS \
+                 FETCH NEXT 10 ROWS ONLY) AS _c",
+            ),
+            (
+                "select distinct top (5) id from t order by id",
+                "SELECT COUNT_BIG(*) FROM (select distinct top (5) id from t order by id) AS _c",
+            ),
+            (
+                "SELECT id FROM t ORDER BY id OPTION (RECOMPILE)",
+                "SELECT COUNT_BIG(*) FROM (SELECT id FROM t) AS _c OPTION (RECOMPILE)",
+            ),
+            (
+                "WITH a AS (SELECT 1 AS x ORDER BY x OFFSET 0 ROWS), b (y) AS (SELECT x FROM a) \
+                 SELECT y FROM b ORDER BY y",
+                "WITH a AS (SELECT 1 AS x ORDER BY x OFFSET 0 ROWS), b (y) AS (SELECT x FROM a) \
+                 SELECT COUNT_BIG(*) FROM (SELECT y FROM b) AS _c",
+            ),
+        ] {
+            assert_eq!(count_statement(sql).unwrap(), expected);
+        }
+
+        for sql in ["", " ; ", "SELECT 1; SELECT 2", "WITH a AS (SELECT 1 AS x)"] {
+            assert!(
+                matches!(count_statement(sql), Err(Error::InvalidArgument(_))),
+                "{sql:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn it_splits_text_chunks_between_characters() {
+        let text = "aé€😀".as_bytes();
+        assert_eq!(utf8_chunk_len(text, true).unwrap(), text.len());
+
+        // cut inside the 4-byte emoji
+        assert_eq!(utf8_chunk_len(&text[..8], false).unwrap(), 6);
+        assert!(utf8_chunk_len(&text[..8], true).is_err());
+
+        assert!(utf8_chunk_len(b"a\xffb", false).is_err());
+        assert_eq!(utf8_chunk_len(b"", true).unwrap(), 0);
+    }
+
+    #[test]
+    fn it_escapes_execute_as_user_names() {
+        assert_eq!(
+            execute_as_statement("app_reader").unwrap(),
+            "EXECUTE AS USER = N'app_reader'"
+        );
+        assert_eq!(
+            execute_as_statement("o'brien'; REVERT; --").unwrap(),
+            "EXECUTE AS USER = N'o''brien''; REVERT; --'"
+        );
+
+        for user in ["", "a\nb", &"x".repeat(129)] {
+            assert!(
+                matches!(execute_as_statement(user), Err(Error::InvalidArgument(_))),
+                "{user:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn it_rejects_invalid_dbcc_commands() {
+        for command in ["", "DBCC ", "OPENTRAN; DROP TABLE t", "OPENTRAN --"] {
+            assert!(
+                matches!(dbcc_statement(command), Err(Error::InvalidArgument(_))),
+                "{command:?}"
+            );
+        }
     }
 }
@@ -49,6 +49,13 @@ pub enum MssqlArgumentValue {
     I16(i16),
     I32(i32),
     I64(i64),
+    /// Bound as `BIGINT` when it fits, as `NUMERIC(20, 0)` otherwise.
+    U64(u64),
+    /// Bound as `NUMERIC(38, 0)`; encoding checks the value has at most 38 digits.
+    I128(i128),
+    /// Ten-thousandths of a `MONEY` or `SMALLMONEY` value, bound as `NUMERIC(19, 4)` since
+    /// TDS parameters have no money type in tiberius.
+    Money(i64),
     F32(f32),
     F64(f64),
     String(String),
@@ -75,4 +82,9 @@ pub enum MssqlArgumentValue {
     TimeOffsetDateTime(time::OffsetDateTime),
     #[cfg(feature = "bigdecimal")]
     BigDecimal(bigdecimal::BigDecimal),
+    /// A `TIME`, `DATETIME2` or `DATETIMEOFFSET` value sent with the given number of
+    /// fractional-second digits (0 to 7) instead of 7; see
+    /// [`MssqlScaled`](crate::MssqlScaled).
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    Scaled(Box<MssqlArgumentValue>, u8),
 }
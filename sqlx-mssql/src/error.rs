@@ -5,6 +5,10 @@ use std::fmt::{self, Debug, Display, Formatter};
 pub(crate) use sqlx_core::error::*;
 
 /// An error returned from the MSSQL database.
+///
+/// When a request raises several errors, this is the first of them: the underlying TDS
+/// client keeps only the first error token of a response and logs the others as tracing
+/// events.
 pub struct MssqlDatabaseError {
     pub(crate) number: u32,
     pub(crate) state: u8,
@@ -26,10 +30,45 @@ impl MssqlDatabaseError {
     }
 
     /// The severity class of the error.
+    ///
+    /// Classes 0-10 are informational, 11-16 are errors the user can correct (see
+    /// [`is_user_error`](Self::is_user_error)), 17-19 are resource or software errors that
+    /// need an administrator, and 20-25 are fatal errors that terminate the connection (see
+    /// [`is_fatal`](Self::is_fatal)).
     pub fn class(&self) -> u8 {
         self.class
     }
 
+    /// Returns `true` if the error is fatal (severity 20 or higher).
+    ///
+    /// SQL Server closes the connection after a fatal error, so the statement must not be
+    /// retried on it: discard the connection and, if the operation is idempotent, retry on a
+    /// new one.
+    pub fn is_fatal(&self) -> bool {
+        self.class >= 20
+    }
+
+    /// Returns `true` if the error was caused by the statement itself (severity 11-16),
+    /// e.g. a constraint violation, a missing object or a syntax error.
+    ///
+    /// Retrying the same statement fails the same way; fix the input instead. Some of these
+    /// are still worth retrying, e.g. a deadlock (1205, severity 13), so check
+    /// [`is_transient`](Self::is_transient) first.
+    pub fn is_user_error(&self) -> bool {
+        (11..=16).contains(&self.class)
+    }
+
+    /// Returns `true` if the operation is likely to succeed when retried after a short delay.
+    ///
+    /// Combines [`is_azure_retryable`](Self::is_azure_retryable) and
+    /// [`is_timeout`](Self::is_timeout) with the errors that abort a statement because of
+    /// concurrent activity: deadlock victim (1205), snapshot isolation update conflict
+    /// (3960) and insufficient memory (701). Retry the whole transaction, not just the
+    /// failed statement, since SQL Server has rolled it back.
+    pub fn is_transient(&self) -> bool {
+        self.is_azure_retryable() || self.is_timeout() || matches!(self.number, 701 | 1205 | 3960)
+    }
+
     /// The server name that generated the error, if available.
     pub fn server(&self) -> Option<&str> {
         self.server.as_deref()
@@ -39,6 +78,80 @@ impl MssqlDatabaseError {
     pub fn procedure(&self) -> Option<&str> {
         self.procedure.as_deref()
     }
+
+    /// Returns `true` if a string or binary value was too long for its column.
+    ///
+    /// SQL Server reports this as error 8152 ("String or binary data would be truncated"),
+    /// or as 2628, which also names the table, column and truncated value, from SQL Server
+    /// 2019 on. With `ANSI_WARNINGS OFF` the value is silently truncated instead and no
+    /// error is raised.
+    ///
+    /// [`ErrorKind`] has no truncation kind, so these errors are classified as
+    /// [`ErrorKind::Other`].
+    pub fn is_truncation(&self) -> bool {
+        matches!(self.number, 8152 | 2628)
+    }
+
+    /// Returns `true` if the server refused the statement for lack of a permission.
+    ///
+    /// Covers 229 and 230 (permission denied on an object or column), 262 (permission denied
+    /// in the database), 297 (the user lacks the permission for the action) and 300 (a
+    /// server-level permission such as `VIEW SERVER STATE` is missing). The message names the
+    /// missing permission.
+    pub fn is_permission_denied(&self) -> bool {
+        matches!(self.number, 229 | 230 | 262 | 297 | 300)
+    }
+
+    /// Returns `true` if the statement tried to write to a read-only database (error 3906).
+    ///
+    /// This is what a write gets on an Always On readable secondary, e.g. a connection made
+    /// with [`application_intent_read_only`][crate::MssqlConnectOptions::application_intent_read_only]
+    /// that was routed to a replica, and on databases set `READ_ONLY`. Retrying on the same
+    /// connection fails the same way; send the write over a read-write connection instead.
+    ///
+    /// [`ErrorKind`] has no read-only kind, so this error is classified as
+    /// [`ErrorKind::Other`].
+    pub fn is_read_only(&self) -> bool {
+        self.number == 3906
+    }
+
+    /// Returns `true` if the server gave up on the statement because it waited too long,
+    /// as opposed to the statement itself failing.
+    ///
+    /// Covers 1222 ("Lock request time out period exceeded", see `SET LOCK_TIMEOUT`), 8628
+    /// (timed out waiting to optimize the query) and 8645 (timed out waiting for a memory
+    /// grant). These are usually worth retrying once the server is less busy.
+    ///
+    /// A timeout on the client side, e.g. the socket giving up on a slow server, is not a
+    /// database error: it is returned as [`Error::Io`] with [`std::io::ErrorKind::TimedOut`].
+    pub fn is_timeout(&self) -> bool {
+        matches!(self.number, 1222 | 8628 | 8645)
+    }
+
+    /// Returns `true` for the transient errors Azure SQL raises while a database is being
+    /// moved, scaled or failed over, which usually succeed when retried after a short delay.
+    ///
+    /// Covers e.g. 40613 ("Database is not currently available"), 40197, 40501 (service
+    /// busy), 49918-49920 and the 10928/10929 resource limits. Login failures such as 18456
+    /// are not retryable.
+    pub fn is_azure_retryable(&self) -> bool {
+        matches!(
+            self.number,
+            4060 | 4221
+                | 10928
+                | 10929
+                | 40143
+                | 40197
+                | 40501
+                | 40540
+                | 40613
+                | 42108
+                | 42109
+                | 49918
+                | 49919
+                | 49920
+        )
+    }
 }
 
 impl Debug for MssqlDatabaseError {
@@ -93,17 +206,29 @@ impl DatabaseError for MssqlDatabaseError {
         match self.number {
             // Cannot insert duplicate key
             2601 | 2627 => ErrorKind::UniqueViolation,
-            // Foreign key constraint violation
+            // Statement conflicted with a CHECK or FOREIGN KEY constraint
+            547 if self.message.contains("CHECK constraint") => ErrorKind::CheckViolation,
             547 => ErrorKind::ForeignKeyViolation,
             // Cannot insert NULL
             515 => ErrorKind::NotNullViolation,
-            // Check constraint violation
-            2628 => ErrorKind::CheckViolation,
+            // String or binary data would be truncated, see `is_truncation`, and writes to a
+            // read-only database, see `is_read_only`
             _ => ErrorKind::Other,
         }
     }
 }
 
+/// Whether `err` is a transient server error worth retrying, see
+/// [`MssqlDatabaseError::is_azure_retryable`].
+pub(crate) fn is_retryable(err: &Error) -> bool {
+    match err {
+        Error::Database(err) => err
+            .try_downcast_ref::<MssqlDatabaseError>()
+            .is_some_and(MssqlDatabaseError::is_azure_retryable),
+        _ => false,
+    }
+}
+
 /// Convert a tiberius error into an sqlx Error.
 pub(crate) fn tiberius_err(err: tiberius::error::Error) -> Error {
     match err {
@@ -137,3 +262,161 @@ pub(crate) fn tiberius_err(err: tiberius::error::Error) -> Error {
         other => Error::Protocol(other.to_string()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn database_error(number: u32, message: &str) -> MssqlDatabaseError {
+        MssqlDatabaseError {
+            number,
+            state: 1,
+            class: 16,
+            message: message.to_owned(),
+            server: None,
+            procedure: None,
+        }
+    }
+
+    #[test]
+    fn it_classifies_truncation_errors() {
+        let err = database_error(8152, "String or binary data would be truncated.");
+        assert!(err.is_truncation());
+        assert_eq!(err.kind(), ErrorKind::Other);
+
+        let err = database_error(
+            2628,
+            "String or binary data would be truncated in table 'db.dbo.t', column 'name'. \
+             Truncated value: 'abc'.",
+        );
+        assert!(err.is_truncation());
+        assert_eq!(err.kind(), ErrorKind::Other);
+
+        assert!(!database_error(2627, "Violation of PRIMARY KEY constraint").is_truncation());
+    }
+
+    #[test]
+    fn it_classifies_permission_errors() {
+        let err = database_error(
+            300,
+            "VIEW SERVER STATE permission was denied on object 'server', database 'master'.",
+        );
+        assert!(err.is_permission_denied());
+        assert!(!err.is_transient());
+
+        assert!(
+            database_error(229, "The SELECT permission was denied on the object 't'.")
+                .is_permission_denied()
+        );
+        assert!(!database_error(208, "Invalid object name 't'.").is_permission_denied());
+    }
+
+    #[test]
+    fn it_classifies_read_only_errors() {
+        let err = database_error(
+            3906,
+            "Failed to update database \"orders\" because the database is read-only.",
+        );
+        assert!(err.is_read_only());
+        assert!(err.is_user_error());
+        assert!(!err.is_transient());
+        assert_eq!(err.kind(), ErrorKind::Other);
+
+        assert!(!database_error(229, "The INSERT permission was denied").is_read_only());
+    }
+
+    #[test]
+    fn it_classifies_timeout_errors() {
+        let err = database_error(1222, "Lock request time out period exceeded.");
+        assert!(err.is_timeout());
+        assert_eq!(err.kind(), ErrorKind::Other);
+
+        assert!(database_error(
+            8645,
+            "A timeout occurred while waiting for memory resources to execute the query."
+        )
+        .is_timeout());
+        assert!(!database_error(8134, "Divide by zero error encountered.").is_timeout());
+        assert!(!database_error(1205, "Transaction was deadlocked").is_timeout());
+    }
+
+    #[test]
+    fn it_classifies_azure_retryable_errors() {
+        let err = database_error(
+            40613,
+            "Database 'db' on server 'srv' is not currently available.",
+        );
+        assert!(err.is_azure_retryable());
+        assert!(is_retryable(&Error::Database(Box::new(err))));
+
+        let err = database_error(18456, "Login failed for user 'sa'.");
+        assert!(!err.is_azure_retryable());
+        assert!(!is_retryable(&Error::Database(Box::new(err))));
+
+        assert!(!is_retryable(&Error::PoolTimedOut));
+    }
+
+    fn error_with_class(number: u32, class: u8) -> MssqlDatabaseError {
+        MssqlDatabaseError {
+            class,
+            ..database_error(number, "")
+        }
+    }
+
+    #[test]
+    fn it_classifies_errors_by_severity() {
+        // Informational messages
+        let err = error_with_class(5701, 10);
+        assert!(!err.is_user_error());
+        assert!(!err.is_fatal());
+
+        // Invalid object name
+        let err = error_with_class(208, 16);
+        assert!(err.is_user_error());
+        assert!(!err.is_fatal());
+        assert!(!err.is_transient());
+
+        // Insufficient resources
+        let err = error_with_class(9002, 17);
+        assert!(!err.is_user_error());
+        assert!(!err.is_fatal());
+
+        // Fatal errors end the connection
+        for class in [20, 21, 23, 25] {
+            let err = error_with_class(3624, class);
+            assert!(err.is_fatal(), "{class}");
+            assert!(!err.is_user_error(), "{class}");
+        }
+    }
+
+    #[test]
+    fn it_classifies_transient_errors() {
+        // Deadlock victims are user errors by severity, but worth retrying.
+        let err = error_with_class(1205, 13);
+        assert!(err.is_user_error());
+        assert!(err.is_transient());
+
+        assert!(error_with_class(3960, 16).is_transient());
+        assert!(error_with_class(1222, 16).is_transient());
+        assert!(error_with_class(40613, 17).is_transient());
+        assert!(error_with_class(701, 17).is_transient());
+
+        assert!(!error_with_class(2627, 14).is_transient());
+        assert!(!error_with_class(18456, 14).is_transient());
+    }
+
+    #[test]
+    fn it_tells_check_and_foreign_key_violations_apart() {
+        let err = database_error(
+            547,
+            "The INSERT statement conflicted with the CHECK constraint \"CK_price\".",
+        );
+        assert_eq!(err.kind(), ErrorKind::CheckViolation);
+
+        let err = database_error(
+            547,
+            "The INSERT statement conflicted with the FOREIGN KEY constraint \"FK_tweet\".",
+        );
+        assert_eq!(err.kind(), ErrorKind::ForeignKeyViolation);
+    }
+}
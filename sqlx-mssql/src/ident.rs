@@ -0,0 +1,278 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::error::Error;
+
+/// Quote a single identifier part with brackets, escaping any `]` it contains.
+pub(crate) fn quote_identifier(part: &str) -> String {
+    format!("[{}]", part.replace(']', "]]"))
+}
+
+/// A safely quoted, optionally qualified SQL Server object name:
+/// `[database].[schema].[object]`.
+///
+/// Each part is bracket-quoted with any `]` escaped, so names taken from configuration or
+/// user input can be interpolated into dynamic SQL without risk of injection. Parts are
+/// validated when set: they must be non-empty, at most 128 characters (the length of
+/// `sysname`) and free of control characters.
+///
+/// A database without a schema renders as `[database]..[object]`, which resolves the object
+/// in the default schema.
+///
+/// # Example
+///
+/// ```rust
+/// # fn example() -> sqlx::Result<()> {
+/// use sqlx::mssql::{Mssql, MssqlObjectName};
+/// use sqlx::QueryBuilder;
+///
+/// let table = MssqlObjectName::new("order items")?
+///     .schema("sales")?
+///     .database("archive]2023")?;
+///
+/// assert_eq!(table.to_string(), "[archive]]2023].[sales].[order items]");
+///
+/// let mut qb = QueryBuilder::<Mssql>::new("SELECT * FROM ");
+/// qb.push(&table);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MssqlObjectName {
+    database: Option<String>,
+    schema: Option<String>,
+    object: String,
+}
+
+impl MssqlObjectName {
+    /// Create an unqualified name for `object`, e.g. a table, view or procedure.
+    pub fn new(object: &str) -> Result<Self, Error> {
+        Ok(Self {
+            database: None,
+            schema: None,
+            object: validate_part(object)?,
+        })
+    }
+
+    /// Qualify the name with a schema.
+    pub fn schema(mut self, schema: &str) -> Result<Self, Error> {
+        self.schema = Some(validate_part(schema)?);
+        Ok(self)
+    }
+
+    /// Qualify the name with a database.
+    pub fn database(mut self, database: &str) -> Result<Self, Error> {
+        self.database = Some(validate_part(database)?);
+        Ok(self)
+    }
+
+    /// Get the unquoted object part.
+    pub fn get_object(&self) -> &str {
+        &self.object
+    }
+
+    /// Get the unquoted schema part, if any.
+    pub fn get_schema(&self) -> Option<&str> {
+        self.schema.as_deref()
+    }
+
+    /// Get the unquoted database part, if any.
+    pub fn get_database(&self) -> Option<&str> {
+        self.database.as_deref()
+    }
+}
+
+impl Display for MssqlObjectName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(database) = &self.database {
+            write!(f, "{}.", quote_identifier(database))?;
+
+            // `db..object` resolves the object in the default schema
+            if self.schema.is_none() {
+                f.write_str(".")?;
+            }
+        }
+
+        if let Some(schema) = &self.schema {
+            write!(f, "{}.", quote_identifier(schema))?;
+        }
+
+        f.write_str(&quote_identifier(&self.object))
+    }
+}
+
+pub(crate) fn validate_part(part: &str) -> Result<String, Error> {
+    if part.is_empty() {
+        return Err(Error::InvalidArgument("object name part is empty".into()));
+    }
+
+    if part.chars().count() > 128 {
+        return Err(Error::InvalidArgument(format!(
+            "object name part {part:?} is longer than 128 characters"
+        )));
+    }
+
+    if part.chars().any(char::is_control) {
+        return Err(Error::InvalidArgument(format!(
+            "object name part {part:?} contains control characters"
+        )));
+    }
+
+    Ok(part.to_owned())
+}
+
+/// Quote a possibly multi-part object name such as `dbo.users` or `[my db].dbo.[t]`.
+///
+/// Parts already enclosed in brackets are kept as-is (after checking that every `]` inside
+/// is escaped); bare parts are quoted with [`quote_identifier`]. At most three parts
+/// (`database.schema.object`) are accepted and none may be empty.
+pub(crate) fn quote_object_name(name: &str) -> Result<String, Error> {
+    let mut parts = Vec::new();
+    let mut rest = name.trim();
+
+    loop {
+        let (part, remainder) = if let Some(bracketed) = rest.strip_prefix('[') {
+            let end = closing_bracket(bracketed).ok_or_else(|| invalid(name))?;
+            let (inner, remainder) = bracketed.split_at(end);
+            (quote_identifier(&inner.replace("]]", "]")), &remainder[1..])
+        } else {
+            let end = rest.find('.').unwrap_or(rest.len());
+            let (bare, remainder) = rest.split_at(end);
+            let bare = bare.trim();
+
+            if bare.is_empty() || bare.contains(['[', ']']) {
+                return Err(invalid(name));
+            }
+
+            (quote_identifier(bare), remainder)
+        };
+
+        parts.push(part);
+
+        match remainder.trim_start().strip_prefix('.') {
+            Some(next) => rest = next.trim_start(),
+            None if remainder.trim().is_empty() => break,
+            None => return Err(invalid(name)),
+        }
+    }
+
+    if parts.len() > 3 {
+        return Err(invalid(name));
+    }
+
+    Ok(parts.join("."))
+}
+
+/// Index of the `]` closing a bracketed identifier, skipping escaped `]]`.
+fn closing_bracket(s: &str) -> Option<usize> {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b']' {
+            if bytes.get(i + 1) == Some(&b']') {
+                i += 2;
+                continue;
+            }
+
+            return Some(i).filter(|&i| i > 0);
+        }
+
+        i += 1;
+    }
+
+    None
+}
+
+fn invalid(name: &str) -> Error {
+    Error::InvalidArgument(format!("invalid object name {name:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_quotes_object_names() {
+        for (name, expected) in [
+            ("users", "[users]"),
+            ("#temp", "[#temp]"),
+            ("dbo.users", "[dbo].[users]"),
+            ("app.dbo.users", "[app].[dbo].[users]"),
+            ("[my db].dbo.[order]", "[my db].[dbo].[order]"),
+            ("[a]]b].[c.d]", "[a]]b].[c.d]"),
+            (" dbo . users ", "[dbo].[users]"),
+        ] {
+            assert_eq!(quote_object_name(name).unwrap(), expected, "{name:?}");
+        }
+    }
+
+    #[test]
+    fn it_renders_object_names() -> Result<(), Error> {
+        let object = || MssqlObjectName::new("users");
+
+        assert_eq!(object()?.to_string(), "[users]");
+        assert_eq!(object()?.schema("dbo")?.to_string(), "[dbo].[users]");
+        assert_eq!(object()?.database("app")?.to_string(), "[app]..[users]");
+        assert_eq!(
+            object()?.schema("dbo")?.database("app")?.to_string(),
+            "[app].[dbo].[users]"
+        );
+        assert_eq!(
+            MssqlObjectName::new("a]b")?
+                .schema("[x]")?
+                .database("my db")?
+                .to_string(),
+            "[my db].[[x]]].[a]]b]"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_rejects_malformed_object_name_parts() {
+        let long = "x".repeat(129);
+
+        for part in ["", "a\0b", "line\nbreak", long.as_str()] {
+            assert!(
+                matches!(MssqlObjectName::new(part), Err(Error::InvalidArgument(_))),
+                "{part:?}"
+            );
+            assert!(
+                matches!(
+                    MssqlObjectName::new("t").and_then(|n| n.schema(part)),
+                    Err(Error::InvalidArgument(_))
+                ),
+                "{part:?}"
+            );
+            assert!(
+                matches!(
+                    MssqlObjectName::new("t").and_then(|n| n.database(part)),
+                    Err(Error::InvalidArgument(_))
+                ),
+                "{part:?}"
+            );
+        }
+
+        assert!(MssqlObjectName::new(&"x".repeat(128)).is_ok());
+    }
+
+    #[test]
+    fn it_rejects_malformed_object_names() {
+        for name in [
+            "",
+            ".users",
+            "dbo.",
+            "a.b.c.d",
+            "[unterminated",
+            "[]",
+            "[a]b",
+            "users]; DROP TABLE x; --",
+            "us[ers",
+        ] {
+            assert!(
+                matches!(quote_object_name(name), Err(Error::InvalidArgument(_))),
+                "{name:?}"
+            );
+        }
+    }
+}
@@ -46,6 +46,18 @@ impl MssqlIsolationLevel {
             Self::Serializable => "SERIALIZABLE",
         }
     }
+
+    /// The level of a `transaction_isolation_level` in `sys.dm_exec_sessions`, where `0`
+    /// (unspecified) means the default.
+    pub(crate) fn from_session_level(level: i16) -> Self {
+        match level {
+            1 => Self::ReadUncommitted,
+            3 => Self::RepeatableRead,
+            4 => Self::Serializable,
+            5 => Self::Snapshot,
+            _ => Self::ReadCommitted,
+        }
+    }
 }
 
 impl fmt::Display for MssqlIsolationLevel {
@@ -12,6 +12,7 @@ pub(crate) use sqlx_core::driver_prelude::*;
 
 pub mod advisory_lock;
 mod bulk_insert;
+mod capabilities;
 mod isolation_level;
 
 #[cfg(feature = "any")]
@@ -22,12 +23,19 @@ mod column;
 mod connection;
 mod database;
 mod error;
+mod ident;
 mod io;
 mod options;
+mod pool_ext;
+mod procedure;
+mod query_builder_ext;
 mod query_result;
+mod request_info;
 mod row;
 mod statement;
+mod stats;
 mod transaction;
+mod tree;
 mod type_checking;
 mod type_info;
 pub mod types;
@@ -42,18 +50,40 @@ mod testing;
 pub use advisory_lock::{MssqlAdvisoryLock, MssqlAdvisoryLockGuard, MssqlAdvisoryLockMode};
 pub use arguments::MssqlArguments;
 pub use bulk_insert::MssqlBulkInsert;
+pub use capabilities::MssqlServerCapabilities;
 pub use column::MssqlColumn;
 pub use connection::MssqlConnection;
 pub use database::Mssql;
 pub use error::MssqlDatabaseError;
+pub use ident::MssqlObjectName;
 pub use isolation_level::MssqlIsolationLevel;
-pub use options::ssl_mode::MssqlSslMode;
+pub use options::ssl_mode::{MssqlEncryptionLevel, MssqlSslMode};
 pub use options::MssqlConnectOptions;
+pub use pool_ext::{MssqlPoolExt, MssqlPoolKeepalive, MssqlPoolOptionsExt};
+pub use procedure::{MssqlProcedureParams, MssqlProcedureResult};
+#[cfg(feature = "json")]
+pub use query_builder_ext::MssqlJsonColumn;
+pub use query_builder_ext::{MssqlDatePart, MssqlHint, MssqlQueryBuilderExt, MssqlSortDirection};
 pub use query_result::MssqlQueryResult;
+pub use request_info::MssqlRequestInfo;
 pub use row::MssqlRow;
 pub use statement::MssqlStatement;
+pub use stats::MssqlStats;
 pub use transaction::MssqlTransactionManager;
+pub use tree::{build_tree, MssqlTreeNode};
 pub use type_info::MssqlTypeInfo;
+pub use types::bom_stripped::MssqlBomStrippedStr;
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub use types::date_days::MssqlDateDays;
+pub use types::duration_nanos::MssqlDurationNanos;
+#[cfg(feature = "rust_decimal")]
+pub use types::float_decimal::MssqlFloatDecimal;
+pub use types::money::{MssqlMoney, MssqlSmallMoney};
+pub use types::rowversion::MssqlRowVersion;
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub use types::scaled::MssqlScaled;
+pub use types::spatial::MssqlSpatial;
+pub use types::variant::MssqlVariant;
 pub use types::xml::MssqlXml;
 pub use value::{MssqlValue, MssqlValueRef};
 
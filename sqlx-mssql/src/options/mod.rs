@@ -2,7 +2,11 @@ mod connect;
 mod parse;
 pub mod ssl_mode;
 
+use std::fmt::{self, Write};
+use std::time::Duration;
+
 use crate::connection::LogSettings;
+use crate::error::Error;
 use ssl_mode::MssqlSslMode;
 
 /// Options and flags which can be used to configure a MSSQL connection.
@@ -20,16 +24,27 @@ use ssl_mode::MssqlSslMode;
 ///
 /// |Parameter|Default|Description|
 /// |---------|-------|-----------|
-/// | `sslmode` / `ssl_mode` | `preferred` | SSL encryption mode: `disabled`, `login_only`, `preferred`, `required`. |
-/// | `encrypt` | (none) | Legacy alias: `true` maps to `required`, `false` to `disabled`. |
+/// | `sslmode` / `ssl_mode` | `preferred` | SSL encryption mode: `disabled`, `login_only`, `preferred`, `required`, `strict`. |
+/// | `encrypt` | (none) | Legacy alias: `true` maps to `required`, `false` to `disabled`, `strict` to `strict`. |
 /// | `trust_server_certificate` | `false` | Whether to trust the server certificate without validation. |
 /// | `trust_server_certificate_ca` | (none) | Path to a CA certificate file to validate the server certificate against. Mutually exclusive with `trust_server_certificate`. |
 /// | `application_intent` | `read_write` | Application intent: `read_write` or `read_only`. `read_only` routes to Always On read replicas. |
 /// | `statement-cache-capacity` | `100` | The maximum number of prepared statements stored in the cache. |
 /// | `app_name` | `sqlx` | The application name sent to the server. |
+/// | `app_attribute` | (none) | A `key=value` attribute appended to the application name; can be repeated. See [`app_attribute`][MssqlConnectOptions::app_attribute]. |
 /// | `instance` | `None` | The SQL Server instance name. |
 /// | `auth` | `sql_server` | Authentication method: `sql_server`, `windows` (cfg-gated), `integrated` (cfg-gated), `aad_token`. |
+/// | `trusted_connection` | `false` | `true` (or `yes`, `sspi`) is the same as `auth=integrated`. |
 /// | `token` | (none) | Azure AD bearer token (used when `auth=aad_token`). |
+/// | `access_token` | (none) | Same as `auth=aad_token&token=...`. |
+/// | `deadlock_priority` | (none) | `SET DEADLOCK_PRIORITY` issued on connect: `low`, `normal`, `high`, or `-10` to `10`. |
+/// | `connect_timeout` | `30` | How many seconds the TCP connect and login handshake may take; `0` means no limit. |
+/// | `connect_retries` | `0` | How many times to retry connecting after a transient Azure SQL error. |
+/// | `required_set_options` | `true` | Whether to set the `SET` options required by indexed views and indexes on computed columns on connect. |
+/// | `no_count` | `false` | Whether to issue `SET NOCOUNT ON` on connect. See [`no_count`][MssqlConnectOptions::no_count] for its effect on `rows_affected`. |
+/// | `expected_collation` | (none) | Fail to connect unless the server collation is this one, e.g. `SQL_Latin1_General_CP1_CI_AS`. |
+/// | `capture_last_insert_id` | `false` | Whether `INSERT` statements report `SCOPE_IDENTITY()` as `last_insert_id`. |
+/// | `command_timeout` | (none) | How many seconds a query may wait on the server before failing; `0` means no limit. |
 ///
 /// # Example
 ///
@@ -51,7 +66,7 @@ use ssl_mode::MssqlSslMode;
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct MssqlConnectOptions {
     pub(crate) host: String,
     pub(crate) port: u16,
@@ -65,6 +80,7 @@ pub struct MssqlConnectOptions {
     pub(crate) application_intent_read_only: bool,
     pub(crate) statement_cache_capacity: usize,
     pub(crate) app_name: String,
+    pub(crate) app_attributes: Vec<(String, String)>,
     pub(crate) log_settings: LogSettings,
     /// When `true`, use Windows (NTLM) authentication instead of SQL Server auth.
     /// The username can use `domain\user` syntax which tiberius parses internally.
@@ -78,6 +94,66 @@ pub struct MssqlConnectOptions {
     pub(crate) integrated_auth: bool,
     /// Azure AD bearer token for AAD authentication.
     pub(crate) aad_token: Option<String>,
+    pub(crate) deadlock_priority: Option<i8>,
+    pub(crate) connect_timeout: Option<Duration>,
+    pub(crate) connect_retries: u32,
+    pub(crate) connect_retry_backoff: Duration,
+    pub(crate) required_set_options: bool,
+    pub(crate) no_count: bool,
+    pub(crate) expected_collation: Option<String>,
+    pub(crate) capture_last_insert_id: bool,
+    pub(crate) command_timeout: Option<Duration>,
+}
+
+/// Placeholder printed in place of secrets.
+const REDACTED: &str = "****";
+
+/// The longest application name a LOGIN7 packet can carry.
+const MAX_PROGRAM_NAME_LEN: usize = 128;
+
+/// Secrets (password and AAD token) are printed as `Some("****")`.
+impl fmt::Debug for MssqlConnectOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("MssqlConnectOptions");
+        s.field("host", &self.host)
+            .field("port", &self.port)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| REDACTED))
+            .field("database", &self.database)
+            .field("instance", &self.instance)
+            .field("ssl_mode", &self.ssl_mode)
+            .field("trust_server_certificate", &self.trust_server_certificate)
+            .field(
+                "trust_server_certificate_ca",
+                &self.trust_server_certificate_ca,
+            )
+            .field(
+                "application_intent_read_only",
+                &self.application_intent_read_only,
+            )
+            .field("statement_cache_capacity", &self.statement_cache_capacity)
+            .field("app_name", &self.app_name)
+            .field("app_attributes", &self.app_attributes)
+            .field("log_settings", &self.log_settings);
+        #[cfg(all(windows, feature = "winauth"))]
+        s.field("windows_auth", &self.windows_auth);
+        #[cfg(any(
+            all(windows, feature = "winauth"),
+            all(unix, feature = "integrated-auth-gssapi")
+        ))]
+        s.field("integrated_auth", &self.integrated_auth);
+        s.field("aad_token", &self.aad_token.as_ref().map(|_| REDACTED))
+            .field("deadlock_priority", &self.deadlock_priority)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("connect_retries", &self.connect_retries)
+            .field("connect_retry_backoff", &self.connect_retry_backoff)
+            .field("required_set_options", &self.required_set_options)
+            .field("no_count", &self.no_count)
+            .field("expected_collation", &self.expected_collation)
+            .field("capture_last_insert_id", &self.capture_last_insert_id)
+            .field("command_timeout", &self.command_timeout)
+            .finish()
+    }
 }
 
 impl Default for MssqlConnectOptions {
@@ -102,6 +178,7 @@ impl MssqlConnectOptions {
             application_intent_read_only: false,
             statement_cache_capacity: 100,
             app_name: String::from("sqlx"),
+            app_attributes: Vec::new(),
             log_settings: Default::default(),
             #[cfg(all(windows, feature = "winauth"))]
             windows_auth: false,
@@ -111,6 +188,15 @@ impl MssqlConnectOptions {
             ))]
             integrated_auth: false,
             aad_token: None,
+            deadlock_priority: None,
+            connect_timeout: Some(Duration::from_secs(30)),
+            connect_retries: 0,
+            connect_retry_backoff: Duration::from_millis(500),
+            required_set_options: true,
+            no_count: false,
+            expected_collation: None,
+            capture_last_insert_id: false,
+            command_timeout: None,
         }
     }
 
@@ -183,6 +269,9 @@ impl MssqlConnectOptions {
     ///
     /// This is mutually exclusive with [`trust_server_certificate`](Self::trust_server_certificate).
     /// When a CA path is set, `trust_server_certificate` is ignored.
+    ///
+    /// Pinning a certificate by thumbprint isn't supported; to trust only a self-signed
+    /// server certificate, pass that certificate's file here instead.
     pub fn trust_server_certificate_ca(mut self, path: &str) -> Self {
         self.trust_server_certificate_ca = Some(path.to_owned());
         self
@@ -192,6 +281,9 @@ impl MssqlConnectOptions {
     ///
     /// When `true`, sets `ApplicationIntent=ReadOnly` in the TDS login packet,
     /// which routes connections to Always On Availability Group read replicas.
+    ///
+    /// Writes on a connection routed to a replica fail with error 3906 when they execute;
+    /// check for it with [`MssqlDatabaseError::is_read_only`][crate::MssqlDatabaseError::is_read_only].
     pub fn application_intent_read_only(mut self, read_only: bool) -> Self {
         self.application_intent_read_only = read_only;
         self
@@ -214,6 +306,47 @@ impl MssqlConnectOptions {
         self
     }
 
+    /// Get the application name, without the [`app_attribute`][Self::app_attribute]s.
+    pub fn get_app_name(&self) -> &str {
+        &self.app_name
+    }
+
+    /// Adds a `key=value` attribute to the application name, replacing any earlier value of
+    /// `key`, so gateways and monitoring that only see the login can classify connections.
+    ///
+    /// TDS has no field for arbitrary connection attributes, so they are appended to the
+    /// application name as `app;key=value;key=value`, which the server reports as
+    /// `program_name` in `sys.dm_exec_sessions` and `APP_NAME()`. Keys may not contain `;` or
+    /// `=`, values may not contain `;`, and the whole name must fit in 128 characters;
+    /// connecting fails with [`Error::Configuration`] otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn example(base: sqlx::mssql::MssqlConnectOptions) {
+    /// // Sent as "billing;pool=reporting"
+    /// let reporting = base.clone().app_name("billing").app_attribute("pool", "reporting");
+    /// let writer = base.app_name("billing").app_attribute("pool", "writer");
+    /// # }
+    /// ```
+    pub fn app_attribute(mut self, key: &str, value: &str) -> Self {
+        match self.app_attributes.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => value.clone_into(v),
+            None => self.app_attributes.push((key.to_owned(), value.to_owned())),
+        }
+        self
+    }
+
+    /// The application name as sent in the login, with the
+    /// [`app_attribute`][Self::app_attribute]s appended.
+    pub fn get_program_name(&self) -> String {
+        let mut name = self.app_name.clone();
+        for (key, value) in &self.app_attributes {
+            let _ = write!(name, ";{key}={value}");
+        }
+        name
+    }
+
     /// Sets whether to use Windows (NTLM) authentication.
     ///
     /// When enabled, the username can use `domain\user` syntax
@@ -236,12 +369,278 @@ impl MssqlConnectOptions {
 
     /// Sets an Azure AD bearer token for authentication.
     ///
-    /// When set, AAD token authentication takes precedence over other auth methods.
+    /// When set, AAD token authentication takes precedence over other auth methods: the
+    /// username and password are ignored, and the connection is always encrypted, as if
+    /// [`ssl_mode`][Self::ssl_mode] were [`MssqlSslMode::Required`].
     pub fn aad_token(mut self, token: &str) -> Self {
         self.aad_token = Some(token.to_owned());
         self
     }
 
+    /// Sets an OAuth access token for authentication, e.g. one fetched for a managed
+    /// identity or service principal with `azure_identity`.
+    ///
+    /// The same as [`aad_token`][Self::aad_token].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn example(token: &str) {
+    /// use sqlx::mssql::MssqlConnectOptions;
+    ///
+    /// // `token` for the scope `https://database.windows.net/.default`
+    /// let opts = MssqlConnectOptions::new()
+    ///     .host("your-server.database.windows.net")
+    ///     .database("mydb")
+    ///     .access_token(token);
+    /// # }
+    /// ```
+    pub fn access_token(self, token: &str) -> Self {
+        self.aad_token(token)
+    }
+
+    /// The SSL mode actually requested from tiberius: token authentication is never sent
+    /// over an unencrypted connection.
+    pub(crate) fn effective_ssl_mode(&self) -> MssqlSslMode {
+        match self.ssl_mode {
+            MssqlSslMode::Strict => MssqlSslMode::Strict,
+            _ if self.aad_token.is_some() => MssqlSslMode::Required,
+            mode => mode,
+        }
+    }
+
+    /// Reject combinations of options that cannot be honored, checked before connecting
+    /// since the builder methods are infallible.
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        if self.ssl_mode == MssqlSslMode::Strict
+            && self.trust_server_certificate
+            && self.trust_server_certificate_ca.is_none()
+        {
+            return Err(Error::Configuration(
+                "sslmode=strict validates the server certificate and cannot be combined with \
+                 trust_server_certificate; set trust_server_certificate_ca instead"
+                    .into(),
+            ));
+        }
+
+        for (key, value) in &self.app_attributes {
+            if key.is_empty() || key.contains([';', '=']) || value.contains(';') {
+                return Err(Error::Configuration(
+                    format!("invalid application name attribute {key:?}={value:?}").into(),
+                ));
+            }
+        }
+
+        let program_name = self.get_program_name();
+        if program_name.chars().count() > MAX_PROGRAM_NAME_LEN {
+            return Err(Error::Configuration(
+                format!(
+                    "application name {program_name:?} is longer than \
+                     {MAX_PROGRAM_NAME_LEN} characters"
+                )
+                .into(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Sets the session's deadlock priority, issued as `SET DEADLOCK_PRIORITY` on connect.
+    ///
+    /// When two sessions deadlock, SQL Server picks the one with the lower priority as the
+    /// victim, so background jobs can use a low priority to yield to interactive queries.
+    /// The value is clamped to `-10..=10`; `LOW`, `NORMAL` and `HIGH` correspond to
+    /// `-5`, `0` and `5`.
+    ///
+    /// The setting is session-scoped and connections are not reset when returned to a pool,
+    /// so a `SET DEADLOCK_PRIORITY` executed on a pooled connection persists until that
+    /// connection is closed. Every new connection starts from this value.
+    pub fn deadlock_priority(mut self, priority: i8) -> Self {
+        self.deadlock_priority = Some(priority.clamp(-10, 10));
+        self
+    }
+
+    /// Get the deadlock priority issued on connect, if any.
+    pub fn get_deadlock_priority(&self) -> Option<i8> {
+        self.deadlock_priority
+    }
+
+    /// Sets how long opening a connection may take before it fails with an [`Error::Io`] of
+    /// kind [`std::io::ErrorKind::TimedOut`].
+    ///
+    /// The timeout covers the TCP connect together with the TDS pre-login, TLS and login
+    /// exchange, since a gateway can accept the socket and then stall the handshake. Each
+    /// [retry](Self::connect_retries) gets the full timeout again. A zero duration disables
+    /// it. The default is 30 seconds.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout).filter(|timeout| !timeout.is_zero());
+        self
+    }
+
+    /// Get the timeout for opening a connection, if any.
+    pub fn get_connect_timeout(&self) -> Option<Duration> {
+        self.connect_timeout
+    }
+
+    /// Sets how many times to retry connecting when the server rejects the login with a
+    /// transient error.
+    ///
+    /// Azure SQL can refuse logins for a few seconds while a database fails over or is
+    /// scaled (e.g. error 40613). Only errors classified by
+    /// [`MssqlDatabaseError::is_azure_retryable`] are retried; anything else, including bad
+    /// credentials (18456), fails immediately. Each retry repeats the whole connect.
+    ///
+    /// The default is `0`, i.e. no retries.
+    ///
+    /// [`MssqlDatabaseError::is_azure_retryable`]: crate::MssqlDatabaseError::is_azure_retryable
+    pub fn connect_retries(mut self, retries: u32) -> Self {
+        self.connect_retries = retries;
+        self
+    }
+
+    /// Sets the delay before the first connect retry, doubled for each further retry.
+    ///
+    /// The default is 500 milliseconds.
+    pub fn connect_retry_backoff(mut self, backoff: Duration) -> Self {
+        self.connect_retry_backoff = backoff;
+        self
+    }
+
+    /// Get the number of connect retries on transient errors.
+    pub fn get_connect_retries(&self) -> u32 {
+        self.connect_retries
+    }
+
+    /// Sets whether to issue the `SET` options SQL Server requires for indexed views,
+    /// indexes on computed columns and filtered indexes when connecting.
+    ///
+    /// When enabled (the default), every new connection runs `SET ANSI_NULLS, ANSI_PADDING,
+    /// ANSI_WARNINGS, ARITHABORT, CONCAT_NULL_YIELDS_NULL, QUOTED_IDENTIFIER ON` and
+    /// `SET NUMERIC_ROUNDABORT OFF`. With any of these set differently, creating such an
+    /// index or modifying a table that has one fails with error 1934 ("... failed because
+    /// the following SET options have incorrect settings"). Most drivers already log in
+    /// with these values except `ARITHABORT`, which is what SSMS sets and the optimizer
+    /// requires to use an indexed view.
+    ///
+    /// The options are session-scoped and connections are not reset when returned to a
+    /// pool, so a `SET QUOTED_IDENTIFIER OFF` executed on a pooled connection persists and
+    /// breaks DDL on indexed views for its later users. Stored procedures and triggers also
+    /// capture `ANSI_NULLS` and `QUOTED_IDENTIFIER` when they are created, so create them
+    /// with these options on.
+    pub fn required_set_options(mut self, enabled: bool) -> Self {
+        self.required_set_options = enabled;
+        self
+    }
+
+    /// Get whether the `SET` options required by indexed views are issued on connect.
+    pub fn get_required_set_options(&self) -> bool {
+        self.required_set_options
+    }
+
+    /// Sets whether to issue `SET NOCOUNT ON` when connecting.
+    ///
+    /// With `NOCOUNT` on, the server leaves the row count out of the DONE token it sends
+    /// after each statement. That saves network traffic for stored procedures and batches
+    /// that run many statements, e.g. loops of single-row updates.
+    ///
+    /// It also changes [`rows_affected`][crate::MssqlQueryResult::rows_affected]: the counts
+    /// of parameterized queries come from those DONE tokens, so `execute` and
+    /// `execute_many` report 0 for their `INSERT`, `UPDATE` and `DELETE` statements, and so
+    /// do helpers that add them up, such as [`insert_chunked`][crate::MssqlConnection::insert_chunked].
+    /// Counts of queries without parameters are those of the rows returned, and stay the
+    /// same. `@@ROWCOUNT` is still maintained, so
+    /// [`last_rowcount`][crate::MssqlConnection::last_rowcount] and an `OUTPUT` clause
+    /// still tell how many rows a statement changed.
+    ///
+    /// The default is `false`, which leaves the server's default (`NOCOUNT OFF`) in place.
+    pub fn no_count(mut self, enabled: bool) -> Self {
+        self.no_count = enabled;
+        self
+    }
+
+    /// Get whether `SET NOCOUNT ON` is issued on connect.
+    pub fn get_no_count(&self) -> bool {
+        self.no_count
+    }
+
+    /// Sets the server collation this application expects, checked on connect.
+    ///
+    /// When set, every new connection reads `SERVERPROPERTY('Collation')` and fails with
+    /// [`Error::Configuration`] if it differs from `collation` (compared
+    /// case-insensitively). This catches environment drift, such as a staging server
+    /// installed with a case-sensitive collation, before it shows up as comparisons,
+    /// `GROUP BY` or unique constraints behaving differently than in production.
+    ///
+    /// Only the server collation is checked; databases and columns can override it.
+    /// The default is `None`, which skips the check and its round-trip.
+    pub fn expected_collation(mut self, collation: &str) -> Self {
+        self.expected_collation = Some(collation.to_owned());
+        self
+    }
+
+    /// Get the server collation checked on connect, if any.
+    pub fn get_expected_collation(&self) -> Option<&str> {
+        self.expected_collation.as_deref()
+    }
+
+    /// Sets whether `INSERT` statements report the identity value they generated.
+    ///
+    /// When enabled, every query starting with `INSERT` is followed by
+    /// `SELECT SCOPE_IDENTITY(), ROWCOUNT_BIG()` in the same batch, and the result is
+    /// exposed as [`MssqlQueryResult::last_insert_id`][crate::MssqlQueryResult::last_insert_id]
+    /// (and `AnyQueryResult::last_insert_id`). This saves adding `OUTPUT INSERTED.id` to
+    /// every insert, at the cost of one extra result set per `INSERT`.
+    ///
+    /// `SCOPE_IDENTITY()` is the last identity value generated by the batch, so for a batch
+    /// of several statements it belongs to its last insert, and since the batch is then run
+    /// as a single query, `execute_many` reports one result for it. It is `None` when no
+    /// identity value was generated. The default is `false`.
+    pub fn capture_last_insert_id(mut self, enabled: bool) -> Self {
+        self.capture_last_insert_id = enabled;
+        self
+    }
+
+    /// Get whether `INSERT` statements report the identity value they generated.
+    pub fn get_capture_last_insert_id(&self) -> bool {
+        self.capture_last_insert_id
+    }
+
+    /// Sets how long a query may wait on the server before it fails with an [`Error::Io`] of
+    /// kind [`std::io::ErrorKind::TimedOut`].
+    ///
+    /// Only the time spent waiting for the server counts, not the time spent consuming rows
+    /// that have already arrived. Can be changed per connection with
+    /// [`MssqlConnection::set_command_timeout`][crate::MssqlConnection::set_command_timeout].
+    /// The default is no timeout.
+    ///
+    /// tiberius cannot send the attention signal that cancels a running request, so once a
+    /// query times out the connection is unusable: every later request on it, including
+    /// `ping`, fails with an [`Error::Io`] of kind [`std::io::ErrorKind::NotConnected`]. A
+    /// pool closes such a connection instead of handing it out again, and closing it makes
+    /// the server abort the statement.
+    pub fn command_timeout(mut self, timeout: Duration) -> Self {
+        self.command_timeout = Some(timeout).filter(|timeout| !timeout.is_zero());
+        self
+    }
+
+    /// Get the timeout for waiting on the server, if any.
+    pub fn get_command_timeout(&self) -> Option<Duration> {
+        self.command_timeout
+    }
+
+    /// How long to wait before retrying a connect attempt that failed with `error`, or
+    /// `None` if it should not be retried. `attempt` counts the retries already made.
+    pub(crate) fn connect_retry_delay(&self, attempt: u32, error: &Error) -> Option<Duration> {
+        if attempt >= self.connect_retries || !crate::error::is_retryable(error) {
+            return None;
+        }
+
+        Some(
+            self.connect_retry_backoff
+                .saturating_mul(2u32.saturating_pow(attempt)),
+        )
+    }
+
     /// Get the current host.
     pub fn get_host(&self) -> &str {
         &self.host
@@ -262,13 +661,54 @@ impl MssqlConnectOptions {
         self.database.as_deref()
     }
 
+    /// Return a copy of these options with the password and AAD token replaced by `****`,
+    /// safe to log or include in error reports.
+    ///
+    /// The copy is only meant for display (e.g. via [`ConnectOptions::to_url_lossy`]); it
+    /// can't be used to log in.
+    ///
+    /// [`ConnectOptions::to_url_lossy`]: sqlx_core::connection::ConnectOptions::to_url_lossy
+    pub fn redacted(&self) -> Self {
+        let mut options = self.clone();
+        if options.password.is_some() {
+            options.password = Some(REDACTED.to_owned());
+        }
+        if options.aad_token.is_some() {
+            options.aad_token = Some(REDACTED.to_owned());
+        }
+        options
+    }
+
+    /// Name of the authentication method [`to_tiberius_config`](Self::to_tiberius_config)
+    /// selects, for diagnostics.
+    pub(crate) fn auth_method(&self) -> &'static str {
+        if self.aad_token.is_some() {
+            return "aad_token";
+        }
+
+        #[cfg(any(
+            all(windows, feature = "winauth"),
+            all(unix, feature = "integrated-auth-gssapi")
+        ))]
+        if self.integrated_auth {
+            return "integrated";
+        }
+
+        #[cfg(all(windows, feature = "winauth"))]
+        if self.windows_auth {
+            return "windows";
+        }
+
+        "sql_server"
+    }
+
     /// Build a `tiberius::Config` from these options.
     pub(crate) fn to_tiberius_config(&self) -> tiberius::Config {
         let mut config = tiberius::Config::new();
 
         config.host(&self.host);
         config.port(self.port);
-        config.application_name(&self.app_name);
+        config.application_name(self.get_program_name());
 
         if let Some(database) = &self.database {
             config.database(database);
@@ -321,11 +761,11 @@ impl MssqlConnectOptions {
             config.readonly(true);
         }
 
-        config.encryption(match self.ssl_mode {
+        config.encryption(match self.effective_ssl_mode() {
             MssqlSslMode::Disabled => tiberius::EncryptionLevel::NotSupported,
             MssqlSslMode::LoginOnly => tiberius::EncryptionLevel::Off,
             MssqlSslMode::Preferred => tiberius::EncryptionLevel::On,
-            MssqlSslMode::Required => tiberius::EncryptionLevel::Required,
+            MssqlSslMode::Required | MssqlSslMode::Strict => tiberius::EncryptionLevel::Required,
         });
 
         config
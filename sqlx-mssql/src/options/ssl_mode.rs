@@ -1,7 +1,7 @@
 /// The SSL mode to use when connecting to MSSQL.
 ///
 /// Maps to the tiberius `EncryptionLevel` variants.
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum MssqlSslMode {
     /// No encryption at all (`EncryptionLevel::NotSupported`).
     Disabled,
@@ -15,4 +15,32 @@ pub enum MssqlSslMode {
 
     /// Always encrypt; fail if the server doesn't support it (`EncryptionLevel::Required`).
     Required,
+
+    /// Always encrypt and validate the server certificate, as `Encrypt=Strict` does in
+    /// other drivers (`EncryptionLevel::Required`).
+    ///
+    /// The certificate must chain to a trusted root or to
+    /// [`trust_server_certificate_ca`][crate::MssqlConnectOptions::trust_server_certificate_ca];
+    /// connecting fails if [`trust_server_certificate`][crate::MssqlConnectOptions::trust_server_certificate]
+    /// is set without a CA.
+    ///
+    /// TLS is still negotiated after PRELOGIN, since the TDS 8.0 handshake (TLS before any
+    /// TDS traffic) is not supported yet, so servers configured with "Force Strict
+    /// Encryption" reject the connection.
+    Strict,
+}
+
+/// The encryption actually in effect on an open connection, returned by
+/// [`MssqlConnection::encryption_level`][crate::MssqlConnection::encryption_level].
+///
+/// This can differ from the requested [`MssqlSslMode`]: `Preferred` falls back to an
+/// unencrypted session when the server does not support TLS, and `LoginOnly` encrypts the
+/// login packet only, so it reports `NotEncrypted`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MssqlEncryptionLevel {
+    /// Session traffic is sent in plain text.
+    NotEncrypted,
+
+    /// All traffic after the handshake is encrypted with TLS.
+    Encrypted,
 }
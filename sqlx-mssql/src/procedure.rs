@@ -0,0 +1,277 @@
+use std::fmt::Write;
+
+use sqlx_core::error::BoxDynError;
+
+use crate::decode::Decode;
+use crate::encode::Encode;
+use crate::error::Error;
+use crate::ident::quote_object_name;
+use crate::row::Row;
+use crate::type_info::is_plain_sql_type;
+use crate::types::Type;
+use crate::{Mssql, MssqlArguments, MssqlRow};
+
+/// The parameters of a stored procedure call, passed to
+/// [`MssqlConnection::call_procedure`][crate::MssqlConnection::call_procedure].
+///
+/// Parameters are passed by position, in the order they are added. `OUTPUT` parameters are
+/// declared as variables of the given SQL type, so their values can be read back from the
+/// [`MssqlProcedureResult`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+/// use sqlx::mssql::MssqlProcedureParams;
+///
+/// let params = MssqlProcedureParams::new()
+///     .bind(42_i32)
+///     .bind_output("BIGINT")
+///     .bind_in_out("NVARCHAR(100)", "pending");
+///
+/// let result = conn.call_procedure("dbo.close_account", params).await?;
+///
+/// let balance: i64 = result.output(0)?;
+/// let status: String = result.output(1)?;
+/// if result.return_value() != 0 {
+///     // legacy status code
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct MssqlProcedureParams {
+    arguments: Result<MssqlArguments, BoxDynError>,
+    params: Vec<Param>,
+}
+
+#[derive(Debug)]
+enum Param {
+    /// An input parameter, bound as `@pN`.
+    Input(usize),
+    /// An `OUTPUT` parameter, with its SQL type and the `@pN` of its initial value, if any.
+    Output {
+        sql_type: String,
+        initial: Option<usize>,
+    },
+}
+
+impl Default for MssqlProcedureParams {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MssqlProcedureParams {
+    pub fn new() -> Self {
+        Self {
+            arguments: Ok(MssqlArguments::default()),
+            params: Vec::new(),
+        }
+    }
+
+    /// Add an input parameter.
+    pub fn bind<'q, T>(mut self, value: T) -> Self
+    where
+        T: Encode<'q, Mssql> + Type<Mssql>,
+    {
+        if let Some(index) = self.add_argument(value) {
+            self.params.push(Param::Input(index));
+        }
+        self
+    }
+
+    /// Add an `OUTPUT` parameter of type `sql_type`, e.g. `INT` or `NVARCHAR(100)`, starting
+    /// out as `NULL`.
+    ///
+    /// Give string and decimal types their length or precision: a bare `NVARCHAR` is
+    /// `NVARCHAR(1)` and truncates the value.
+    pub fn bind_output(mut self, sql_type: &str) -> Self {
+        self.params.push(Param::Output {
+            sql_type: sql_type.to_owned(),
+            initial: None,
+        });
+        self
+    }
+
+    /// Add an `OUTPUT` parameter of type `sql_type` that passes `value` in as well.
+    pub fn bind_in_out<'q, T>(mut self, sql_type: &str, value: T) -> Self
+    where
+        T: Encode<'q, Mssql> + Type<Mssql>,
+    {
+        if let Some(index) = self.add_argument(value) {
+            self.params.push(Param::Output {
+                sql_type: sql_type.to_owned(),
+                initial: Some(index),
+            });
+        }
+        self
+    }
+
+    /// Encode `value`, returning its 1-based placeholder index. The first error is kept and
+    /// reported when the procedure is called.
+    fn add_argument<'q, T>(&mut self, value: T) -> Option<usize>
+    where
+        T: Encode<'q, Mssql> + Type<Mssql>,
+    {
+        let arguments = self.arguments.as_mut().ok()?;
+
+        match arguments.add(value) {
+            Ok(()) => Some(arguments.values.len()),
+            Err(error) => {
+                self.arguments = Err(error);
+                None
+            }
+        }
+    }
+
+    /// Build the batch calling `procedure`: the `OUTPUT` parameters are declared as
+    /// variables, passed to `EXEC`, and selected together with the return code at the end.
+    pub(crate) fn into_batch(
+        self,
+        procedure: &str,
+    ) -> Result<(String, Result<MssqlArguments, BoxDynError>), Error> {
+        let procedure = quote_object_name(procedure)?;
+
+        let mut declare = String::from("DECLARE @__sqlx_return INT");
+        let mut exec = format!("EXEC @__sqlx_return = {procedure}");
+        let mut select = String::from("SELECT @__sqlx_return");
+        let mut outputs = 0;
+
+        for (i, param) in self.params.iter().enumerate() {
+            exec.push_str(if i == 0 { " " } else { ", " });
+
+            match param {
+                Param::Input(index) => {
+                    let _ = write!(exec, "@p{index}");
+                }
+                Param::Output { sql_type, initial } => {
+                    check_sql_type(sql_type)?;
+                    outputs += 1;
+
+                    let _ = write!(declare, ", @__sqlx_out{outputs} {sql_type}");
+                    if let Some(index) = initial {
+                        let _ = write!(declare, " = @p{index}");
+                    }
+                    let _ = write!(exec, "@__sqlx_out{outputs} OUTPUT");
+                    let _ = write!(select, ", @__sqlx_out{outputs}");
+                }
+            }
+        }
+
+        Ok((format!("{declare};\n{exec};\n{select};"), self.arguments))
+    }
+}
+
+/// Reject anything but a plain type name such as `DECIMAL(19, 4)` or `VARBINARY(MAX)`, since
+/// the type is written into the batch as is.
+fn check_sql_type(sql_type: &str) -> Result<(), Error> {
+    if !is_plain_sql_type(sql_type) {
+        return Err(Error::InvalidArgument(format!(
+            "invalid OUTPUT parameter type {sql_type:?}"
+        )));
+    }
+
+    Ok(())
+}
+
+/// The outcome of [`MssqlConnection::call_procedure`][crate::MssqlConnection::call_procedure]:
+/// the result sets of the procedure, the final values of its `OUTPUT` parameters and its
+/// return code.
+#[derive(Debug)]
+pub struct MssqlProcedureResult {
+    pub(crate) result_sets: Vec<Vec<MssqlRow>>,
+    pub(crate) return_value: i32,
+    /// The return code followed by the `OUTPUT` parameters.
+    pub(crate) outputs: MssqlRow,
+}
+
+impl MssqlProcedureResult {
+    /// The value of `RETURN n` in the procedure; `0` if it returned without a value.
+    pub fn return_value(&self) -> i32 {
+        self.return_value
+    }
+
+    /// Decode the final value of the `OUTPUT` parameter at `index`, counting only `OUTPUT`
+    /// parameters (including those added with
+    /// [`bind_in_out`][MssqlProcedureParams::bind_in_out]).
+    ///
+    /// Decode into an `Option` if the parameter can be `NULL`.
+    pub fn output<'r, T>(&'r self, index: usize) -> Result<T, Error>
+    where
+        T: Decode<'r, Mssql> + Type<Mssql>,
+    {
+        let len = self.outputs.len().saturating_sub(1);
+        if index >= len {
+            return Err(Error::ColumnIndexOutOfBounds { index, len });
+        }
+
+        self.outputs.try_get(index + 1)
+    }
+
+    /// The result sets returned by the procedure, in order.
+    pub fn result_sets(&self) -> &[Vec<MssqlRow>] {
+        &self.result_sets
+    }
+
+    /// Take the result sets returned by the procedure.
+    pub fn into_result_sets(self) -> Vec<Vec<MssqlRow>> {
+        self.result_sets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_builds_the_call_batch() {
+        let params = MssqlProcedureParams::new()
+            .bind(1_i32)
+            .bind_output("DECIMAL(19, 4)")
+            .bind("a")
+            .bind_in_out("NVARCHAR(10)", "b");
+
+        let (sql, arguments) = params.into_batch("dbo.transfer").unwrap();
+        assert_eq!(
+            sql,
+            "DECLARE @__sqlx_return INT, @__sqlx_out1 DECIMAL(19, 4), \
+             @__sqlx_out2 NVARCHAR(10) = @p3;\n\
+             EXEC @__sqlx_return = [dbo].[transfer] @p1, @__sqlx_out1 OUTPUT, @p2, \
+             @__sqlx_out2 OUTPUT;\n\
+             SELECT @__sqlx_return, @__sqlx_out1, @__sqlx_out2;"
+        );
+        assert_eq!(arguments.unwrap().values.len(), 3);
+    }
+
+    #[test]
+    fn it_builds_a_call_without_parameters() {
+        let (sql, _) = MssqlProcedureParams::new().into_batch("cleanup").unwrap();
+        assert_eq!(
+            sql,
+            "DECLARE @__sqlx_return INT;\n\
+             EXEC @__sqlx_return = [cleanup];\n\
+             SELECT @__sqlx_return;"
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_names_and_types() {
+        for sql_type in [
+            "INT; DROP TABLE users",
+            "INT DROP TABLE users",
+            "DECIMAL(19, 4) DROP TABLE users",
+            "NVARCHAR)(10",
+            "DECIMAL(1, 2, 3)",
+            "VARCHAR(x)",
+        ] {
+            let params = MssqlProcedureParams::new().bind_output(sql_type);
+            assert!(
+                matches!(params.into_batch("dbo.p"), Err(Error::InvalidArgument(_))),
+                "{sql_type:?}"
+            );
+        }
+
+        assert!(MssqlProcedureParams::new().into_batch("a.b.c.d").is_err());
+    }
+}
@@ -0,0 +1,1358 @@
+use std::fmt::Display;
+
+use crate::arguments::MAX_PARAMETERS;
+use crate::capabilities::MssqlServerCapabilities;
+use crate::error::Error;
+use crate::ident::{quote_identifier, quote_object_name, validate_part};
+use crate::query_builder::{QueryBuilder, Separated};
+#[cfg(feature = "json")]
+use crate::types::{Json, Type};
+use crate::Mssql;
+
+/// The most rows SQL Server accepts in a single `VALUES` clause of an `INSERT`.
+pub(crate) const MAX_VALUES_ROWS: usize = 1000;
+
+/// The most rows of `params_per_row` bound values that fit in one `INSERT ... VALUES`
+/// statement, within both the row limit and the parameter limit.
+pub(crate) fn values_chunk_size(params_per_row: usize) -> usize {
+    match params_per_row {
+        0 => MAX_VALUES_ROWS,
+        n => (MAX_PARAMETERS / n).clamp(1, MAX_VALUES_ROWS),
+    }
+}
+
+/// Lower the number of every `@p<n>` placeholder in `sql` by `offset`, so SQL rendered after
+/// `offset` other parameters can be sent with its own parameters only.
+///
+/// String literals are copied as they are.
+pub(crate) fn shift_placeholders(sql: &str, offset: usize) -> String {
+    let is_ident_char = |c: char| c.is_alphanumeric() || matches!(c, '_' | '@' | '#' | '$');
+    let mut shifted = String::with_capacity(sql.len());
+
+    // Splitting on quotes puts the literals at odd indices, including `''` escapes.
+    for (i, part) in sql.split('\'').enumerate() {
+        if i > 0 {
+            shifted.push('\'');
+        }
+        if i % 2 == 1 {
+            shifted.push_str(part);
+            continue;
+        }
+
+        let mut rest = part;
+        while let Some(at) = rest.find("@p") {
+            let (before, placeholder) = rest.split_at(at);
+            shifted.push_str(before);
+
+            let digits = placeholder[2..]
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(placeholder.len() - 2);
+            let (placeholder, after) = placeholder.split_at(2 + digits);
+            let standalone = !before.ends_with(is_ident_char) && !after.starts_with(is_ident_char);
+
+            match placeholder[2..].parse::<usize>() {
+                Ok(n) if standalone && n > offset => {
+                    shifted.push_str(&format!("@p{}", n - offset));
+                }
+                _ => shifted.push_str(placeholder),
+            }
+            rest = after;
+        }
+        shifted.push_str(rest);
+    }
+
+    shifted
+}
+
+/// Sort direction for [`MssqlQueryBuilderExt::push_order_by`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MssqlSortDirection {
+    /// `ASC`, smallest first.
+    #[default]
+    Asc,
+    /// `DESC`, largest first.
+    Desc,
+}
+
+impl MssqlSortDirection {
+    fn as_sql(self) -> &'static str {
+        match self {
+            MssqlSortDirection::Asc => "ASC",
+            MssqlSortDirection::Desc => "DESC",
+        }
+    }
+}
+
+/// A table or query hint for [`MssqlQueryBuilderExt::push_table_hints`] and
+/// [`MssqlQueryBuilderExt::push_query_hints`].
+///
+/// Table hints ([`is_table_hint`](Self::is_table_hint)) go in a `WITH (...)` clause after
+/// the table they apply to; the others are query hints, which go in the `OPTION (...)` clause
+/// at the end of the statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MssqlHint {
+    /// `NOLOCK`: read without shared locks, possibly seeing uncommitted data. Conflicts with
+    /// `UPDLOCK` and `HOLDLOCK`.
+    NoLock,
+    /// `UPDLOCK`: take update locks on the rows read.
+    UpdLock,
+    /// `HOLDLOCK`: hold the locks until the transaction ends, as `SERIALIZABLE` does.
+    HoldLock,
+    /// `FORCESEEK`: only use an index seek to access the table.
+    ForceSeek,
+    /// `INDEX([name])`: use the named index.
+    Index(String),
+    /// `RECOMPILE`: compile a fresh plan for this execution and don't cache it.
+    Recompile,
+    /// `MAXDOP n`: use at most `n` processors; `0` lets the server decide.
+    MaxDop(u16),
+    /// `OPTIMIZE FOR UNKNOWN`: plan for average rather than the sniffed parameter values.
+    OptimizeForUnknown,
+}
+
+impl MssqlHint {
+    /// Whether the hint goes in a table's `WITH (...)` clause rather than in `OPTION (...)`.
+    pub fn is_table_hint(&self) -> bool {
+        matches!(
+            self,
+            MssqlHint::NoLock
+                | MssqlHint::UpdLock
+                | MssqlHint::HoldLock
+                | MssqlHint::ForceSeek
+                | MssqlHint::Index(_)
+        )
+    }
+
+    fn keyword(&self) -> &'static str {
+        match self {
+            MssqlHint::NoLock => "NOLOCK",
+            MssqlHint::UpdLock => "UPDLOCK",
+            MssqlHint::HoldLock => "HOLDLOCK",
+            MssqlHint::ForceSeek => "FORCESEEK",
+            MssqlHint::Index(_) => "INDEX",
+            MssqlHint::Recompile => "RECOMPILE",
+            MssqlHint::MaxDop(_) => "MAXDOP",
+            MssqlHint::OptimizeForUnknown => "OPTIMIZE FOR UNKNOWN",
+        }
+    }
+
+    fn to_sql(&self) -> Result<String, Error> {
+        Ok(match self {
+            MssqlHint::Index(name) => format!("INDEX({})", quote_identifier(&validate_part(name)?)),
+            MssqlHint::MaxDop(n) => format!("MAXDOP {n}"),
+            hint => hint.keyword().to_owned(),
+        })
+    }
+
+    /// Whether SQL Server rejects `self` and `other` in the same clause: a hint given twice,
+    /// or `NOLOCK` with a hint that takes locks.
+    fn conflicts_with(&self, other: &MssqlHint) -> bool {
+        use MssqlHint::{HoldLock, NoLock, UpdLock};
+
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+            || matches!(
+                (self, other),
+                (NoLock, UpdLock | HoldLock) | (UpdLock | HoldLock, NoLock)
+            )
+    }
+}
+
+/// Render `hints` as a comma-separated list, checking they all belong in the table (or
+/// query) hint clause and can be combined.
+fn render_hints(hints: &[MssqlHint], table: bool) -> Result<String, Error> {
+    if hints.is_empty() {
+        return Err(Error::InvalidArgument("hint list is empty".into()));
+    }
+
+    for (i, hint) in hints.iter().enumerate() {
+        if hint.is_table_hint() != table {
+            let (kind, method) = if table {
+                ("query", "push_query_hints")
+            } else {
+                ("table", "push_table_hints")
+            };
+            return Err(Error::InvalidArgument(format!(
+                "{} is a {kind} hint; push it with {method}",
+                hint.keyword()
+            )));
+        }
+
+        if let Some(other) = hints[..i].iter().find(|other| other.conflicts_with(hint)) {
+            return Err(Error::InvalidArgument(format!(
+                "hints {} and {} cannot be combined",
+                other.keyword(),
+                hint.keyword()
+            )));
+        }
+    }
+
+    let hints = hints
+        .iter()
+        .map(MssqlHint::to_sql)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(hints.join(", "))
+}
+
+/// Granularity of the buckets pushed by [`MssqlQueryBuilderExt::push_date_bucket`].
+///
+/// Weeks are left out on purpose: `DATETRUNC(week, ...)` starts weeks on `@@DATEFIRST`,
+/// while `DATEDIFF(week, ...)` always counts Sunday boundaries, so the two expressions would
+/// disagree on servers not using `SET DATEFIRST 7`. Seconds are left out because
+/// `DATEDIFF(second, 0, ...)` overflows `INT` for dates after 1968.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MssqlDatePart {
+    Year,
+    Quarter,
+    Month,
+    Day,
+    Hour,
+    Minute,
+}
+
+impl MssqlDatePart {
+    fn as_sql(self) -> &'static str {
+        match self {
+            MssqlDatePart::Year => "year",
+            MssqlDatePart::Quarter => "quarter",
+            MssqlDatePart::Month => "month",
+            MssqlDatePart::Day => "day",
+            MssqlDatePart::Hour => "hour",
+            MssqlDatePart::Minute => "minute",
+        }
+    }
+}
+
+/// A column of the `WITH` clause pushed by [`MssqlQueryBuilderExt::push_openjson`]: its
+/// name, which is also the key read from each JSON object, and its SQL Server type.
+#[cfg(feature = "json")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MssqlJsonColumn {
+    name: String,
+    sql_type: String,
+}
+
+#[cfg(feature = "json")]
+impl MssqlJsonColumn {
+    /// A column of an explicit SQL Server type, e.g. `"NVARCHAR(100)"` or `"DECIMAL(10, 2)"`.
+    ///
+    /// Returns [`Error::InvalidArgument`] if `name` is empty, longer than 128 characters or
+    /// contains control characters, or if `sql_type` is anything but a type name with an
+    /// optional parenthesized length, precision or scale.
+    pub fn new(name: &str, sql_type: &str) -> Result<Self, Error> {
+        let sql_type = sql_type.trim();
+
+        if !crate::type_info::is_plain_sql_type(sql_type) {
+            return Err(Error::InvalidArgument(format!(
+                "invalid SQL type {sql_type:?} for OPENJSON column {name:?}"
+            )));
+        }
+
+        Ok(Self {
+            name: json_column_name(name)?,
+            sql_type: sql_type.to_owned(),
+        })
+    }
+
+    /// A column of the SQL Server type `T` binds as, e.g. `INT` for `i32`.
+    ///
+    /// Strings are read as `NVARCHAR(MAX)`. `DECIMAL` needs a precision and scale, and JSON
+    /// has no binary values, so for `rust_decimal::Decimal`, `bigdecimal::BigDecimal`,
+    /// `i128`, byte arrays and [`MssqlVariant`][crate::MssqlVariant] this returns
+    /// [`Error::InvalidArgument`]; declare those with [`new`](Self::new) instead.
+    pub fn of<T: Type<Mssql> + ?Sized>(name: &str) -> Result<Self, Error> {
+        let type_info = T::type_info();
+
+        let sql_type = match type_info.base_name() {
+            "NVARCHAR" | "VARCHAR" | "NCHAR" | "CHAR" | "NTEXT" | "TEXT" | "XML" => {
+                "NVARCHAR(MAX)".to_owned()
+            }
+            "DECIMAL" | "NUMERIC" | "VARBINARY" | "BINARY" | "IMAGE" | "SQL_VARIANT"
+                if !type_info.name.contains('(') =>
+            {
+                return Err(Error::InvalidArgument(format!(
+                    "cannot infer the OPENJSON type of column {name:?} from {type_info}; \
+                     declare it with MssqlJsonColumn::new"
+                )));
+            }
+            _ => type_info.name,
+        };
+
+        Ok(Self {
+            name: json_column_name(name)?,
+            sql_type,
+        })
+    }
+}
+
+/// MSSQL-specific extensions to [`QueryBuilder`].
+pub trait MssqlQueryBuilderExt {
+    /// Append a query hint clause, `OPTION (<hints>)`, to the current statement.
+    ///
+    /// `hints` is the comma-separated hint list without the surrounding `OPTION (...)`,
+    /// e.g. `"RECOMPILE"` or `"MAXDOP 1, OPTIMIZE FOR UNKNOWN"`.
+    ///
+    /// SQL Server requires `OPTION` to be the last clause of a statement and allows only one
+    /// per statement, so push it after everything else. Returns [`Error::InvalidArgument`]
+    /// if the current statement already has an `OPTION` clause, or if `hints` is empty,
+    /// has unbalanced parentheses, or contains a statement terminator or comment.
+    ///
+    /// ### Note
+    /// Hints are pushed as raw SQL and cannot be bound as parameters. Never build them from
+    /// untrusted input.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn example() -> sqlx::Result<()> {
+    /// use sqlx::mssql::{Mssql, MssqlQueryBuilderExt};
+    /// use sqlx::QueryBuilder;
+    ///
+    /// let mut qb = QueryBuilder::<Mssql>::new("SELECT * FROM users WHERE id = ");
+    /// qb.push_bind(42i32);
+    /// qb.push_option_hint("RECOMPILE")?;
+    ///
+    /// assert_eq!(qb.sql(), "SELECT * FROM users WHERE id = @p1 OPTION (RECOMPILE)");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn push_option_hint(&mut self, hints: &str) -> Result<&mut Self, Error>;
+
+    /// Append a table hint clause, `WITH (<hints>)`, after a table reference.
+    ///
+    /// Push it directly after the table name or alias it applies to, e.g.
+    /// `FROM users u WITH (NOLOCK)`. `hints` is the comma-separated hint list without the
+    /// surrounding `WITH (...)`, e.g. `"NOLOCK"` or `"UPDLOCK, HOLDLOCK"`.
+    ///
+    /// Table hints override the transaction isolation level for that table:
+    ///
+    /// * `NOLOCK` / `READUNCOMMITTED` read without shared locks and may return uncommitted
+    ///   ("dirty") data, rows twice, or skip rows entirely while pages are being moved.
+    ///   Only use it where approximate results are acceptable.
+    /// * `UPDLOCK, HOLDLOCK` take update locks and hold them until the end of the transaction,
+    ///   the usual way to make a read-then-write "upsert" safe against concurrent writers.
+    /// * `ROWLOCK`, `PAGLOCK`, `TABLOCK`, `TABLOCKX` choose the lock granularity.
+    ///
+    /// Returns [`Error::InvalidArgument`] if `hints` is empty, has unbalanced parentheses,
+    /// or contains a statement terminator or comment.
+    ///
+    /// ### Note
+    /// Hints are pushed as raw SQL and cannot be bound as parameters. Never build them from
+    /// untrusted input.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn example() -> sqlx::Result<()> {
+    /// use sqlx::mssql::{Mssql, MssqlQueryBuilderExt};
+    /// use sqlx::QueryBuilder;
+    ///
+    /// let mut qb = QueryBuilder::<Mssql>::new("SELECT * FROM users");
+    /// qb.push_table_hint("NOLOCK")?;
+    /// qb.push(" WHERE id = ").push_bind(42i32);
+    ///
+    /// assert_eq!(qb.sql(), "SELECT * FROM users WITH (NOLOCK) WHERE id = @p1");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn push_table_hint(&mut self, hints: &str) -> Result<&mut Self, Error>;
+
+    /// Like [`push_table_hint`](Self::push_table_hint), but with typed hints, e.g.
+    /// `[MssqlHint::UpdLock, MssqlHint::HoldLock]` for ` WITH (UPDLOCK, HOLDLOCK)`.
+    ///
+    /// Returns [`Error::InvalidArgument`] if `hints` is empty or includes a query hint,
+    /// the same hint twice, `NOLOCK` together with `UPDLOCK` or `HOLDLOCK`, or an
+    /// [`Index`](MssqlHint::Index) name that is empty, longer than 128 characters or
+    /// contains control characters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn example() -> sqlx::Result<()> {
+    /// use sqlx::mssql::{Mssql, MssqlHint, MssqlQueryBuilderExt};
+    /// use sqlx::QueryBuilder;
+    ///
+    /// let mut qb = QueryBuilder::<Mssql>::new("SELECT * FROM orders");
+    /// qb.push_table_hints(&[MssqlHint::ForceSeek, MssqlHint::Index("ix_customer".into())])?;
+    /// qb.push(" WHERE customer_id = ").push_bind(7i32);
+    /// qb.push_query_hints(&[MssqlHint::Recompile, MssqlHint::MaxDop(1)])?;
+    ///
+    /// assert_eq!(
+    ///     qb.sql(),
+    ///     "SELECT * FROM orders WITH (FORCESEEK, INDEX([ix_customer])) \
+    ///      WHERE customer_id = @p1 OPTION (RECOMPILE, MAXDOP 1)"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn push_table_hints(&mut self, hints: &[MssqlHint]) -> Result<&mut Self, Error>;
+
+    /// Like [`push_option_hint`](Self::push_option_hint), but with typed hints, e.g.
+    /// `[MssqlHint::Recompile]` for ` OPTION (RECOMPILE)`.
+    ///
+    /// Returns [`Error::InvalidArgument`] if `hints` is empty or includes a table hint or the
+    /// same hint twice, or if the current statement already has an `OPTION` clause.
+    fn push_query_hints(&mut self, hints: &[MssqlHint]) -> Result<&mut Self, Error>;
+
+    /// Append an optimistic-concurrency predicate, `[<column>] = @pN`, binding the
+    /// `ROWVERSION` value the row was read with.
+    ///
+    /// Push it as the last condition of the `WHERE` clause of an `UPDATE` or `DELETE`. If
+    /// another writer changed the row in the meantime, its rowversion no longer matches and
+    /// the statement touches no rows, which the caller should treat as a conflict.
+    ///
+    /// Add `OUTPUT INSERTED.<column>` to the statement so it returns the new rowversion of
+    /// the updated row: [`rows_affected()`][crate::MssqlQueryResult::rows_affected] is then
+    /// `1` on success and `0` on conflict.
+    ///
+    /// Returns [`Error::InvalidArgument`] if `rowversion` isn't exactly 8 bytes, the size of
+    /// a `ROWVERSION` value; a shorter or longer value would never match and every update
+    /// would look like a conflict.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn example() -> sqlx::Result<()> {
+    /// use sqlx::mssql::{Mssql, MssqlQueryBuilderExt};
+    /// use sqlx::QueryBuilder;
+    ///
+    /// let version = [0, 0, 0, 0, 0, 0, 0x07, 0xd1];
+    ///
+    /// let mut qb = QueryBuilder::<Mssql>::new("UPDATE orders SET qty = ");
+    /// qb.push_bind(5i32);
+    /// qb.push(" OUTPUT INSERTED.version WHERE id = ").push_bind(42i32);
+    /// qb.push(" AND ");
+    /// qb.push_rowversion_check("version", &version)?;
+    ///
+    /// assert_eq!(
+    ///     qb.sql(),
+    ///     "UPDATE orders SET qty = @p1 OUTPUT INSERTED.version WHERE id = @p2 AND [version] = @p3"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn push_rowversion_check(
+        &mut self,
+        column: &str,
+        rowversion: &[u8],
+    ) -> Result<&mut Self, Error>;
+
+    /// Append ` EXCEPT (<subquery>)`, keeping only the rows of the query built so far that
+    /// the subquery doesn't return.
+    ///
+    /// `subquery` is called with the builder, between the parentheses, to push the subquery
+    /// and bind its parameters. Parenthesizing each operand keeps `ORDER BY` / `TOP` in one
+    /// operand from applying to the others; put an `ORDER BY` for the combined result after
+    /// the last operand.
+    ///
+    /// ### Note
+    /// `INTERSECT` binds tighter than `EXCEPT` and `UNION`, so `a EXCEPT (b) INTERSECT (c)`
+    /// is `a EXCEPT (b INTERSECT c)`. Chains of the same operator are evaluated left to
+    /// right. Matching column counts and types is up to the server.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use sqlx::mssql::{Mssql, MssqlQueryBuilderExt};
+    /// use sqlx::QueryBuilder;
+    ///
+    /// let mut qb = QueryBuilder::<Mssql>::new("SELECT id FROM customers");
+    /// qb.push_except(|qb| {
+    ///     qb.push("SELECT customer_id FROM blocked WHERE reason = ")
+    ///         .push_bind("fraud");
+    /// });
+    ///
+    /// assert_eq!(
+    ///     qb.sql(),
+    ///     "SELECT id FROM customers EXCEPT (SELECT customer_id FROM blocked WHERE reason = @p1)"
+    /// );
+    /// ```
+    fn push_except<F>(&mut self, subquery: F) -> &mut Self
+    where
+        F: FnOnce(&mut Self);
+
+    /// Append ` INTERSECT (<subquery>)`, keeping only the rows of the query built so far
+    /// that the subquery also returns.
+    ///
+    /// See [`push_except`](Self::push_except) for how `subquery` is pushed and how set
+    /// operators combine.
+    fn push_intersect<F>(&mut self, subquery: F) -> &mut Self
+    where
+        F: FnOnce(&mut Self);
+
+    /// Append ` ORDER BY <column> ASC|DESC`, sorting by a column chosen at runtime.
+    ///
+    /// Column names can't be bound as parameters, so `column`, typically taken straight from
+    /// a request (e.g. the column header a user clicked), is checked against the `allowed`
+    /// list first. It is matched ignoring ASCII case and the matching entry of `allowed` is
+    /// pushed, bracket-quoted; entries may be qualified, e.g. `u.name`.
+    ///
+    /// Returns [`Error::InvalidArgument`] if `column` isn't in `allowed`, or if the matching
+    /// entry isn't a valid (up to three-part) name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn example() -> sqlx::Result<()> {
+    /// use sqlx::mssql::{Mssql, MssqlQueryBuilderExt, MssqlSortDirection};
+    /// use sqlx::QueryBuilder;
+    ///
+    /// const SORTABLE: &[&str] = &["name", "created_at"];
+    ///
+    /// let mut qb = QueryBuilder::<Mssql>::new("SELECT * FROM users");
+    /// qb.push_order_by("created_at", MssqlSortDirection::Desc, SORTABLE)?;
+    /// assert_eq!(qb.sql(), "SELECT * FROM users ORDER BY [created_at] DESC");
+    ///
+    /// assert!(qb.push_order_by("1; DROP TABLE users", MssqlSortDirection::Asc, SORTABLE).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn push_order_by(
+        &mut self,
+        column: &str,
+        direction: MssqlSortDirection,
+        allowed: &[&str],
+    ) -> Result<&mut Self, Error>;
+
+    /// Append a full-text `CONTAINS(<column>, @pN)` predicate, binding `condition`.
+    ///
+    /// `condition` is passed to SQL Server as a full-text *search condition*, which has its
+    /// own syntax: `"phrase"`, `prefix*`, `AND` / `OR` / `AND NOT`, `NEAR(...)`,
+    /// `FORMSOF(...)`, etc. Binding keeps it from injecting SQL, but a user typing
+    /// `cats and dogs` still gets `AND` semantics, and unbalanced quotes are a syntax error
+    /// from the server. Use [`push_contains_phrase`](Self::push_contains_phrase) to search
+    /// for raw user input literally.
+    ///
+    /// `column` is a column name (optionally qualified, e.g. `d.body`), a parenthesized
+    /// column list such as `(title, body)`, or `*` for all full-text indexed columns.
+    /// Returns [`Error::InvalidArgument`] if `column` is invalid or `condition` is blank,
+    /// which SQL Server rejects.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn example() -> sqlx::Result<()> {
+    /// use sqlx::mssql::{Mssql, MssqlQueryBuilderExt};
+    /// use sqlx::QueryBuilder;
+    ///
+    /// let mut qb = QueryBuilder::<Mssql>::new("SELECT id FROM docs WHERE ");
+    /// qb.push_contains("body", r#""rust*" AND NOT java"#)?;
+    ///
+    /// assert_eq!(qb.sql(), "SELECT id FROM docs WHERE CONTAINS([body], @p1)");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn push_contains(&mut self, column: &str, condition: &str) -> Result<&mut Self, Error>;
+
+    /// Append a `CONTAINS(<column>, @pN)` predicate matching `phrase` literally.
+    ///
+    /// The phrase is wrapped in double quotes (with embedded `"` doubled) before binding, so
+    /// words like `AND`, `NEAR` or `*` in user input are searched for instead of being
+    /// interpreted. See [`push_contains`](Self::push_contains) for `column`.
+    fn push_contains_phrase(&mut self, column: &str, phrase: &str) -> Result<&mut Self, Error>;
+
+    /// Append a full-text `FREETEXT(<column>, @pN)` predicate, binding `text`.
+    ///
+    /// Unlike `CONTAINS`, `FREETEXT` has no search syntax: `text` is split into words and
+    /// matched by meaning (inflections and thesaurus expansions), so raw user input can be
+    /// bound as-is. See [`push_contains`](Self::push_contains) for `column`.
+    fn push_freetext(&mut self, column: &str, text: &str) -> Result<&mut Self, Error>;
+
+    /// Append `<column> IN (SELECT value FROM STRING_SPLIT(@pN, N'<delimiter>'))`, binding
+    /// `values` joined by `delimiter` as a single string.
+    ///
+    /// One parameter carries the whole list, so the statement text is the same for any
+    /// number of values and the plan can be reused. `STRING_SPLIT` returns `NVARCHAR`
+    /// values, which the server implicitly converts when comparing against e.g. an `INT`
+    /// column. An empty `values` binds `NULL`, which splits into no rows and matches
+    /// nothing.
+    ///
+    /// Requires SQL Server 2016 or later and database compatibility level 130 or higher.
+    ///
+    /// Returns [`Error::InvalidArgument`] if a value contains `delimiter`, as it would
+    /// silently be split into two values, or if `column` is not a valid name.
+    ///
+    /// ### Note
+    /// `STRING_SPLIT` has no escaping, so the delimiter can't occur in the values at all.
+    /// For arbitrary strings, such as user input, bind a JSON array and expand it with
+    /// `OPENJSON` instead (see the `Binding Lists` section of `MSSQL_SUPPORT.md`).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn example() -> sqlx::Result<()> {
+    /// use sqlx::mssql::{Mssql, MssqlQueryBuilderExt};
+    /// use sqlx::QueryBuilder;
+    ///
+    /// let mut qb = QueryBuilder::<Mssql>::new("SELECT * FROM users WHERE ");
+    /// qb.push_string_split_in("id", [1, 2, 3], ',')?;
+    ///
+    /// assert_eq!(
+    ///     qb.sql(),
+    ///     "SELECT * FROM users WHERE [id] IN (SELECT value FROM STRING_SPLIT(@p1, N','))"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn push_string_split_in<I>(
+        &mut self,
+        column: &str,
+        values: I,
+        delimiter: char,
+    ) -> Result<&mut Self, Error>
+    where
+        I: IntoIterator,
+        I::Item: Display;
+
+    /// Append an expression truncating the date/time `column` to the start of its `part`
+    /// bucket, for grouping time series, e.g. `GROUP BY <bucket>`.
+    ///
+    /// When `capabilities` reports `DATETRUNC` support (SQL Server 2022+), this pushes
+    /// `DATETRUNC(<part>, <column>)`, which keeps the type of the column. Older servers get
+    /// the `DATEADD(<part>, DATEDIFF(<part>, 0, <column>), 0)` idiom instead, which counts
+    /// whole buckets since 1900-01-01 and returns `DATETIME`; minute buckets can only be
+    /// computed this way for dates before the year 5983.
+    ///
+    /// `column` is bracket-quoted as an (up to three-part) name. Returns
+    /// [`Error::InvalidArgument`] if it isn't valid.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// use sqlx::mssql::{Mssql, MssqlDatePart, MssqlQueryBuilderExt};
+    /// use sqlx::QueryBuilder;
+    ///
+    /// let capabilities = conn.server_capabilities().await?;
+    ///
+    /// let mut qb = QueryBuilder::<Mssql>::new("SELECT ");
+    /// qb.push_date_bucket(MssqlDatePart::Hour, "created_at", capabilities)?;
+    /// qb.push(" AS bucket, COUNT(*) FROM events GROUP BY ");
+    /// qb.push_date_bucket(MssqlDatePart::Hour, "created_at", capabilities)?;
+    ///
+    /// // SQL Server 2022: SELECT DATETRUNC(hour, [created_at]) AS bucket, ...
+    /// // older:           SELECT DATEADD(hour, DATEDIFF(hour, 0, [created_at]), 0) AS bucket, ...
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn push_date_bucket(
+        &mut self,
+        part: MssqlDatePart,
+        column: &str,
+        capabilities: MssqlServerCapabilities,
+    ) -> Result<&mut Self, Error>;
+
+    /// Append `OPENJSON(@pN) WITH (<columns>)`, binding `rows` serialized as one JSON array,
+    /// so a list of structs can be selected from or joined against like a table.
+    ///
+    /// This is the usual replacement for a table-valued parameter, which can't be bound (see
+    /// the `Table-Valued Parameters` section of `MSSQL_SUPPORT.md`). Each of `columns` reads
+    /// the object key of the same name, and converts it to the column's type on the server.
+    /// Keys missing from an object come back as `NULL`, and a value that can't be converted
+    /// fails the query. Use `AS <alias>` after the fragment to name the rows.
+    ///
+    /// An empty `rows` yields no rows. Requires SQL Server 2016 or later and database
+    /// compatibility level 130 or higher.
+    ///
+    /// Returns [`Error::InvalidArgument`] if `columns` is empty, and [`Error::Encode`] if
+    /// `rows` can't be serialized.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn example() -> sqlx::Result<()> {
+    /// use sqlx::mssql::{Mssql, MssqlJsonColumn, MssqlQueryBuilderExt};
+    /// use sqlx::QueryBuilder;
+    ///
+    /// #[derive(serde::Serialize)]
+    /// struct Filter {
+    ///     id: i32,
+    ///     name: String,
+    /// }
+    ///
+    /// let filters = [Filter { id: 1, name: "alice".into() }];
+    ///
+    /// let mut qb = QueryBuilder::<Mssql>::new("SELECT u.* FROM users u JOIN ");
+    /// qb.push_openjson(
+    ///     &filters,
+    ///     &[
+    ///         MssqlJsonColumn::of::<i32>("id")?,
+    ///         MssqlJsonColumn::new("name", "NVARCHAR(100)")?,
+    ///     ],
+    /// )?;
+    /// qb.push(" AS f ON f.id = u.id AND f.name = u.name");
+    ///
+    /// assert_eq!(
+    ///     qb.sql(),
+    ///     "SELECT u.* FROM users u JOIN OPENJSON(@p1) WITH ([id] INT N'$.id', \
+    ///      [name] NVARCHAR(100) N'$.name') AS f ON f.id = u.id AND f.name = u.name"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "json")]
+    fn push_openjson<T>(
+        &mut self,
+        rows: &[T],
+        columns: &[MssqlJsonColumn],
+    ) -> Result<&mut Self, Error>
+    where
+        T: serde::Serialize;
+
+    /// Like [`QueryBuilder::push_values`], but checks SQL Server's limit of 1000 rows per
+    /// `VALUES` clause up front.
+    ///
+    /// Returns [`Error::InvalidArgument`] without pushing anything if `tuples` is empty or
+    /// has more than 1000 rows, instead of building a statement the server rejects with
+    /// error 10738. Split larger inserts into batches, or use
+    /// [`bulk_insert`][crate::MssqlConnection::bulk_insert].
+    ///
+    /// A request also carries at most 2100 parameters, two of which are taken by
+    /// `sp_executesql`, so e.g. 1000 rows of 3 bound columns are too many. The parameter
+    /// count isn't known until the rows are pushed; a query with more than 2098 bound
+    /// parameters fails with [`Error::InvalidArgument`] when it is executed, before anything
+    /// is sent to the server.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # fn example() -> sqlx::Result<()> {
+    /// use sqlx::mssql::{Mssql, MssqlQueryBuilderExt};
+    /// use sqlx::QueryBuilder;
+    ///
+    /// let users = [(1i32, "alice"), (2, "bob")];
+    ///
+    /// let mut qb = QueryBuilder::<Mssql>::new("INSERT INTO users (id, name) ");
+    /// qb.try_push_values(users, |mut b, (id, name)| {
+    ///     b.push_bind(id).push_bind(name);
+    /// })?;
+    ///
+    /// assert_eq!(
+    ///     qb.sql(),
+    ///     "INSERT INTO users (id, name) VALUES (@p1, @p2), (@p3, @p4)"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn try_push_values<I, F>(&mut self, tuples: I, push_tuple: F) -> Result<&mut Self, Error>
+    where
+        I: IntoIterator,
+        F: FnMut(Separated<'_, Mssql, &'static str>, I::Item);
+}
+
+impl MssqlQueryBuilderExt for QueryBuilder<Mssql> {
+    fn push_option_hint(&mut self, hints: &str) -> Result<&mut Self, Error> {
+        let hints = validate_hints(hints)?;
+
+        if has_option_clause(current_statement(self.sql().as_str())) {
+            return Err(Error::InvalidArgument(
+                "statement already has an OPTION clause; combine hints into a single clause".into(),
+            ));
+        }
+
+        Ok(self.push(format_args!(" OPTION ({hints})")))
+    }
+
+    fn push_table_hint(&mut self, hints: &str) -> Result<&mut Self, Error> {
+        let hints = validate_hints(hints)?;
+        Ok(self.push(format_args!(" WITH ({hints})")))
+    }
+
+    fn push_table_hints(&mut self, hints: &[MssqlHint]) -> Result<&mut Self, Error> {
+        let hints = render_hints(hints, true)?;
+        self.push_table_hint(&hints)
+    }
+
+    fn push_query_hints(&mut self, hints: &[MssqlHint]) -> Result<&mut Self, Error> {
+        let hints = render_hints(hints, false)?;
+        self.push_option_hint(&hints)
+    }
+
+    fn push_rowversion_check(
+        &mut self,
+        column: &str,
+        rowversion: &[u8],
+    ) -> Result<&mut Self, Error> {
+        let rowversion: [u8; 8] = rowversion.try_into().map_err(|_| {
+            Error::InvalidArgument(format!(
+                "rowversion must be exactly 8 bytes, got {} bytes",
+                rowversion.len()
+            ))
+        })?;
+
+        self.push(quote_identifier(column)).push(" = ");
+        Ok(self.push_bind(rowversion))
+    }
+
+    fn push_except<F>(&mut self, subquery: F) -> &mut Self
+    where
+        F: FnOnce(&mut Self),
+    {
+        push_set_operation(self, "EXCEPT", subquery)
+    }
+
+    fn push_intersect<F>(&mut self, subquery: F) -> &mut Self
+    where
+        F: FnOnce(&mut Self),
+    {
+        push_set_operation(self, "INTERSECT", subquery)
+    }
+
+    fn push_order_by(
+        &mut self,
+        column: &str,
+        direction: MssqlSortDirection,
+        allowed: &[&str],
+    ) -> Result<&mut Self, Error> {
+        let column = allowed
+            .iter()
+            .find(|allowed| allowed.eq_ignore_ascii_case(column.trim()))
+            .ok_or_else(|| {
+                Error::InvalidArgument(format!("cannot sort by unknown column {column:?}"))
+            })?;
+
+        let column = quote_object_name(column)?;
+        Ok(self.push(format_args!(" ORDER BY {column} {}", direction.as_sql())))
+    }
+
+    fn push_contains(&mut self, column: &str, condition: &str) -> Result<&mut Self, Error> {
+        push_full_text_predicate(self, "CONTAINS", column, condition)
+    }
+
+    fn push_contains_phrase(&mut self, column: &str, phrase: &str) -> Result<&mut Self, Error> {
+        let phrase = format!("\"{}\"", phrase.trim().replace('"', "\"\""));
+        push_full_text_predicate(self, "CONTAINS", column, &phrase)
+    }
+
+    fn push_freetext(&mut self, column: &str, text: &str) -> Result<&mut Self, Error> {
+        push_full_text_predicate(self, "FREETEXT", column, text)
+    }
+
+    fn push_string_split_in<I>(
+        &mut self,
+        column: &str,
+        values: I,
+        delimiter: char,
+    ) -> Result<&mut Self, Error>
+    where
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        let column = quote_object_name(column)?;
+
+        let mut list: Option<String> = None;
+        for value in values {
+            let value = value.to_string();
+            if value.contains(delimiter) {
+                return Err(Error::InvalidArgument(format!(
+                    "value {value:?} contains the STRING_SPLIT delimiter {delimiter:?}"
+                )));
+            }
+
+            match &mut list {
+                Some(list) => {
+                    list.push(delimiter);
+                    list.push_str(&value);
+                }
+                None => list = Some(value),
+            }
+        }
+
+        let delimiter = delimiter.to_string().replace('\'', "''");
+
+        self.push(format_args!("{column} IN (SELECT value FROM STRING_SPLIT("))
+            .push_bind(list)
+            .push(format_args!(", N'{delimiter}'))"));
+        Ok(self)
+    }
+
+    fn push_date_bucket(
+        &mut self,
+        part: MssqlDatePart,
+        column: &str,
+        capabilities: MssqlServerCapabilities,
+    ) -> Result<&mut Self, Error> {
+        let column = quote_object_name(column)?;
+        let part = part.as_sql();
+
+        if capabilities.supports_datetrunc() {
+            Ok(self.push(format_args!("DATETRUNC({part}, {column})")))
+        } else {
+            Ok(self.push(format_args!(
+                "DATEADD({part}, DATEDIFF({part}, 0, {column}), 0)"
+            )))
+        }
+    }
+
+    #[cfg(feature = "json")]
+    fn push_openjson<T>(
+        &mut self,
+        rows: &[T],
+        columns: &[MssqlJsonColumn],
+    ) -> Result<&mut Self, Error>
+    where
+        T: serde::Serialize,
+    {
+        if columns.is_empty() {
+            return Err(Error::InvalidArgument(
+                "OPENJSON WITH clause has no columns".into(),
+            ));
+        }
+
+        let json = Json(rows)
+            .encode_to_string()
+            .map_err(|e| Error::Encode(e.into()))?;
+
+        self.push("OPENJSON(").push_bind(json).push(") WITH (");
+
+        for (i, column) in columns.iter().enumerate() {
+            if i > 0 {
+                self.push(", ");
+            }
+
+            self.push(format_args!(
+                "{} {} N'{}'",
+                quote_identifier(&column.name),
+                column.sql_type,
+                json_path(&column.name).replace('\'', "''")
+            ));
+        }
+
+        Ok(self.push(")"))
+    }
+
+    fn try_push_values<I, F>(&mut self, tuples: I, push_tuple: F) -> Result<&mut Self, Error>
+    where
+        I: IntoIterator,
+        F: FnMut(Separated<'_, Mssql, &'static str>, I::Item),
+    {
+        let rows: Vec<_> = tuples.into_iter().collect();
+
+        if rows.is_empty() {
+            return Err(Error::InvalidArgument("VALUES list has no rows".into()));
+        }
+
+        if rows.len() > MAX_VALUES_ROWS {
+            return Err(Error::InvalidArgument(format!(
+                "VALUES list has {} rows, but SQL Server allows at most {MAX_VALUES_ROWS}",
+                rows.len()
+            )));
+        }
+
+        Ok(self.push_values(rows, push_tuple))
+    }
+}
+
+/// Check a column name for [`MssqlJsonColumn`] against the rules for identifiers.
+#[cfg(feature = "json")]
+fn json_column_name(name: &str) -> Result<String, Error> {
+    if name.is_empty() || name.chars().count() > 128 || name.chars().any(char::is_control) {
+        return Err(Error::InvalidArgument(format!(
+            "invalid OPENJSON column name {name:?}: must be 1 to 128 characters without control \
+             characters"
+        )));
+    }
+
+    Ok(name.to_owned())
+}
+
+/// The JSON path of the object key `name`, quoted unless it is a plain identifier.
+#[cfg(feature = "json")]
+fn json_path(name: &str) -> String {
+    let plain = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    if plain {
+        format!("$.{name}")
+    } else {
+        format!("$.\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+fn push_full_text_predicate<'a>(
+    qb: &'a mut QueryBuilder<Mssql>,
+    predicate: &str,
+    column: &str,
+    term: &str,
+) -> Result<&'a mut QueryBuilder<Mssql>, Error> {
+    let column = full_text_columns(column)?;
+
+    if term.trim().trim_matches('"').trim().is_empty() {
+        return Err(Error::InvalidArgument(format!(
+            "{predicate} search term is empty"
+        )));
+    }
+
+    qb.push(format_args!("{predicate}({column}, "));
+    qb.push_bind(term.to_owned());
+    Ok(qb.push(")"))
+}
+
+/// Quote the column argument of a full-text predicate: `*`, a column name, or a
+/// parenthesized list of column names.
+fn full_text_columns(column: &str) -> Result<String, Error> {
+    let column = column.trim();
+
+    if column == "*" {
+        return Ok(column.to_owned());
+    }
+
+    match column
+        .strip_prefix('(')
+        .and_then(|list| list.strip_suffix(')'))
+    {
+        Some(list) => {
+            let columns = list
+                .split(',')
+                .map(quote_object_name)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(format!("({})", columns.join(", ")))
+        }
+        None => quote_object_name(column),
+    }
+}
+
+fn push_set_operation<'a, F>(
+    qb: &'a mut QueryBuilder<Mssql>,
+    operator: &str,
+    subquery: F,
+) -> &'a mut QueryBuilder<Mssql>
+where
+    F: FnOnce(&mut QueryBuilder<Mssql>),
+{
+    qb.push(format_args!(" {operator} ("));
+    subquery(qb);
+    qb.push(")")
+}
+
+/// Check that a hint list is non-empty, has balanced parentheses, and can't terminate the
+/// statement or comment out the rest of it.
+fn validate_hints(hints: &str) -> Result<&str, Error> {
+    let hints = hints.trim();
+
+    if hints.is_empty() {
+        return Err(Error::InvalidArgument("hint list is empty".into()));
+    }
+
+    if hints.contains(';') || hints.contains("--") || hints.contains("/*") {
+        return Err(Error::InvalidArgument(format!(
+            "hint list {hints:?} must not contain `;` or comments"
+        )));
+    }
+
+    let mut depth = 0usize;
+    let mut in_string = false;
+
+    for c in hints.chars() {
+        match c {
+            '\'' => in_string = !in_string,
+            '(' if !in_string => depth += 1,
+            ')' if !in_string => {
+                depth = depth.checked_sub(1).ok_or_else(|| {
+                    Error::InvalidArgument(format!("unbalanced parentheses in hints {hints:?}"))
+                })?;
+            }
+            _ => {}
+        }
+    }
+
+    if depth != 0 || in_string {
+        return Err(Error::InvalidArgument(format!(
+            "unbalanced parentheses or quotes in hints {hints:?}"
+        )));
+    }
+
+    Ok(hints)
+}
+
+/// The text of the statement currently being built, i.e. everything after the last `;`.
+fn current_statement(sql: &str) -> &str {
+    sql.rsplit(';').next().unwrap_or(sql)
+}
+
+fn has_option_clause(statement: &str) -> bool {
+    let upper = statement.to_ascii_uppercase();
+    upper.contains(" OPTION (") || upper.contains(" OPTION(")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_sizes_values_chunks_within_limits() {
+        assert_eq!(values_chunk_size(0), 1000);
+        assert_eq!(values_chunk_size(1), 1000);
+        assert_eq!(values_chunk_size(2), 1000);
+        assert_eq!(values_chunk_size(3), 699);
+        assert_eq!(values_chunk_size(2098), 1);
+        assert_eq!(values_chunk_size(5000), 1);
+    }
+
+    #[test]
+    fn it_shifts_placeholders_outside_literals() {
+        assert_eq!(shift_placeholders("(@p5, @p6)", 4), "(@p1, @p2)");
+        assert_eq!(shift_placeholders("(@p10,@p12)", 9), "(@p1,@p3)");
+        assert_eq!(
+            shift_placeholders("(@p5, N'@p5 it''s @p6', @p6)", 4),
+            "(@p1, N'@p5 it''s @p6', @p2)"
+        );
+        assert_eq!(
+            shift_placeholders("(@p5, @@pid, @px, @p5x)", 4),
+            "(@p1, @@pid, @px, @p5x)"
+        );
+    }
+
+    #[test]
+    fn it_accepts_hint_lists() {
+        assert_eq!(validate_hints(" RECOMPILE ").unwrap(), "RECOMPILE");
+        assert_eq!(
+            validate_hints("MAXDOP 1, OPTIMIZE FOR (@p1 = 'a(b')").unwrap(),
+            "MAXDOP 1, OPTIMIZE FOR (@p1 = 'a(b')"
+        );
+    }
+
+    #[test]
+    fn it_renders_typed_hints_in_their_clause() {
+        for (hint, clause) in [
+            (MssqlHint::NoLock, " WITH (NOLOCK)"),
+            (MssqlHint::UpdLock, " WITH (UPDLOCK)"),
+            (MssqlHint::HoldLock, " WITH (HOLDLOCK)"),
+            (MssqlHint::ForceSeek, " WITH (FORCESEEK)"),
+            (MssqlHint::Index("ix]1".into()), " WITH (INDEX([ix]]1]))"),
+            (MssqlHint::Recompile, " OPTION (RECOMPILE)"),
+            (MssqlHint::MaxDop(4), " OPTION (MAXDOP 4)"),
+            (
+                MssqlHint::OptimizeForUnknown,
+                " OPTION (OPTIMIZE FOR UNKNOWN)",
+            ),
+        ] {
+            let hints = [hint];
+            let mut qb = QueryBuilder::<Mssql>::new("SELECT * FROM t");
+            let mut misplaced = QueryBuilder::<Mssql>::new("SELECT * FROM t");
+
+            if hints[0].is_table_hint() {
+                qb.push_table_hints(&hints).unwrap();
+                assert!(misplaced.push_query_hints(&hints).is_err());
+            } else {
+                qb.push_query_hints(&hints).unwrap();
+                assert!(misplaced.push_table_hints(&hints).is_err());
+            }
+
+            assert_eq!(qb.sql(), format!("SELECT * FROM t{clause}"));
+            assert_eq!(misplaced.sql(), "SELECT * FROM t");
+        }
+    }
+
+    #[test]
+    fn it_rejects_conflicting_typed_hints() {
+        for hints in [
+            vec![],
+            vec![MssqlHint::NoLock, MssqlHint::UpdLock],
+            vec![MssqlHint::HoldLock, MssqlHint::NoLock],
+            vec![MssqlHint::Index("a".into()), MssqlHint::Index("b".into())],
+            vec![MssqlHint::ForceSeek, MssqlHint::ForceSeek],
+            vec![MssqlHint::Index(String::new())],
+        ] {
+            let mut qb = QueryBuilder::<Mssql>::new("SELECT * FROM t");
+            assert!(
+                matches!(qb.push_table_hints(&hints), Err(Error::InvalidArgument(_))),
+                "{hints:?}"
+            );
+            assert_eq!(qb.sql(), "SELECT * FROM t");
+        }
+
+        let mut qb = QueryBuilder::<Mssql>::new("SELECT * FROM t");
+        assert!(qb
+            .push_query_hints(&[MssqlHint::MaxDop(1), MssqlHint::MaxDop(2)])
+            .is_err());
+
+        qb.push_table_hints(&[MssqlHint::UpdLock, MssqlHint::HoldLock])
+            .unwrap();
+        qb.push_query_hints(&[MssqlHint::Recompile]).unwrap();
+        assert_eq!(
+            qb.sql(),
+            "SELECT * FROM t WITH (UPDLOCK, HOLDLOCK) OPTION (RECOMPILE)"
+        );
+
+        // only one OPTION clause per statement
+        assert!(qb.push_query_hints(&[MssqlHint::MaxDop(1)]).is_err());
+    }
+
+    #[test]
+    fn it_rejects_invalid_hint_lists() {
+        for hints in [
+            "",
+            "   ",
+            "RECOMPILE; DROP TABLE users",
+            "RECOMPILE --",
+            "RECOMPILE /* */",
+            "OPTIMIZE FOR (@p1 = 1",
+            "RECOMPILE)",
+            "OPTIMIZE FOR (@p1 = 'a)",
+        ] {
+            assert!(
+                matches!(validate_hints(hints), Err(Error::InvalidArgument(_))),
+                "{hints:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn it_requires_an_8_byte_rowversion() {
+        let mut qb = QueryBuilder::<Mssql>::new("DELETE FROM t WHERE ");
+        qb.push_rowversion_check("row]version", &[0; 8]).unwrap();
+        assert_eq!(qb.sql(), "DELETE FROM t WHERE [row]]version] = @p1");
+
+        for len in [0, 7, 9, 16] {
+            assert!(matches!(
+                qb.push_rowversion_check("version", &vec![0; len]),
+                Err(Error::InvalidArgument(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn it_quotes_full_text_columns() {
+        assert_eq!(full_text_columns(" * ").unwrap(), "*");
+        assert_eq!(full_text_columns("d.body").unwrap(), "[d].[body]");
+        assert_eq!(
+            full_text_columns("(title, [body text])").unwrap(),
+            "([title], [body text])"
+        );
+
+        // Anything else is quoted as a name, so it can't escape the predicate
+        assert_eq!(
+            full_text_columns("body) OR 1=1 --").unwrap(),
+            "[body) OR 1=1 --]"
+        );
+
+        for column in ["", "()", "(title,)", "a.b.c.d"] {
+            assert!(full_text_columns(column).is_err(), "{column:?}");
+        }
+    }
+
+    #[test]
+    fn it_buckets_dates_with_datetrunc_on_2022() {
+        let caps = MssqlServerCapabilities::new("16.0.1000.6", 2).unwrap();
+
+        for (part, sql) in [
+            (MssqlDatePart::Hour, "DATETRUNC(hour, [e].[created_at])"),
+            (MssqlDatePart::Day, "DATETRUNC(day, [e].[created_at])"),
+            (MssqlDatePart::Month, "DATETRUNC(month, [e].[created_at])"),
+        ] {
+            let mut qb = QueryBuilder::<Mssql>::new("");
+            qb.push_date_bucket(part, "e.created_at", caps).unwrap();
+            assert_eq!(qb.sql(), sql);
+        }
+    }
+
+    #[test]
+    fn it_buckets_dates_with_dateadd_before_2022() {
+        let caps = MssqlServerCapabilities::new("15.0.2000.5", 2).unwrap();
+
+        for (part, sql) in [
+            (
+                MssqlDatePart::Hour,
+                "DATEADD(hour, DATEDIFF(hour, 0, [e].[created_at]), 0)",
+            ),
+            (
+                MssqlDatePart::Day,
+                "DATEADD(day, DATEDIFF(day, 0, [e].[created_at]), 0)",
+            ),
+            (
+                MssqlDatePart::Month,
+                "DATEADD(month, DATEDIFF(month, 0, [e].[created_at]), 0)",
+            ),
+        ] {
+            let mut qb = QueryBuilder::<Mssql>::new("");
+            qb.push_date_bucket(part, "e.created_at", caps).unwrap();
+            assert_eq!(qb.sql(), sql);
+        }
+
+        let mut qb = QueryBuilder::<Mssql>::new("");
+        assert!(matches!(
+            qb.push_date_bucket(MssqlDatePart::Day, "a.b.c.d", caps),
+            Err(Error::InvalidArgument(_))
+        ));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn it_quotes_openjson_paths() {
+        assert_eq!(json_path("id"), "$.id");
+        assert_eq!(json_path("_row2"), "$._row2");
+        assert_eq!(json_path("first name"), r#"$."first name""#);
+        assert_eq!(json_path("2nd"), r#"$."2nd""#);
+        assert_eq!(json_path(r#"a"b\c"#), r#"$."a\"b\\c""#);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn it_infers_openjson_column_types() {
+        let sql_type = |column: MssqlJsonColumn| column.sql_type;
+
+        assert_eq!(sql_type(MssqlJsonColumn::of::<i32>("id").unwrap()), "INT");
+        assert_eq!(sql_type(MssqlJsonColumn::of::<bool>("on").unwrap()), "BIT");
+        assert_eq!(
+            sql_type(MssqlJsonColumn::of::<String>("name").unwrap()),
+            "NVARCHAR(MAX)"
+        );
+        assert_eq!(
+            sql_type(MssqlJsonColumn::of::<Option<f64>>("score").unwrap()),
+            "FLOAT"
+        );
+
+        assert!(matches!(
+            MssqlJsonColumn::of::<Vec<u8>>("data"),
+            Err(Error::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            MssqlJsonColumn::of::<i128>("big"),
+            Err(Error::InvalidArgument(_))
+        ));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn it_rejects_invalid_openjson_columns() {
+        assert!(MssqlJsonColumn::new("amount", "DECIMAL(10, 2)").is_ok());
+
+        for sql_type in [
+            "",
+            "INT; DROP TABLE users",
+            "INT '$.x'",
+            "INT --",
+            "DECIMAL(10",
+            "NVARCHAR)(10",
+            "INT DROP TABLE users",
+        ] {
+            assert!(
+                matches!(
+                    MssqlJsonColumn::new("x", sql_type),
+                    Err(Error::InvalidArgument(_))
+                ),
+                "{sql_type:?}"
+            );
+        }
+
+        assert!(MssqlJsonColumn::new("", "INT").is_err());
+        assert!(MssqlJsonColumn::new("a\nb", "INT").is_err());
+
+        let mut qb = QueryBuilder::<Mssql>::new("SELECT * FROM ");
+        assert!(matches!(
+            qb.push_openjson(&[1, 2], &[]),
+            Err(Error::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn it_only_checks_the_current_statement() {
+        assert!(has_option_clause(current_statement(
+            "SELECT 1 OPTION (RECOMPILE)"
+        )));
+        assert!(!has_option_clause(current_statement(
+            "SELECT 1 OPTION (RECOMPILE); SELECT 2"
+        )));
+    }
+}
@@ -3,18 +3,32 @@ use std::iter::{Extend, IntoIterator};
 #[derive(Debug, Default)]
 pub struct MssqlQueryResult {
     pub(super) rows_affected: u64,
+    pub(super) last_insert_id: Option<i64>,
 }
 
 impl MssqlQueryResult {
     pub fn rows_affected(&self) -> u64 {
         self.rows_affected
     }
+
+    /// The identity value generated by an `INSERT`, as reported by `SCOPE_IDENTITY()`.
+    ///
+    /// Only captured when
+    /// [`MssqlConnectOptions::capture_last_insert_id`][crate::MssqlConnectOptions::capture_last_insert_id]
+    /// is enabled. `None` if the statement inserted into a table without an identity column,
+    /// or was not an `INSERT`.
+    pub fn last_insert_id(&self) -> Option<i64> {
+        self.last_insert_id
+    }
 }
 
 impl Extend<MssqlQueryResult> for MssqlQueryResult {
     fn extend<T: IntoIterator<Item = MssqlQueryResult>>(&mut self, iter: T) {
         for elem in iter {
             self.rows_affected += elem.rows_affected;
+            if elem.last_insert_id.is_some() {
+                self.last_insert_id = elem.last_insert_id;
+            }
         }
     }
 }
@@ -24,7 +38,7 @@ impl From<MssqlQueryResult> for sqlx_core::any::AnyQueryResult {
     fn from(done: MssqlQueryResult) -> Self {
         sqlx_core::any::AnyQueryResult {
             rows_affected: done.rows_affected(),
-            last_insert_id: None,
+            last_insert_id: done.last_insert_id(),
         }
     }
 }
@@ -0,0 +1,151 @@
+use std::time::Duration;
+
+/// A snapshot of the request a session is running, returned by
+/// [`MssqlConnection::current_request_info`][crate::MssqlConnection::current_request_info].
+///
+/// Read from `sys.dm_exec_requests` and `sys.dm_exec_sql_text`, which need the
+/// `VIEW SERVER STATE` permission to see sessions other than the caller's own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MssqlRequestInfo {
+    pub(crate) session_id: i16,
+    pub(crate) status: String,
+    pub(crate) command: String,
+    pub(crate) wait_type: Option<String>,
+    pub(crate) wait_time: Duration,
+    pub(crate) blocking_session_id: Option<i16>,
+    pub(crate) elapsed_time: Duration,
+    pub(crate) sql_text: Option<String>,
+    pub(crate) statement: Option<String>,
+}
+
+/// A row of [`MssqlRequestInfo::QUERY`].
+pub(crate) type RequestInfoRow = (
+    i16,
+    String,
+    String,
+    Option<String>,
+    i32,
+    Option<i16>,
+    i32,
+    Option<String>,
+    Option<String>,
+);
+
+impl MssqlRequestInfo {
+    /// The running request of session `@p1`, with its batch text and the statement within it
+    /// that is currently executing.
+    pub(crate) const QUERY: &'static str = "SELECT r.session_id, r.status, r.command, \
+         r.wait_type, r.wait_time, r.blocking_session_id, r.total_elapsed_time, t.text, \
+         SUBSTRING(t.text, r.statement_start_offset / 2 + 1, \
+             (CASE r.statement_end_offset WHEN -1 THEN DATALENGTH(t.text) \
+              ELSE r.statement_end_offset END - r.statement_start_offset) / 2 + 1) \
+         FROM sys.dm_exec_requests r \
+         OUTER APPLY sys.dm_exec_sql_text(r.sql_handle) t \
+         WHERE r.session_id = @p1";
+
+    /// The session (SPID) running the request.
+    pub fn session_id(&self) -> i16 {
+        self.session_id
+    }
+
+    /// The state of the request: `running`, `runnable`, `suspended`, `sleeping` or
+    /// `background`.
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    /// The kind of command being run, e.g. `SELECT`, `UPDATE` or `WAITFOR`.
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    /// What the request is waiting on, e.g. `LCK_M_X` for a lock, if it is waiting.
+    pub fn wait_type(&self) -> Option<&str> {
+        self.wait_type.as_deref()
+    }
+
+    /// How long the request has been waiting, at millisecond resolution.
+    pub fn wait_time(&self) -> Duration {
+        self.wait_time
+    }
+
+    /// The session holding what this request waits for, if it is blocked by another session.
+    pub fn blocking_session_id(&self) -> Option<i16> {
+        self.blocking_session_id
+    }
+
+    /// How long the request has been running, at millisecond resolution.
+    pub fn elapsed_time(&self) -> Duration {
+        self.elapsed_time
+    }
+
+    /// The text of the whole batch or module being run.
+    pub fn sql_text(&self) -> Option<&str> {
+        self.sql_text.as_deref()
+    }
+
+    /// The statement of [`sql_text`][Self::sql_text] that is currently executing.
+    pub fn statement(&self) -> Option<&str> {
+        self.statement.as_deref()
+    }
+}
+
+impl From<RequestInfoRow> for MssqlRequestInfo {
+    fn from(
+        (
+            session_id,
+            status,
+            command,
+            wait_type,
+            wait_time_ms,
+            blocking_session_id,
+            elapsed_time_ms,
+            sql_text,
+            statement,
+        ): RequestInfoRow,
+    ) -> Self {
+        fn millis(ms: i32) -> Duration {
+            Duration::from_millis(u64::try_from(ms).unwrap_or(0))
+        }
+
+        Self {
+            session_id,
+            status: status.trim().to_owned(),
+            command: command.trim().to_owned(),
+            wait_type: wait_type.filter(|wait_type| !wait_type.is_empty()),
+            wait_time: millis(wait_time_ms),
+            // 0 means "not blocked"; negative ids stand for orphaned or deferred
+            // distributed transactions and latches, which have no session to report
+            blocking_session_id: blocking_session_id.filter(|id| *id > 0),
+            elapsed_time: millis(elapsed_time_ms),
+            sql_text,
+            statement,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_normalizes_dmv_values() {
+        let info = MssqlRequestInfo::from((
+            53,
+            "suspended".to_owned(),
+            "UPDATE          ".to_owned(),
+            Some("LCK_M_X".to_owned()),
+            1500,
+            Some(0),
+            -1,
+            None,
+            None,
+        ));
+
+        assert_eq!(info.command(), "UPDATE");
+        assert_eq!(info.wait_type(), Some("LCK_M_X"));
+        assert_eq!(info.wait_time(), Duration::from_millis(1500));
+        assert_eq!(info.blocking_session_id(), None);
+        assert_eq!(info.elapsed_time(), Duration::ZERO);
+    }
+}
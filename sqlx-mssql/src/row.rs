@@ -38,6 +38,32 @@ impl Row for MssqlRow {
     }
 }
 
+impl MssqlRow {
+    /// Read any column as text, or `None` if it is `NULL`.
+    ///
+    /// Unlike `try_get::<String, _>`, which requires a character column, this formats
+    /// values of every type in a canonical text form, for generic exports such as CSV
+    /// dumps:
+    ///
+    /// * integers, floats and decimals as by `Display`; decimals keep their scale,
+    ///   e.g. `12.340` for a `DECIMAL(10, 3)`,
+    /// * `BIT` as `1` or `0`,
+    /// * `BINARY`/`VARBINARY` as `0x`-prefixed uppercase hex,
+    /// * dates and times in ISO 8601, e.g. `2024-01-02T03:04:05.500` or
+    ///   `2024-01-02T03:04:05+02:00`, with no, 3, 6 or 9 fractional digits,
+    /// * `UNIQUEIDENTIFIER` as a lowercase hyphenated UUID.
+    ///
+    /// Which date/time, decimal and UUID columns can be formatted depends on the enabled
+    /// type integration features, as with typed decoding.
+    pub fn try_get_string<I>(&self, index: I) -> Result<Option<String>, Error>
+    where
+        I: ColumnIndex<Self>,
+    {
+        let index = index.index(self)?;
+        Ok(self.values[index].to_text())
+    }
+}
+
 impl ColumnIndex<MssqlRow> for &'_ str {
     fn index(&self, row: &MssqlRow) -> Result<usize, Error> {
         row.column_names
@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+/// Execution statistics for a single query, returned by
+/// [`MssqlConnection::fetch_with_stats`][crate::MssqlConnection::fetch_with_stats].
+///
+/// The reads and CPU time correspond to the output of `SET STATISTICS IO` and `SET STATISTICS
+/// TIME`, summed over every table and statement the query touched. The wall time is measured
+/// by the client instead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MssqlStats {
+    pub(crate) logical_reads: u64,
+    pub(crate) physical_reads: u64,
+    pub(crate) cpu_time: Duration,
+    pub(crate) wall_time: Duration,
+}
+
+impl MssqlStats {
+    /// Pages read from the buffer cache.
+    pub fn logical_reads(&self) -> u64 {
+        self.logical_reads
+    }
+
+    /// Pages read from disk.
+    pub fn physical_reads(&self) -> u64 {
+        self.physical_reads
+    }
+
+    /// CPU time spent by the server, at millisecond resolution.
+    pub fn cpu_time(&self) -> Duration {
+        self.cpu_time
+    }
+
+    /// Wall-clock time from sending the query until its last row was read, as observed by the
+    /// client.
+    ///
+    /// This includes network time and the time taken to decode the rows, so it is not the
+    /// server's elapsed time that `SET STATISTICS TIME` reports.
+    pub fn wall_time(&self) -> Duration {
+        self.wall_time
+    }
+}
+
+/// Cumulative counters of a session, as reported by `sys.dm_exec_sessions`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SessionCounters {
+    pub(crate) cpu_time_ms: i64,
+    pub(crate) physical_reads: i64,
+    pub(crate) logical_reads: i64,
+}
+
+impl SessionCounters {
+    pub(crate) const QUERY: &'static str = "SELECT CAST(cpu_time AS BIGINT), reads, logical_reads \
+        FROM sys.dm_exec_sessions WHERE session_id = @@SPID";
+
+    /// The statistics accumulated between `before` and `self`, for a query that took
+    /// `wall_time`.
+    pub(crate) fn since(&self, before: &SessionCounters, wall_time: Duration) -> MssqlStats {
+        fn delta(after: i64, before: i64) -> u64 {
+            u64::try_from(after.saturating_sub(before)).unwrap_or(0)
+        }
+
+        MssqlStats {
+            logical_reads: delta(self.logical_reads, before.logical_reads),
+            physical_reads: delta(self.physical_reads, before.physical_reads),
+            cpu_time: Duration::from_millis(delta(self.cpu_time_ms, before.cpu_time_ms)),
+            wall_time,
+        }
+    }
+}
+
+impl From<(i64, i64, i64)> for SessionCounters {
+    fn from((cpu_time_ms, physical_reads, logical_reads): (i64, i64, i64)) -> Self {
+        Self {
+            cpu_time_ms,
+            physical_reads,
+            logical_reads,
+        }
+    }
+}
@@ -2,6 +2,7 @@ use sqlx_core::sql_str::{AssertSqlSafe, SqlSafeStr, SqlStr};
 
 use crate::error::{tiberius_err, Error};
 use crate::executor::Executor;
+use crate::query_scalar::query_scalar;
 use crate::{Mssql, MssqlConnection};
 
 pub(crate) use sqlx_core::transaction::*;
@@ -39,7 +40,13 @@ impl TransactionManager for MssqlTransactionManager {
             }
         };
 
-        conn.execute(statement).await?;
+        if let Err(error) = conn.execute(statement).await {
+            if depth == 0 {
+                // Undo any `SET` that ran before `BEGIN TRANSACTION` failed.
+                let _ = restore_transaction_settings(conn).await;
+            }
+            return Err(error);
+        }
         conn.inner.transaction_depth += 1;
 
         Ok(())
@@ -50,11 +57,25 @@ impl TransactionManager for MssqlTransactionManager {
 
         if depth > 0 {
             if depth == 1 {
-                // Only the outermost transaction actually commits
-                conn.execute("COMMIT").await?;
+                // Only the outermost transaction actually commits. The depth is only
+                // decremented once `COMMIT` succeeded, since a failed `COMMIT` (e.g. of a
+                // doomed transaction) leaves the transaction open.
+                if let Err(error) = conn.execute("COMMIT").await {
+                    sync_transaction_depth(conn).await;
+                    return Err(error);
+                }
             }
             // Savepoints auto-commit with their parent transaction, so no-op for depth > 1
             conn.inner.transaction_depth = depth - 1;
+            forget_savepoints(conn);
+            // The data is committed either way; a failed reset is retried before the next
+            // request.
+            let _ = restore_transaction_settings(conn).await;
+        } else if conn.inner.rolled_back_guards > 0 {
+            // The guard stays open, so dropping it forgets it via `start_rollback`
+            return Err(Error::Protocol(
+                "cannot commit a transaction that was rolled back by `rollback_all`".into(),
+            ));
         }
 
         Ok(())
@@ -71,6 +92,13 @@ impl TransactionManager for MssqlTransactionManager {
                 conn.execute(AssertSqlSafe(savepoint)).await?;
             }
             conn.inner.transaction_depth = depth - 1;
+            forget_savepoints(conn);
+            // The rollback happened either way; a failed reset is retried before the next
+            // request.
+            let _ = restore_transaction_settings(conn).await;
+        } else {
+            // Already rolled back by `rollback_all`
+            conn.inner.rolled_back_guards = conn.inner.rolled_back_guards.saturating_sub(1);
         }
 
         Ok(())
@@ -78,11 +106,15 @@ impl TransactionManager for MssqlTransactionManager {
 
     fn start_rollback(conn: &mut MssqlConnection) {
         let depth = conn.inner.transaction_depth;
-        if depth > 0 {
+        if depth == 0 {
+            // Already rolled back by `rollback_all`
+            conn.inner.rolled_back_guards = conn.inner.rolled_back_guards.saturating_sub(1);
+        } else {
             // We can't execute async SQL from a synchronous context (Drop),
             // so we set a flag and execute the rollback on the next operation.
             conn.inner.pending_rollback = true;
             conn.inner.transaction_depth = depth - 1;
+            forget_savepoints(conn);
         }
     }
 
@@ -91,8 +123,105 @@ impl TransactionManager for MssqlTransactionManager {
     }
 }
 
-/// Execute pending rollback if one was triggered by `start_rollback`.
+/// Reset the transaction depth if the server no longer has a transaction open.
+///
+/// SQL Server may roll back the whole transaction on its own (e.g. under `XACT_ABORT`), in
+/// which case a later `COMMIT` fails and there is nothing left to roll back either.
+async fn sync_transaction_depth(conn: &mut MssqlConnection) {
+    if let Ok(0) = query_scalar::<_, i32>("SELECT @@TRANCOUNT")
+        .fetch_one(&mut *conn)
+        .await
+    {
+        conn.inner.transaction_depth = 0;
+        forget_savepoints(conn);
+        let _ = restore_transaction_settings(conn).await;
+    }
+}
+
+/// The longest savepoint name SQL Server accepts.
+const MAX_SAVEPOINT_NAME: usize = 32;
+
+/// Check a user-chosen savepoint name, which must not clash with the `_sqlx_` savepoints
+/// of nested transactions and bulk inserts.
+pub(crate) fn validate_savepoint_name(name: &str) -> Result<&str, Error> {
+    let problem = if name.is_empty() {
+        "is empty"
+    } else if name.chars().count() > MAX_SAVEPOINT_NAME {
+        "is longer than 32 characters"
+    } else if name.chars().any(char::is_control) {
+        "contains control characters"
+    } else if name.starts_with("_sqlx_") {
+        "starts with the reserved prefix `_sqlx_`"
+    } else {
+        return Ok(name);
+    };
+
+    Err(Error::InvalidArgument(format!(
+        "savepoint name {name:?} {problem}"
+    )))
+}
+
+/// Drop the named savepoints of transactions that have ended.
+pub(crate) fn forget_savepoints(conn: &mut MssqlConnection) {
+    let depth = conn.inner.transaction_depth;
+    conn.inner
+        .savepoints
+        .retain(|&(_, savepoint_depth)| savepoint_depth <= depth);
+}
+
+/// Restore the session settings changed by `begin_with_options` once no transaction is
+/// open any more.
+///
+/// The reset is kept until it has run, so if it fails it is retried by
+/// [`resolve_pending_rollback`] before the next request.
+pub(crate) async fn restore_transaction_settings(conn: &mut MssqlConnection) -> Result<(), Error> {
+    if conn.inner.transaction_depth > 0 {
+        return Ok(());
+    }
+
+    let Some(reset) = conn.inner.transaction_settings_reset.clone() else {
+        return Ok(());
+    };
+
+    conn.inner.settings_reset_pending = true;
+
+    conn.inner
+        .client
+        .simple_query(reset)
+        .await
+        .map_err(tiberius_err)?
+        .into_results()
+        .await
+        .map_err(tiberius_err)?;
+
+    conn.inner.transaction_settings_reset = None;
+    conn.inner.settings_reset_pending = false;
+
+    Ok(())
+}
+
+/// Execute pending rollback if one was triggered by `start_rollback` or
+/// [`MssqlBulkInsert::abort`][crate::MssqlBulkInsert::abort].
 pub(crate) async fn resolve_pending_rollback(conn: &mut MssqlConnection) -> Result<(), Error> {
+    // A request follows, and every request overwrites `@@ROWCOUNT`.
+    conn.inner.captured_rowcount = None;
+
+    if std::mem::take(&mut conn.inner.pending_bulk_insert_rollback) {
+        // The transaction may already be gone, e.g. rolled back by `XACT_ABORT`.
+        let rollback = format!(
+            "IF @@TRANCOUNT > 0 ROLLBACK TRANSACTION {}",
+            crate::bulk_insert::BULK_INSERT_SAVEPOINT
+        );
+        conn.inner
+            .client
+            .simple_query(rollback)
+            .await
+            .map_err(tiberius_err)?
+            .into_results()
+            .await
+            .map_err(tiberius_err)?;
+    }
+
     if conn.inner.pending_rollback {
         conn.inner.pending_rollback = false;
         let depth = conn.inner.transaction_depth;
@@ -106,6 +235,7 @@ pub(crate) async fn resolve_pending_rollback(conn: &mut MssqlConnection) -> Resu
                 .into_results()
                 .await
                 .map_err(tiberius_err)?;
+            conn.inner.settings_reset_pending = conn.inner.transaction_settings_reset.is_some();
         } else {
             let savepoint = format!("ROLLBACK TRANSACTION _sqlx_savepoint_{}", depth);
             conn.inner
@@ -118,5 +248,45 @@ pub(crate) async fn resolve_pending_rollback(conn: &mut MssqlConnection) -> Resu
                 .map_err(tiberius_err)?;
         }
     }
+
+    // The request that follows doesn't depend on the settings being restored, so a reset
+    // that fails again only stays pending.
+    if conn.inner.settings_reset_pending {
+        if let Err(error) = restore_transaction_settings(conn).await {
+            tracing::warn!(
+                %error,
+                "failed to restore the session settings changed by begin_with_options",
+            );
+        }
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_validates_savepoint_names() {
+        for name in ["a", "before items", "x]y", &"n".repeat(32), &"ü".repeat(32)] {
+            assert_eq!(validate_savepoint_name(name).unwrap(), name);
+        }
+
+        for name in [
+            "",
+            &"n".repeat(33),
+            "a\nb",
+            "_sqlx_savepoint_1",
+            "_sqlx_bulk_insert",
+        ] {
+            assert!(
+                matches!(
+                    validate_savepoint_name(name),
+                    Err(Error::InvalidArgument(_))
+                ),
+                "{name:?}"
+            );
+        }
+    }
+}
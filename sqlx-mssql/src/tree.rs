@@ -0,0 +1,233 @@
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+use crate::decode::Decode;
+use crate::error::Error;
+use crate::row::Row;
+use crate::types::Type;
+use crate::HashMap;
+use crate::{Mssql, MssqlRow};
+
+/// A row together with the rows that name it as their parent, as assembled by
+/// [`build_tree`].
+#[derive(Debug)]
+pub struct MssqlTreeNode {
+    /// The row itself.
+    pub row: MssqlRow,
+    /// The child rows, in the order they appeared in the result set.
+    pub children: Vec<MssqlTreeNode>,
+}
+
+/// Assemble flat adjacency-list rows, such as those of a recursive CTE, into trees.
+///
+/// Each row is identified by its `id_col` and attached under the row whose id equals its
+/// `parent_col`. Both columns are decoded as `K`, e.g. `i32` or `uuid::Uuid`. The returned
+/// roots, and the children of every node, keep the order of `rows`, so an `ORDER BY` in
+/// the query orders siblings.
+///
+/// A row becomes a root if its parent is `NULL` or is not among `rows`. The second case
+/// covers orphaned rows whose parent was deleted or filtered out, and the anchor of a CTE
+/// that fetches a subtree, whose parent is outside the result.
+///
+/// Fails with [`Error::Decode`] if two rows have the same id, or if following the parents
+/// from some row never reaches a root, i.e. the rows contain a cycle (a row can also be
+/// its own parent). Note that a recursive CTE anchored on a row inside a cycle doesn't
+/// terminate on its own and fails once it exceeds `MAXRECURSION`; see the guide for a query
+/// that stops at cycles instead.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+/// use sqlx::mssql::build_tree;
+///
+/// let rows = sqlx::query(
+///     "WITH tree AS ( \
+///          SELECT id, parent_id, name FROM categories WHERE id = @p1 \
+///          UNION ALL \
+///          SELECT c.id, c.parent_id, c.name FROM categories c JOIN tree t ON c.parent_id = t.id \
+///      ) \
+///      SELECT * FROM tree ORDER BY name",
+/// )
+/// .bind(1i32)
+/// .fetch_all(&mut *conn)
+/// .await?;
+///
+/// let roots = build_tree::<i32>(rows, "id", "parent_id")?;
+/// assert_eq!(roots.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+pub fn build_tree<K>(
+    rows: Vec<MssqlRow>,
+    id_col: &str,
+    parent_col: &str,
+) -> Result<Vec<MssqlTreeNode>, Error>
+where
+    K: for<'r> Decode<'r, Mssql> + Type<Mssql> + Eq + Hash,
+{
+    let mut index = HashMap::with_capacity(rows.len());
+    let mut parent_ids = Vec::with_capacity(rows.len());
+
+    for (i, row) in rows.iter().enumerate() {
+        let id: K = row.try_get(id_col)?;
+        parent_ids.push(row.try_get::<Option<K>, _>(parent_col)?);
+
+        if index.insert(id, i).is_some() {
+            return Err(Error::Decode(
+                format!("duplicate {id_col} in row {} of build_tree", i + 1).into(),
+            ));
+        }
+    }
+
+    let mut roots = Vec::new();
+    let mut children = vec![Vec::new(); rows.len()];
+
+    for (i, parent_id) in parent_ids.iter().enumerate() {
+        match parent_id
+            .as_ref()
+            .and_then(|parent_id| index.get(parent_id))
+        {
+            Some(&parent) => children[parent].push(i),
+            None => roots.push(i),
+        }
+    }
+
+    // Breadth-first from the roots, so every row comes after its parent. Rows that are
+    // never reached have no path to a root.
+    let mut order = Vec::with_capacity(rows.len());
+    let mut queue: VecDeque<usize> = roots.iter().copied().collect();
+    while let Some(i) = queue.pop_front() {
+        order.push(i);
+        queue.extend(&children[i]);
+    }
+
+    if order.len() < rows.len() {
+        let mut reached = vec![false; rows.len()];
+        for &i in &order {
+            reached[i] = true;
+        }
+        let first = reached
+            .iter()
+            .position(|reached| !reached)
+            .unwrap_or_default();
+
+        return Err(Error::Decode(
+            format!(
+                "row {} of build_tree is part of, or descends from, a cycle in {parent_col}",
+                first + 1
+            )
+            .into(),
+        ));
+    }
+
+    // Build the nodes bottom-up, so children are complete before they are moved into
+    // their parent; this also avoids recursing on deep trees.
+    let mut rows: Vec<Option<MssqlRow>> = rows.into_iter().map(Some).collect();
+    let mut nodes: Vec<Option<MssqlTreeNode>> =
+        std::iter::repeat_with(|| None).take(rows.len()).collect();
+
+    for &i in order.iter().rev() {
+        let children = children[i]
+            .iter()
+            .filter_map(|&child| nodes[child].take())
+            .collect();
+
+        nodes[i] = rows[i].take().map(|row| MssqlTreeNode { row, children });
+    }
+
+    Ok(roots.iter().filter_map(|&i| nodes[i].take()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::column::ColumnOrigin;
+    use crate::ext::ustr::UStr;
+    use crate::value::MssqlData;
+    use crate::{MssqlColumn, MssqlTypeInfo};
+
+    /// Rows of `(id, parent_id)` as two `INT` columns.
+    fn rows(pairs: &[(i32, Option<i32>)]) -> Vec<MssqlRow> {
+        let columns: Arc<Vec<MssqlColumn>> = Arc::new(
+            ["id", "parent_id"]
+                .into_iter()
+                .enumerate()
+                .map(|(ordinal, name)| MssqlColumn {
+                    ordinal,
+                    name: UStr::new(name),
+                    type_info: MssqlTypeInfo::new("INT"),
+                    origin: ColumnOrigin::Unknown,
+                    collation: None,
+                })
+                .collect(),
+        );
+        let column_names = Arc::new(
+            columns
+                .iter()
+                .map(|column| (column.name.clone(), column.ordinal))
+                .collect(),
+        );
+
+        pairs
+            .iter()
+            .map(|&(id, parent_id)| MssqlRow {
+                values: vec![
+                    MssqlData::I32(id),
+                    parent_id.map_or(MssqlData::Null, MssqlData::I32),
+                ],
+                columns: Arc::clone(&columns),
+                column_names: Arc::clone(&column_names),
+            })
+            .collect()
+    }
+
+    /// The tree as `id(child child ...)`.
+    fn render(node: &MssqlTreeNode) -> String {
+        let id: i32 = node.row.try_get("id").unwrap();
+        if node.children.is_empty() {
+            return id.to_string();
+        }
+
+        let children: Vec<String> = node.children.iter().map(render).collect();
+        format!("{id}({})", children.join(" "))
+    }
+
+    #[test]
+    fn it_builds_a_tree_from_an_adjacency_list() {
+        // children listed before their parents, and an orphan whose parent 99 is missing
+        let roots = build_tree::<i32>(
+            rows(&[
+                (4, Some(2)),
+                (2, Some(1)),
+                (1, None),
+                (3, Some(1)),
+                (5, Some(2)),
+                (6, Some(99)),
+            ]),
+            "id",
+            "parent_id",
+        )
+        .unwrap();
+
+        let roots: Vec<String> = roots.iter().map(render).collect();
+        assert_eq!(roots, ["1(2(4 5) 3)", "6"]);
+    }
+
+    #[test]
+    fn it_rejects_cycles_and_duplicate_ids() {
+        for pairs in [
+            &[(1, None), (2, Some(3)), (3, Some(2))][..],
+            &[(1, Some(1))],
+            &[(1, None), (2, Some(1)), (2, Some(1))],
+        ] {
+            let err = build_tree::<i32>(rows(pairs), "id", "parent_id").unwrap_err();
+            assert!(matches!(err, Error::Decode(_)), "{err:?}");
+        }
+
+        let err = build_tree::<i32>(rows(&[(1, None)]), "id", "missing").unwrap_err();
+        assert!(matches!(err, Error::ColumnNotFound(_)), "{err:?}");
+    }
+}
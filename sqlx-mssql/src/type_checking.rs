@@ -1,7 +1,11 @@
 // Type mappings used by the macros and `Debug` impls.
 
-#[allow(unused_imports)]
-use sqlx_core as sqlx;
+// The paths used below will also be emitted by the macros so they have to match the final facade.
+#[allow(unused_imports, dead_code)]
+mod sqlx {
+    pub use crate as mssql;
+    pub use sqlx_core::*;
+}
 
 use crate::Mssql;
 
@@ -15,6 +19,10 @@ impl_type_checking!(
         f32,
         f64,
 
+        // MONEY, SMALLMONEY; before f64, which is also compatible with them
+        sqlx::mssql::MssqlMoney,
+        sqlx::mssql::MssqlSmallMoney,
+
         // ordering is important here as otherwise we might infer strings to be binary
         // NVARCHAR, VARCHAR, NCHAR, CHAR, NTEXT, TEXT
         String,
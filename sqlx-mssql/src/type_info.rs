@@ -38,6 +38,44 @@ impl TypeInfo for MssqlTypeInfo {
     }
 }
 
+/// Whether `sql_type` is a single type name with an optional parenthesized `MAX` or one or
+/// two comma-separated integers, e.g. `INT`, `VARBINARY(MAX)` or `DECIMAL(19, 4)`.
+///
+/// Used wherever a caller-supplied type is written into a batch as is.
+pub(crate) fn is_plain_sql_type(sql_type: &str) -> bool {
+    let sql_type = sql_type.trim();
+
+    let name_len = sql_type
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(sql_type.len());
+    let (name, rest) = sql_type.split_at(name_len);
+
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return false;
+    }
+
+    let rest = rest.trim_start();
+    if rest.is_empty() {
+        return true;
+    }
+
+    let Some(args) = rest
+        .strip_prefix('(')
+        .and_then(|rest| rest.strip_suffix(')'))
+    else {
+        return false;
+    };
+
+    let is_integer = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    let args: Vec<&str> = args.split(',').map(str::trim).collect();
+
+    match args.as_slice() {
+        [arg] => arg.eq_ignore_ascii_case("MAX") || is_integer(arg),
+        [precision, scale] => is_integer(precision) && is_integer(scale),
+        _ => false,
+    }
+}
+
 /// Map a tiberius column type to a MSSQL type name string.
 pub(crate) fn type_name_for_tiberius(col_type: &tiberius::ColumnType) -> &'static str {
     match col_type {
@@ -69,7 +107,11 @@ pub(crate) fn type_name_for_tiberius(col_type: &tiberius::ColumnType) -> &'stati
         tiberius::ColumnType::Intn => "INT",
         tiberius::ColumnType::Bitn => "BIT",
         tiberius::ColumnType::Floatn => "FLOAT",
+        // tiberius can't decode SQL_VARIANT values (it stops on the column metadata), so this
+        // name is only seen through `describe`; values are read by casting to the base type
         tiberius::ColumnType::SSVariant => "SQL_VARIANT",
-        _ => "UNKNOWN",
+        // CLR types such as GEOGRAPHY and GEOMETRY; the type name isn't part of the column
+        // type, but `describe` reports it from `sp_describe_first_result_set`
+        tiberius::ColumnType::Udt => "UDT",
     }
 }
@@ -0,0 +1,119 @@
+use std::fmt::{self, Display, Formatter};
+use std::ops::Deref;
+
+use crate::decode::Decode;
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{Mssql, MssqlTypeInfo, MssqlValueRef};
+
+/// The byte order mark, as it reads after decoding from UTF-16 or UTF-8.
+const BOM: char = '\u{feff}';
+
+/// A string read with a leading byte order mark (`U+FEFF`) removed.
+///
+/// Decoding into `String` or `&str` is faithful: if a client stored text with a leading BOM,
+/// e.g. the contents of a file saved by an editor that writes one, the `String` starts with
+/// `'\u{feff}'` and compares unequal to the same text without it. Decode into this wrapper
+/// instead to drop that character. Only a single leading BOM is removed; `U+FEFF` elsewhere
+/// in the text is kept.
+///
+/// A BOM stored as UTF-8 bytes in a `VARCHAR` column with a non-UTF-8 collation isn't
+/// `U+FEFF` after the server converts it, and is left alone.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+/// use sqlx::mssql::MssqlBomStrippedStr;
+///
+/// let name: MssqlBomStrippedStr =
+///     sqlx::query_scalar("SELECT NCHAR(65279) + N'imported.csv'")
+///         .fetch_one(conn)
+///         .await?;
+/// assert_eq!(&*name, "imported.csv");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct MssqlBomStrippedStr(pub String);
+
+impl MssqlBomStrippedStr {
+    /// Take the string.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl Deref for MssqlBomStrippedStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for MssqlBomStrippedStr {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for MssqlBomStrippedStr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<MssqlBomStrippedStr> for String {
+    fn from(value: MssqlBomStrippedStr) -> Self {
+        value.0
+    }
+}
+
+impl Type<Mssql> for MssqlBomStrippedStr {
+    fn type_info() -> MssqlTypeInfo {
+        <str as Type<Mssql>>::type_info()
+    }
+
+    fn compatible(ty: &MssqlTypeInfo) -> bool {
+        <str as Type<Mssql>>::compatible(ty)
+    }
+}
+
+impl Decode<'_, Mssql> for MssqlBomStrippedStr {
+    fn decode(value: MssqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        let s = value.as_str()?;
+        Ok(Self(s.strip_prefix(BOM).unwrap_or(s).to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value::MssqlData;
+
+    #[test]
+    fn it_strips_only_a_leading_bom() {
+        let data = MssqlData::String("\u{feff}abc\u{feff}".into());
+        let value = || MssqlValueRef {
+            data: &data,
+            type_info: MssqlTypeInfo::new("NVARCHAR"),
+        };
+
+        assert_eq!(
+            <String as Decode<Mssql>>::decode(value()).unwrap(),
+            "\u{feff}abc\u{feff}"
+        );
+        assert_eq!(
+            &*MssqlBomStrippedStr::decode(value()).unwrap(),
+            "abc\u{feff}"
+        );
+
+        let data = MssqlData::String("\u{feff}\u{feff}abc".into());
+        let value = MssqlValueRef {
+            data: &data,
+            type_info: MssqlTypeInfo::new("NVARCHAR"),
+        };
+        assert_eq!(&*MssqlBomStrippedStr::decode(value).unwrap(), "\u{feff}abc");
+    }
+}
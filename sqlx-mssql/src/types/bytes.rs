@@ -58,6 +58,35 @@ impl Decode<'_, Mssql> for Vec<u8> {
     }
 }
 
+impl<const N: usize> Type<Mssql> for [u8; N] {
+    fn type_info() -> MssqlTypeInfo {
+        MssqlTypeInfo::new("BINARY")
+    }
+
+    fn compatible(ty: &MssqlTypeInfo) -> bool {
+        bytes_compatible(ty)
+    }
+}
+
+impl<const N: usize> Encode<'_, Mssql> for [u8; N] {
+    fn encode_by_ref(&self, buf: &mut Vec<MssqlArgumentValue>) -> Result<IsNull, BoxDynError> {
+        <&[u8] as Encode<Mssql>>::encode(self.as_slice(), buf)
+    }
+}
+
+impl<const N: usize> Decode<'_, Mssql> for [u8; N] {
+    fn decode(value: MssqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        let bytes = value.as_bytes()?;
+        bytes.try_into().map_err(|_| {
+            format!(
+                "expected {N} bytes for [u8; {N}], got {} bytes",
+                bytes.len()
+            )
+            .into()
+        })
+    }
+}
+
 forward_encode_impl!(Arc<[u8]>, &[u8], Mssql);
 forward_encode_impl!(Rc<[u8]>, &[u8], Mssql);
 forward_encode_impl!(Box<[u8]>, &[u8], Mssql);
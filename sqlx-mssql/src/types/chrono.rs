@@ -1,4 +1,4 @@
-use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 
 use crate::database::MssqlArgumentValue;
 use crate::decode::Decode;
@@ -164,3 +164,46 @@ impl Decode<'_, Mssql> for DateTime<FixedOffset> {
         }
     }
 }
+
+// ── DateTime<Local> ─────────────────────────────────────────────────────────
+
+impl Type<Mssql> for DateTime<Local> {
+    fn type_info() -> MssqlTypeInfo {
+        MssqlTypeInfo::new("DATETIMEOFFSET")
+    }
+
+    fn compatible(ty: &MssqlTypeInfo) -> bool {
+        matches!(ty.base_name(), "DATETIMEOFFSET" | "DATETIME2")
+    }
+}
+
+impl Encode<'_, Mssql> for DateTime<Local> {
+    fn encode_by_ref(&self, buf: &mut Vec<MssqlArgumentValue>) -> Result<IsNull, BoxDynError> {
+        // Sent with the local offset in effect at this instant. `DATETIMEOFFSET` offsets are
+        // whole minutes, so the odd historical offset with seconds (local mean time before
+        // time zones) is sent as UTC instead of being truncated.
+        let fixed = self.fixed_offset();
+        let fixed = if fixed.offset().local_minus_utc() % 60 == 0 {
+            fixed
+        } else {
+            self.with_timezone(&Utc).fixed_offset()
+        };
+
+        buf.push(MssqlArgumentValue::DateTimeFixedOffset(fixed));
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Mssql> for DateTime<Local> {
+    fn decode(value: MssqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        // Converting an instant is never ambiguous: in a DST overlap, the stored offset picks
+        // which of the two local readings of the wall-clock time is meant.
+        match value.data {
+            MssqlData::DateTimeFixedOffset(v) => Ok(v.with_timezone(&Local)),
+            // Assume UTC if no offset information
+            MssqlData::NaiveDateTime(v) => Ok(v.and_utc().with_timezone(&Local)),
+            MssqlData::Null => Err("unexpected NULL".into()),
+            _ => Err(format!("expected datetimeoffset, got {:?}", value.data).into()),
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use crate::database::MssqlArgumentValue;
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::value::MssqlData;
+use crate::{Mssql, MssqlTypeInfo, MssqlValueRef};
+
+/// Day number of 9999-12-31, the last date a `DATE` column can hold.
+const MAX_DAYS: i32 = 3_652_058;
+
+/// A `DATE` as its raw day number: the days since `0001-01-01` in the proleptic Gregorian
+/// calendar, which is how SQL Server stores the type on disk and on the wire.
+///
+/// Day `0` is `0001-01-01`, day `693_595` is `1900-01-01` and day `3_652_058` is
+/// `9999-12-31`, the last valid date. For interop with systems that compute with day
+/// numbers; use `chrono::NaiveDate` or `time::Date` otherwise.
+///
+/// Encoding a day number outside `0..=3_652_058` fails.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+/// use sqlx::mssql::MssqlDateDays;
+///
+/// let days: MssqlDateDays = sqlx::query_scalar("SELECT CAST('2000-01-01' AS DATE)")
+///     .fetch_one(conn)
+///     .await?;
+/// assert_eq!(days, MssqlDateDays(730_119));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MssqlDateDays(pub i32);
+
+/// Julian day number of `0001-01-01`.
+#[cfg(all(feature = "time", not(feature = "chrono")))]
+const JULIAN_DAY_OFFSET: i32 = 1_721_426;
+
+impl MssqlDateDays {
+    fn check_range(self) -> Result<(), BoxDynError> {
+        if (0..=MAX_DAYS).contains(&self.0) {
+            Ok(())
+        } else {
+            Err(format!("day number {} is out of range for DATE", self.0).into())
+        }
+    }
+}
+
+impl Type<Mssql> for MssqlDateDays {
+    fn type_info() -> MssqlTypeInfo {
+        MssqlTypeInfo::new("DATE")
+    }
+
+    fn compatible(ty: &MssqlTypeInfo) -> bool {
+        ty.base_name() == "DATE"
+    }
+}
+
+impl Encode<'_, Mssql> for MssqlDateDays {
+    fn encode_by_ref(&self, buf: &mut Vec<MssqlArgumentValue>) -> Result<IsNull, BoxDynError> {
+        self.check_range()?;
+
+        // `num_days_from_ce` counts 0001-01-01 as day 1.
+        #[cfg(feature = "chrono")]
+        let value = chrono::NaiveDate::from_num_days_from_ce_opt(self.0 + 1)
+            .map(MssqlArgumentValue::NaiveDate);
+
+        #[cfg(not(feature = "chrono"))]
+        let value = time::Date::from_julian_day(self.0 + JULIAN_DAY_OFFSET)
+            .ok()
+            .map(MssqlArgumentValue::TimeDate);
+
+        let value =
+            value.ok_or_else(|| format!("day number {} is out of range for DATE", self.0))?;
+        buf.push(value);
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Mssql> for MssqlDateDays {
+    fn decode(value: MssqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.data {
+            #[cfg(feature = "chrono")]
+            MssqlData::NaiveDate(v) => {
+                use chrono::Datelike;
+                Ok(MssqlDateDays(v.num_days_from_ce() - 1))
+            }
+            #[cfg(all(feature = "time", not(feature = "chrono")))]
+            MssqlData::TimeDate(v) => Ok(MssqlDateDays(v.to_julian_day() - JULIAN_DAY_OFFSET)),
+            MssqlData::Null => Err("unexpected NULL".into()),
+            _ => Err(format!("expected date, got {:?}", value.data).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(days: i32) -> i32 {
+        let mut buf = Vec::new();
+        let _ = MssqlDateDays(days).encode_by_ref(&mut buf).unwrap();
+
+        let data = match buf.pop() {
+            #[cfg(feature = "chrono")]
+            Some(MssqlArgumentValue::NaiveDate(v)) => MssqlData::NaiveDate(v),
+            #[cfg(all(feature = "time", not(feature = "chrono")))]
+            Some(MssqlArgumentValue::TimeDate(v)) => MssqlData::TimeDate(v),
+            other => panic!("unexpected argument {other:?}"),
+        };
+
+        MssqlDateDays::decode(MssqlValueRef {
+            data: &data,
+            type_info: MssqlTypeInfo::new("DATE"),
+        })
+        .unwrap()
+        .0
+    }
+
+    #[test]
+    fn it_round_trips_day_numbers() {
+        for days in [0, 1, 693_595, 730_119, MAX_DAYS] {
+            assert_eq!(round_trip(days), days);
+        }
+    }
+
+    #[test]
+    fn it_rejects_days_outside_the_date_range() {
+        for days in [-1, MAX_DAYS + 1, i32::MAX] {
+            assert!(MssqlDateDays(days).encode_by_ref(&mut Vec::new()).is_err());
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::time::Duration;
+
+use crate::database::MssqlArgumentValue;
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{Mssql, MssqlTypeInfo, MssqlValueRef};
+
+/// A [`Duration`] stored as a `BIGINT` count of nanoseconds.
+///
+/// `TIME` only holds spans shorter than a day, so this is the way to store longer elapsed
+/// times, up to `i64::MAX` nanoseconds (about 292 years). The value is exact to the
+/// nanosecond and sums and compares like a number in SQL, e.g.
+/// `SUM(elapsed_ns) / 1000000000` for whole seconds.
+///
+/// Encoding a duration longer than `i64::MAX` nanoseconds fails, as does decoding a
+/// negative value.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+/// use std::time::Duration;
+/// use sqlx::mssql::MssqlDurationNanos;
+///
+/// sqlx::query("INSERT INTO jobs (elapsed_ns) VALUES (@p1)")
+///     .bind(MssqlDurationNanos(Duration::from_secs(3 * 86_400)))
+///     .execute(&mut *conn)
+///     .await?;
+///
+/// let elapsed: MssqlDurationNanos = sqlx::query_scalar("SELECT MAX(elapsed_ns) FROM jobs")
+///     .fetch_one(&mut *conn)
+///     .await?;
+/// assert_eq!(elapsed.0, Duration::from_secs(3 * 86_400));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct MssqlDurationNanos(pub Duration);
+
+impl From<Duration> for MssqlDurationNanos {
+    fn from(value: Duration) -> Self {
+        Self(value)
+    }
+}
+
+impl From<MssqlDurationNanos> for Duration {
+    fn from(value: MssqlDurationNanos) -> Self {
+        value.0
+    }
+}
+
+impl Type<Mssql> for MssqlDurationNanos {
+    fn type_info() -> MssqlTypeInfo {
+        <i64 as Type<Mssql>>::type_info()
+    }
+
+    fn compatible(ty: &MssqlTypeInfo) -> bool {
+        <i64 as Type<Mssql>>::compatible(ty)
+    }
+}
+
+impl Encode<'_, Mssql> for MssqlDurationNanos {
+    fn encode_by_ref(&self, buf: &mut Vec<MssqlArgumentValue>) -> Result<IsNull, BoxDynError> {
+        let nanos = i64::try_from(self.0.as_nanos()).map_err(|_| {
+            format!(
+                "duration {:?} is too long for BIGINT nanoseconds (at most {} ns)",
+                self.0,
+                i64::MAX
+            )
+        })?;
+        buf.push(MssqlArgumentValue::I64(nanos));
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Mssql> for MssqlDurationNanos {
+    fn decode(value: MssqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        let nanos = <i64 as Decode<Mssql>>::decode(value)?;
+        let nanos =
+            u64::try_from(nanos).map_err(|_| format!("negative duration: {nanos} nanoseconds"))?;
+        Ok(Self(Duration::from_nanos(nanos)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value::MssqlData;
+
+    #[test]
+    fn it_stores_durations_as_nanoseconds() {
+        let mut buf = Vec::new();
+        let _ = MssqlDurationNanos(Duration::new(259_200, 5))
+            .encode_by_ref(&mut buf)
+            .unwrap();
+        assert!(matches!(
+            buf[..],
+            [MssqlArgumentValue::I64(259_200_000_000_005)]
+        ));
+
+        let too_long = MssqlDurationNanos(Duration::from_secs(300 * 365 * 86_400));
+        assert!(too_long.encode_by_ref(&mut Vec::new()).is_err());
+
+        let decode = |data: MssqlData| {
+            MssqlDurationNanos::decode(MssqlValueRef {
+                data: &data,
+                type_info: MssqlTypeInfo::new("BIGINT"),
+            })
+        };
+        assert_eq!(
+            decode(MssqlData::I64(1_500_000_000)).unwrap(),
+            MssqlDurationNanos(Duration::from_millis(1_500))
+        );
+        assert!(decode(MssqlData::I64(-1)).is_err());
+    }
+}
@@ -6,6 +6,19 @@ use crate::types::Type;
 use crate::value::MssqlData;
 use crate::{Mssql, MssqlTypeInfo, MssqlValueRef};
 
+/// SQL Server's `REAL` and `FLOAT` can't store `NaN` or the infinities, and the server
+/// rejects the whole RPC request if one is sent.
+fn check_finite(value: f64, ty: &str) -> Result<(), BoxDynError> {
+    if value.is_finite() {
+        Ok(())
+    } else {
+        Err(
+            format!("cannot encode {value} as {ty}: SQL Server does not support non-finite floats")
+                .into(),
+        )
+    }
+}
+
 fn real_compatible(ty: &MssqlTypeInfo) -> bool {
     matches!(ty.base_name(), "REAL" | "FLOAT" | "MONEY" | "SMALLMONEY")
 }
@@ -22,6 +35,7 @@ impl Type<Mssql> for f32 {
 
 impl Encode<'_, Mssql> for f32 {
     fn encode_by_ref(&self, buf: &mut Vec<MssqlArgumentValue>) -> Result<IsNull, BoxDynError> {
+        check_finite(f64::from(*self), "REAL")?;
         buf.push(MssqlArgumentValue::F32(*self));
         Ok(IsNull::No)
     }
@@ -51,6 +65,7 @@ impl Type<Mssql> for f64 {
 
 impl Encode<'_, Mssql> for f64 {
     fn encode_by_ref(&self, buf: &mut Vec<MssqlArgumentValue>) -> Result<IsNull, BoxDynError> {
+        check_finite(*self, "FLOAT")?;
         buf.push(MssqlArgumentValue::F64(*self));
         Ok(IsNull::No)
     }
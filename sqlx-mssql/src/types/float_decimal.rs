@@ -0,0 +1,116 @@
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::database::MssqlArgumentValue;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{Mssql, MssqlTypeInfo};
+
+/// The largest scale SQL Server's `DECIMAL` type supports.
+const MAX_SCALE: u32 = 28;
+
+/// An [`f64`] bound as a `DECIMAL` rounded to a fixed scale.
+///
+/// A plain `f64` is sent as `FLOAT` and converted by the server when stored into a `DECIMAL`
+/// column. This wrapper converts the value on the client instead, using the shortest decimal
+/// representation of the float (so `2.675` is `2.675`, not `2.67499999...`) rounded to
+/// `scale` digits with round-half-to-even ("banker's rounding"). The result is therefore
+/// deterministic regardless of the column definition or server settings.
+///
+/// `NaN` and the infinities have no `DECIMAL` representation and fail to encode, as do values
+/// outside the range of `rust_decimal::Decimal`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+/// use sqlx::mssql::MssqlFloatDecimal;
+///
+/// // Stored as 2.68
+/// sqlx::query("INSERT INTO prices (amount) VALUES (@p1)")
+///     .bind(MssqlFloatDecimal::new(2.675, 2))
+///     .execute(conn)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MssqlFloatDecimal {
+    value: f64,
+    scale: u32,
+}
+
+impl MssqlFloatDecimal {
+    /// Wrap `value` to be rounded to `scale` digits after the decimal point.
+    ///
+    /// `scale` is capped at 28, the largest scale `DECIMAL` supports.
+    pub fn new(value: f64, scale: u32) -> Self {
+        Self {
+            value,
+            scale: std::cmp::min(scale, MAX_SCALE),
+        }
+    }
+
+    /// The value as it will be sent to the server.
+    pub fn to_decimal(&self) -> Result<Decimal, BoxDynError> {
+        if !self.value.is_finite() {
+            return Err(format!("cannot encode {} as DECIMAL", self.value).into());
+        }
+
+        let decimal = Decimal::from_f64(self.value)
+            .ok_or_else(|| format!("{} is out of range for DECIMAL", self.value))?;
+
+        Ok(decimal.round_dp_with_strategy(self.scale, RoundingStrategy::MidpointNearestEven))
+    }
+}
+
+impl Type<Mssql> for MssqlFloatDecimal {
+    fn type_info() -> MssqlTypeInfo {
+        <Decimal as Type<Mssql>>::type_info()
+    }
+
+    fn compatible(ty: &MssqlTypeInfo) -> bool {
+        <Decimal as Type<Mssql>>::compatible(ty)
+    }
+}
+
+impl Encode<'_, Mssql> for MssqlFloatDecimal {
+    fn encode_by_ref(&self, buf: &mut Vec<MssqlArgumentValue>) -> Result<IsNull, BoxDynError> {
+        buf.push(MssqlArgumentValue::Decimal(self.to_decimal()?));
+        Ok(IsNull::No)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rounded(value: f64, scale: u32) -> String {
+        MssqlFloatDecimal::new(value, scale)
+            .to_decimal()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn it_rounds_half_to_even() {
+        assert_eq!(rounded(2.675, 2), "2.68");
+        assert_eq!(rounded(2.665, 2), "2.66");
+        assert_eq!(rounded(0.125, 2), "0.12");
+        assert_eq!(rounded(-0.135, 2), "-0.14");
+        assert_eq!(rounded(2.5, 0), "2");
+        assert_eq!(rounded(3.5, 0), "4");
+        assert_eq!(rounded(0.1, 4), "0.1");
+    }
+
+    #[test]
+    fn it_rejects_non_finite_values() {
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let err = MssqlFloatDecimal::new(value, 2).to_decimal().unwrap_err();
+            assert!(err.to_string().contains("DECIMAL"), "{err}");
+        }
+
+        assert!(MssqlFloatDecimal::new(1e30, 2).to_decimal().is_err());
+    }
+}
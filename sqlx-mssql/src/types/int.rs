@@ -10,6 +10,77 @@ fn int_compatible(ty: &MssqlTypeInfo) -> bool {
     matches!(ty.base_name(), "TINYINT" | "SMALLINT" | "INT" | "BIGINT")
 }
 
+/// Integer types wider than `BIGINT` also read `DECIMAL` columns, as long as the value has no
+/// fractional part.
+fn integral_compatible(ty: &MssqlTypeInfo) -> bool {
+    int_compatible(ty) || matches!(ty.base_name(), "DECIMAL" | "NUMERIC")
+}
+
+/// The largest value of `DECIMAL(38, 0)`.
+const MAX_DECIMAL_38: i128 = 10_i128.pow(38) - 1;
+
+/// Read an integer or integral `DECIMAL` column, failing if the value has a fractional part.
+fn decode_integral(value: &MssqlValueRef<'_>) -> Result<i128, BoxDynError> {
+    let fractional = || format!("expected an integral value, got {:?}", value.data).into();
+
+    match value.data {
+        MssqlData::U8(v) => Ok(i128::from(*v)),
+        MssqlData::I16(v) => Ok(i128::from(*v)),
+        MssqlData::I32(v) => Ok(i128::from(*v)),
+        MssqlData::I64(v) => Ok(i128::from(*v)),
+        #[cfg(feature = "rust_decimal")]
+        MssqlData::Decimal(v) => {
+            use rust_decimal::prelude::ToPrimitive;
+
+            // the 96-bit mantissa always fits
+            v.fract()
+                .is_zero()
+                .then(|| v.to_i128())
+                .flatten()
+                .ok_or_else(fractional)
+        }
+        #[cfg(all(feature = "bigdecimal", not(feature = "rust_decimal")))]
+        MssqlData::BigDecimal(ref v) => {
+            use bigdecimal::ToPrimitive;
+
+            if !v.is_integer() {
+                return Err(fractional());
+            }
+            v.to_i128()
+                .ok_or_else(|| format!("DECIMAL {v} does not fit in i128").into())
+        }
+        #[cfg(any(feature = "rust_decimal", not(feature = "bigdecimal")))]
+        MssqlData::Numeric(v, scale) => {
+            // SQL Server scales are at most 38, and 10^38 fits in an i128
+            let divisor = 10_i128.pow(u32::from(*scale));
+            if v % divisor != 0 {
+                return Err(fractional());
+            }
+            Ok(v / divisor)
+        }
+        MssqlData::Null => Err("unexpected NULL".into()),
+        _ => Err(format!("expected integer, got {:?}", value.data).into()),
+    }
+}
+
+/// Convert an integer column to an unsigned type, failing on negative or too large values.
+fn decode_unsigned<T>(value: &MssqlValueRef<'_>) -> Result<T, BoxDynError>
+where
+    T: From<u8> + TryFrom<i16> + TryFrom<i32> + TryFrom<i64>,
+    <T as TryFrom<i16>>::Error: std::error::Error + Send + Sync + 'static,
+    <T as TryFrom<i32>>::Error: std::error::Error + Send + Sync + 'static,
+    <T as TryFrom<i64>>::Error: std::error::Error + Send + Sync + 'static,
+{
+    match value.data {
+        MssqlData::U8(v) => Ok(T::from(*v)),
+        MssqlData::I16(v) => Ok((*v).try_into()?),
+        MssqlData::I32(v) => Ok((*v).try_into()?),
+        MssqlData::I64(v) => Ok((*v).try_into()?),
+        MssqlData::Null => Err("unexpected NULL".into()),
+        _ => Err(format!("expected integer, got {:?}", value.data).into()),
+    }
+}
+
 // u8 - MSSQL's TINYINT is unsigned (0-255)
 impl Type<Mssql> for u8 {
     fn type_info() -> MssqlTypeInfo {
@@ -168,3 +239,131 @@ impl Decode<'_, Mssql> for i64 {
         }
     }
 }
+
+// u16 - widened to INT, since SMALLINT is signed
+impl Type<Mssql> for u16 {
+    fn type_info() -> MssqlTypeInfo {
+        MssqlTypeInfo::new("INT")
+    }
+
+    fn compatible(ty: &MssqlTypeInfo) -> bool {
+        int_compatible(ty)
+    }
+}
+
+impl Encode<'_, Mssql> for u16 {
+    fn encode_by_ref(&self, buf: &mut Vec<MssqlArgumentValue>) -> Result<IsNull, BoxDynError> {
+        buf.push(MssqlArgumentValue::I32(i32::from(*self)));
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Mssql> for u16 {
+    fn decode(value: MssqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        decode_unsigned(&value)
+    }
+}
+
+// u32 - widened to BIGINT, since INT is signed
+impl Type<Mssql> for u32 {
+    fn type_info() -> MssqlTypeInfo {
+        MssqlTypeInfo::new("BIGINT")
+    }
+
+    fn compatible(ty: &MssqlTypeInfo) -> bool {
+        int_compatible(ty)
+    }
+}
+
+impl Encode<'_, Mssql> for u32 {
+    fn encode_by_ref(&self, buf: &mut Vec<MssqlArgumentValue>) -> Result<IsNull, BoxDynError> {
+        buf.push(MssqlArgumentValue::I64(i64::from(*self)));
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Mssql> for u32 {
+    fn decode(value: MssqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        decode_unsigned(&value)
+    }
+}
+
+// u64 - BIGINT, or NUMERIC(20, 0) for values above i64::MAX
+impl Type<Mssql> for u64 {
+    fn type_info() -> MssqlTypeInfo {
+        MssqlTypeInfo::new("BIGINT")
+    }
+
+    fn compatible(ty: &MssqlTypeInfo) -> bool {
+        integral_compatible(ty)
+    }
+}
+
+impl Encode<'_, Mssql> for u64 {
+    fn encode_by_ref(&self, buf: &mut Vec<MssqlArgumentValue>) -> Result<IsNull, BoxDynError> {
+        buf.push(MssqlArgumentValue::U64(*self));
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Mssql> for u64 {
+    fn decode(value: MssqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        Ok(decode_integral(&value)?.try_into()?)
+    }
+}
+
+// i128 - DECIMAL(38, 0), the widest integer SQL Server can store
+impl Type<Mssql> for i128 {
+    fn type_info() -> MssqlTypeInfo {
+        MssqlTypeInfo::new("DECIMAL")
+    }
+
+    fn compatible(ty: &MssqlTypeInfo) -> bool {
+        integral_compatible(ty)
+    }
+}
+
+impl Encode<'_, Mssql> for i128 {
+    fn encode_by_ref(&self, buf: &mut Vec<MssqlArgumentValue>) -> Result<IsNull, BoxDynError> {
+        if self.unsigned_abs() > MAX_DECIMAL_38.unsigned_abs() {
+            return Err(format!("{self} does not fit in DECIMAL(38, 0)").into());
+        }
+        buf.push(MssqlArgumentValue::I128(*self));
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Mssql> for i128 {
+    fn decode(value: MssqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        decode_integral(&value)
+    }
+}
+
+// u128 - DECIMAL(38, 0)
+impl Type<Mssql> for u128 {
+    fn type_info() -> MssqlTypeInfo {
+        MssqlTypeInfo::new("DECIMAL")
+    }
+
+    fn compatible(ty: &MssqlTypeInfo) -> bool {
+        integral_compatible(ty)
+    }
+}
+
+impl Encode<'_, Mssql> for u128 {
+    fn encode_by_ref(&self, buf: &mut Vec<MssqlArgumentValue>) -> Result<IsNull, BoxDynError> {
+        match i128::try_from(*self) {
+            Ok(value) if value <= MAX_DECIMAL_38 => {
+                buf.push(MssqlArgumentValue::I128(value));
+                Ok(IsNull::No)
+            }
+            _ => Err(format!("{self} does not fit in DECIMAL(38, 0)").into()),
+        }
+    }
+}
+
+impl Decode<'_, Mssql> for u128 {
+    fn decode(value: MssqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        Ok(decode_integral(&value)?.try_into()?)
+    }
+}
@@ -10,10 +10,22 @@
 //! | `i16`                                 | SMALLINT                                             |
 //! | `i32`                                 | INT                                                  |
 //! | `i64`                                 | BIGINT                                               |
+//! | `u16`                                 | INT                                                  |
+//! | `u32`                                 | BIGINT                                               |
+//! | `u64`                                 | BIGINT, NUMERIC(20, 0) above `i64::MAX`              |
+//! | `i128`, `u128`                        | DECIMAL(38, 0)                                       |
 //! | `f32`                                 | REAL, FLOAT                                          |
 //! | `f64`                                 | REAL, FLOAT, MONEY, SMALLMONEY                       |
 //! | `&str`, [`String`]                    | NVARCHAR                                             |
 //! | `&[u8]`, `Vec<u8>`                   | VARBINARY                                            |
+//! | `[u8; N]`                             | BINARY(N)                                            |
+//! | [`MssqlBomStrippedStr`][bom_stripped::MssqlBomStrippedStr] | NVARCHAR (decode only, leading BOM removed) |
+//! | [`MssqlDurationNanos`][duration_nanos::MssqlDurationNanos] | BIGINT (`std::time::Duration` in nanoseconds) |
+//! | [`MssqlMoney`][money::MssqlMoney]     | MONEY, SMALLMONEY                                    |
+//! | [`MssqlSmallMoney`][money::MssqlSmallMoney] | SMALLMONEY                                     |
+//! | [`MssqlRowVersion`][rowversion::MssqlRowVersion] | ROWVERSION (TIMESTAMP), BINARY(8)      |
+//! | [`MssqlSpatial`][spatial::MssqlSpatial] | VARBINARY (GEOGRAPHY/GEOMETRY via `STAsBinary()`)  |
+//! | [`MssqlVariant`][variant::MssqlVariant] | SQL_VARIANT (written with an explicit base type)   |
 //!
 //! ### Feature-gated
 //!
@@ -22,8 +34,12 @@
 //! | `uuid::Uuid`                          | UNIQUEIDENTIFIER                                     |
 //! | `rust_decimal::Decimal`               | DECIMAL, NUMERIC, MONEY                              |
 //! | `bigdecimal::BigDecimal`              | DECIMAL, NUMERIC, MONEY                              |
+//! | [`MssqlFloatDecimal`][float_decimal::MssqlFloatDecimal] (`rust_decimal`) | DECIMAL (`f64` rounded half-to-even) |
+//! | [`MssqlScaled<T>`][scaled::MssqlScaled] (`chrono` or `time`) | TIME, DATETIME2, DATETIMEOFFSET with 0-7 fractional digits (encode only) |
 //! | `time::Date`                          | DATE                                                 |
+//! | [`MssqlDateDays`][date_days::MssqlDateDays] (`chrono` or `time`) | DATE (days since 0001-01-01) |
 //! | `time::Time`                          | TIME                                                 |
+//! | `time::Duration`                      | TIME (at least zero and less than 24 hours)          |
 //! | `time::PrimitiveDateTime`             | DATETIME2, DATETIME, SMALLDATETIME                   |
 //! | `time::OffsetDateTime`                | DATETIMEOFFSET, DATETIME2                            |
 //! | `serde_json::Value` (`Json<T>`)       | NVARCHAR (JSON stored as string)                     |
@@ -37,19 +53,31 @@ pub(crate) use sqlx_core::types::*;
 
 #[cfg(feature = "bigdecimal")]
 mod bigdecimal;
+pub mod bom_stripped;
 mod bool;
 mod bytes;
 #[cfg(feature = "chrono")]
 mod chrono;
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub mod date_days;
+pub mod duration_nanos;
 mod float;
+#[cfg(feature = "rust_decimal")]
+pub mod float_decimal;
 mod int;
 #[cfg(feature = "json")]
 mod json;
+pub mod money;
+pub mod rowversion;
 #[cfg(feature = "rust_decimal")]
 mod rust_decimal;
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub mod scaled;
+pub mod spatial;
 mod str;
 #[cfg(feature = "time")]
 mod time;
 #[cfg(feature = "uuid")]
 mod uuid;
+pub mod variant;
 pub mod xml;
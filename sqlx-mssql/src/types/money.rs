@@ -0,0 +1,208 @@
+use crate::database::MssqlArgumentValue;
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::value::MssqlData;
+use crate::{Mssql, MssqlTypeInfo, MssqlValueRef};
+
+/// Ten-thousandths of a currency unit per unit; `MONEY` and `SMALLMONEY` have four decimals.
+const SCALE: i64 = 10_000;
+
+/// 2^63, the first magnitude outside the range of `MONEY`'s underlying `i64`.
+const I64_LIMIT: f64 = 9_223_372_036_854_775_808.0;
+
+/// A SQL Server `MONEY` value, as a number of ten-thousandths of a currency unit.
+///
+/// `MONEY` is an eight-byte integer scaled by 10,000, so `MssqlMoney(12_345_678)` is
+/// `1234.5678`. Unlike `f64`, which binds as `FLOAT`, this is sent as `NUMERIC(19, 4)` and
+/// stored into a `MONEY` column without rounding.
+///
+/// Values are read back through the `f64` tiberius decodes `MONEY` into, which is exact up
+/// to ±225,179,981,368.5248 (2^51 ten-thousandths); larger amounts can decode off by a few
+/// ten-thousandths.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+/// use sqlx::mssql::MssqlMoney;
+///
+/// let price: MssqlMoney = sqlx::query_scalar("SELECT CAST(19.99 AS MONEY)")
+///     .fetch_one(conn)
+///     .await?;
+/// assert_eq!(price, MssqlMoney(199_900));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct MssqlMoney(pub i64);
+
+/// A SQL Server `SMALLMONEY` value, as a number of ten-thousandths of a currency unit.
+///
+/// `SMALLMONEY` is a four-byte integer scaled by 10,000, so the `i32` covers exactly its
+/// range of -214,748.3648 to 214,748.3647 and every value encodes;
+/// [`from_decimal`][Self::from_decimal] checks the range when converting. Sent as
+/// `NUMERIC(10, 4)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct MssqlSmallMoney(pub i32);
+
+#[cfg(feature = "rust_decimal")]
+impl MssqlMoney {
+    /// The value as a `rust_decimal::Decimal` with four decimals.
+    pub fn to_decimal(self) -> rust_decimal::Decimal {
+        rust_decimal::Decimal::new(self.0, 4)
+    }
+
+    /// Convert `decimal`, or `None` if it has more than four decimals or is outside the
+    /// range of `MONEY`.
+    pub fn from_decimal(decimal: rust_decimal::Decimal) -> Option<Self> {
+        decimal_to_units(decimal).map(Self)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl MssqlSmallMoney {
+    /// The value as a `rust_decimal::Decimal` with four decimals.
+    pub fn to_decimal(self) -> rust_decimal::Decimal {
+        rust_decimal::Decimal::new(i64::from(self.0), 4)
+    }
+
+    /// Convert `decimal`, or `None` if it has more than four decimals or is outside the
+    /// range of `SMALLMONEY`, ±214,748.3647.
+    pub fn from_decimal(decimal: rust_decimal::Decimal) -> Option<Self> {
+        decimal_to_units(decimal)?.try_into().ok().map(Self)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+fn decimal_to_units(decimal: rust_decimal::Decimal) -> Option<i64> {
+    use rust_decimal::prelude::ToPrimitive;
+
+    let units = decimal.checked_mul(rust_decimal::Decimal::from(SCALE))?;
+    if !units.fract().is_zero() {
+        return None;
+    }
+    units.to_i64()
+}
+
+/// Recover the ten-thousandths of a `MONEY` or `SMALLMONEY` value, which tiberius reads as
+/// `f64`.
+fn decode_units(value: &MssqlValueRef<'_>) -> Result<i64, BoxDynError> {
+    match value.data {
+        MssqlData::F64(v) => {
+            let units = (v * SCALE as f64).round();
+            if units.is_nan() || units.abs() >= I64_LIMIT {
+                return Err(format!("{v} is out of range for MONEY").into());
+            }
+            // SAFETY: range-checked above
+            #[allow(clippy::cast_possible_truncation)]
+            Ok(units as i64)
+        }
+        MssqlData::Null => Err("unexpected NULL".into()),
+        _ => Err(format!("expected MONEY, got {:?}", value.data).into()),
+    }
+}
+
+impl Type<Mssql> for MssqlMoney {
+    fn type_info() -> MssqlTypeInfo {
+        MssqlTypeInfo::new("MONEY")
+    }
+
+    fn compatible(ty: &MssqlTypeInfo) -> bool {
+        matches!(ty.base_name(), "MONEY" | "SMALLMONEY")
+    }
+}
+
+impl Encode<'_, Mssql> for MssqlMoney {
+    fn encode_by_ref(&self, buf: &mut Vec<MssqlArgumentValue>) -> Result<IsNull, BoxDynError> {
+        buf.push(MssqlArgumentValue::Money(self.0));
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Mssql> for MssqlMoney {
+    fn decode(value: MssqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        decode_units(&value).map(Self)
+    }
+}
+
+impl Type<Mssql> for MssqlSmallMoney {
+    fn type_info() -> MssqlTypeInfo {
+        MssqlTypeInfo::new("SMALLMONEY")
+    }
+
+    fn compatible(ty: &MssqlTypeInfo) -> bool {
+        ty.base_name() == "SMALLMONEY"
+    }
+}
+
+impl Encode<'_, Mssql> for MssqlSmallMoney {
+    fn encode_by_ref(&self, buf: &mut Vec<MssqlArgumentValue>) -> Result<IsNull, BoxDynError> {
+        buf.push(MssqlArgumentValue::Money(i64::from(self.0)));
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Mssql> for MssqlSmallMoney {
+    fn decode(value: MssqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        let units = decode_units(&value)?;
+        let units = i32::try_from(units)
+            .map_err(|_| format!("{units} ten-thousandths are out of range for SMALLMONEY"))?;
+        Ok(Self(units))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_money(v: f64) -> Result<i64, BoxDynError> {
+        decode_units(&MssqlValueRef {
+            data: &MssqlData::F64(v),
+            type_info: MssqlTypeInfo::new("MONEY"),
+        })
+    }
+
+    #[test]
+    fn it_recovers_exact_units_from_f64() {
+        // how tiberius turns the raw units into f64
+        for units in [
+            0_i64,
+            1,
+            -1,
+            12_345_678,
+            2_147_483_647,
+            -2_147_483_648,
+            1 << 51,
+            -(1 << 51),
+        ] {
+            assert_eq!(decode_money(units as f64 / 1e4).unwrap(), units);
+        }
+
+        assert!(decode_money(f64::NAN).is_err());
+        assert!(decode_money(1e300).is_err());
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn it_converts_decimals_within_range() {
+        use rust_decimal::Decimal;
+
+        let max = Decimal::new(2_147_483_647, 4);
+        assert_eq!(
+            MssqlSmallMoney::from_decimal(max),
+            Some(MssqlSmallMoney(i32::MAX))
+        );
+        assert_eq!(
+            MssqlSmallMoney::from_decimal(max + Decimal::new(1, 4)),
+            None
+        );
+        assert_eq!(MssqlMoney::from_decimal(Decimal::new(1, 5)), None);
+        assert_eq!(
+            MssqlMoney::from_decimal(Decimal::new(-199, 2)),
+            Some(MssqlMoney(-19_900))
+        );
+        assert_eq!(MssqlMoney(-19_900).to_decimal(), Decimal::new(-199, 2));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::database::MssqlArgumentValue;
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::value::MssqlData;
+use crate::{Mssql, MssqlTypeInfo, MssqlValueRef};
+
+/// A SQL Server `ROWVERSION` (formerly `TIMESTAMP`) value.
+///
+/// The server assigns each row a new, database-wide increasing 8-byte value whenever it is
+/// inserted or updated, so comparing the value read with a row against the current one tells
+/// whether anybody changed the row in the meantime. The bytes are big-endian, so ordering
+/// `MssqlRowVersion`s (or their [`to_u64`][Self::to_u64] values) orders the changes.
+///
+/// Over TDS a `ROWVERSION` column is indistinguishable from `BINARY(8)`; decoding checks the
+/// value is exactly 8 bytes. It encodes as a binary parameter for use in a
+/// `WHERE version = @p1` check.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+/// use sqlx::mssql::MssqlRowVersion;
+///
+/// let (qty, version): (i32, MssqlRowVersion) =
+///     sqlx::query_as("SELECT qty, version FROM orders WHERE id = @p1")
+///         .bind(42i32)
+///         .fetch_one(&mut *conn)
+///         .await?;
+///
+/// let updated = sqlx::query(
+///     "UPDATE orders SET qty = @p1 OUTPUT INSERTED.version WHERE id = @p2 AND version = @p3",
+/// )
+/// .bind(qty + 1)
+/// .bind(42i32)
+/// .bind(version)
+/// .execute(&mut *conn)
+/// .await?;
+///
+/// if updated.rows_affected() == 0 {
+///     // somebody else updated the order first
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct MssqlRowVersion(pub [u8; 8]);
+
+impl MssqlRowVersion {
+    /// The value as the big-endian integer the server increments.
+    pub fn to_u64(self) -> u64 {
+        u64::from_be_bytes(self.0)
+    }
+
+    /// The row version with the big-endian bytes of `value`.
+    pub fn from_u64(value: u64) -> Self {
+        Self(value.to_be_bytes())
+    }
+}
+
+impl From<u64> for MssqlRowVersion {
+    fn from(value: u64) -> Self {
+        Self::from_u64(value)
+    }
+}
+
+impl From<MssqlRowVersion> for u64 {
+    fn from(version: MssqlRowVersion) -> Self {
+        version.to_u64()
+    }
+}
+
+impl AsRef<[u8]> for MssqlRowVersion {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Type<Mssql> for MssqlRowVersion {
+    fn type_info() -> MssqlTypeInfo {
+        MssqlTypeInfo::new("BINARY")
+    }
+
+    fn compatible(ty: &MssqlTypeInfo) -> bool {
+        // `@@DBTS` and `MIN_ACTIVE_ROWVERSION()` return `VARBINARY(8)`
+        matches!(ty.base_name(), "BINARY" | "VARBINARY")
+    }
+}
+
+impl Encode<'_, Mssql> for MssqlRowVersion {
+    fn encode_by_ref(&self, buf: &mut Vec<MssqlArgumentValue>) -> Result<IsNull, BoxDynError> {
+        buf.push(MssqlArgumentValue::Binary(self.0.to_vec()));
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Mssql> for MssqlRowVersion {
+    fn decode(value: MssqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.data {
+            MssqlData::Binary(bytes) => bytes.as_slice().try_into().map(Self).map_err(|_| {
+                format!("expected 8 bytes for ROWVERSION, got {} bytes", bytes.len()).into()
+            }),
+            MssqlData::Null => Err("unexpected NULL".into()),
+            _ => Err(format!("expected ROWVERSION, got {:?}", value.data).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(data: MssqlData) -> Result<MssqlRowVersion, BoxDynError> {
+        MssqlRowVersion::decode(MssqlValueRef {
+            data: &data,
+            type_info: MssqlTypeInfo::new("BINARY"),
+        })
+    }
+
+    #[test]
+    fn it_orders_like_the_big_endian_integer() {
+        let version = MssqlRowVersion([0, 0, 0, 0, 0, 0, 0x07, 0xd1]);
+        assert_eq!(version.to_u64(), 2001);
+        assert_eq!(MssqlRowVersion::from_u64(2001), version);
+
+        assert!(MssqlRowVersion::from(0x100) > MssqlRowVersion::from(0xff));
+        assert!(MssqlRowVersion::from(u64::MAX) > MssqlRowVersion::from(1 << 56));
+    }
+
+    #[test]
+    fn it_decodes_exactly_8_bytes() {
+        assert_eq!(
+            decode(MssqlData::Binary(vec![0, 0, 0, 0, 0, 0, 0, 1])).unwrap(),
+            MssqlRowVersion::from(1)
+        );
+
+        for len in [0, 7, 9] {
+            assert!(decode(MssqlData::Binary(vec![0; len])).is_err());
+        }
+        assert!(decode(MssqlData::String("12345678".into())).is_err());
+        assert!(decode(MssqlData::Null).is_err());
+    }
+}
@@ -5,7 +5,7 @@ use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::types::Type;
-use crate::value::MssqlData;
+use crate::value::{numeric_text, MssqlData};
 use crate::{Mssql, MssqlTypeInfo, MssqlValueRef};
 
 impl Type<Mssql> for Decimal {
@@ -32,6 +32,7 @@ impl Decode<'_, Mssql> for Decimal {
     fn decode(value: MssqlValueRef<'_>) -> Result<Self, BoxDynError> {
         match value.data {
             MssqlData::Decimal(v) => Ok(*v),
+            MssqlData::Numeric(v, scale) => decimal_from_numeric(*v, *scale),
             MssqlData::I32(v) => Ok(Decimal::from(*v)),
             MssqlData::I64(v) => Ok(Decimal::from(*v)),
             MssqlData::F64(v) => Decimal::try_from(*v)
@@ -44,3 +45,62 @@ impl Decode<'_, Mssql> for Decimal {
         }
     }
 }
+
+/// Convert a `NUMERIC` that didn't fit a `Decimal` as sent, which happens for `DECIMAL(38, x)`
+/// columns with more than 28 decimals or 96 bits of mantissa.
+///
+/// Trailing zeros of the fraction are dropped first, so e.g. `0.5` in a `DECIMAL(38, 30)`
+/// column still decodes; anything that would need rounding is an error instead.
+fn decimal_from_numeric(mut value: i128, mut scale: u8) -> Result<Decimal, BoxDynError> {
+    let text = numeric_text(value, scale);
+
+    while scale > 0 && value % 10 == 0 {
+        value /= 10;
+        scale -= 1;
+    }
+
+    Decimal::try_from_i128_with_scale(value, u32::from(scale)).map_err(|_| {
+        format!(
+            "NUMERIC value {text} exceeds rust_decimal precision \
+             (at most 28 decimals and 96 bits); CAST it to NVARCHAR to read it as text"
+        )
+        .into()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(data: MssqlData) -> Result<Decimal, BoxDynError> {
+        Decimal::decode(MssqlValueRef {
+            data: &data,
+            type_info: MssqlTypeInfo::new("DECIMAL(38,30)"),
+        })
+    }
+
+    #[test]
+    fn it_drops_trailing_zeros_beyond_rust_decimal_scale() {
+        let half = 5 * 10_i128.pow(29);
+        assert_eq!(
+            decode(MssqlData::Numeric(half, 30)).unwrap(),
+            Decimal::new(5, 1)
+        );
+        assert_eq!(
+            decode(MssqlData::Numeric(-half, 30)).unwrap(),
+            Decimal::new(-5, 1)
+        );
+    }
+
+    #[test]
+    fn it_rejects_numerics_beyond_rust_decimal_precision() {
+        let err = decode(MssqlData::Numeric(10_i128.pow(38) - 1, 0)).unwrap_err();
+        assert!(
+            err.to_string().contains("exceeds rust_decimal precision"),
+            "{err}"
+        );
+
+        // 0.000...001 needs all 30 decimals
+        assert!(decode(MssqlData::Numeric(1, 30)).is_err());
+    }
+}
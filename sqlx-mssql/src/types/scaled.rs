@@ -0,0 +1,130 @@
+use crate::database::MssqlArgumentValue;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{Mssql, MssqlTypeInfo};
+
+/// Fractional-second digits sent for `TIME`, `DATETIME2` and `DATETIMEOFFSET` parameters
+/// unless [`MssqlScaled`] asks for fewer: 100 ns, the most the types hold.
+pub(crate) const MAX_TIME_SCALE: u8 = 7;
+
+/// A `TIME`, `DATETIME2` or `DATETIMEOFFSET` parameter sent with fewer fractional-second
+/// digits than the default 7.
+///
+/// Temporal values are bound with 100 ns precision, and SQL Server rounds them when they are
+/// stored into a column of lower precision such as `DATETIME2(3)`. Wrapping the value sends
+/// it at the column's precision instead, which makes each value up to 2 bytes smaller on
+/// the wire, e.g. in large batches. Digits beyond the scale are dropped rather than
+/// rounded, so `12:00:00.1239` bound with scale 3 is stored as `12:00:00.123`; binding the
+/// unwrapped value into a `TIME(3)` column stores `12:00:00.124`.
+///
+/// The scale isn't detected from the target column; pass the column's scale, from 0
+/// (whole seconds) to 7. Encoding fails for a larger scale, or for a value that isn't a time
+/// of day, date and time, or date and time with offset, e.g. a date. The wrapper is encode
+/// only; decode the inner type directly.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use sqlx::mssql::{Mssql, MssqlConnection};
+/// # async fn example(
+/// #     conn: &mut MssqlConnection,
+/// #     created_at: impl for<'q> sqlx::Encode<'q, Mssql> + sqlx::Type<Mssql> + Send + 'static,
+/// # ) -> sqlx::Result<()> {
+/// use sqlx::mssql::MssqlScaled;
+///
+/// // created_at DATETIME2(3)
+/// sqlx::query("INSERT INTO events (created_at) VALUES (@p1)")
+///     .bind(MssqlScaled::new(created_at).with_scale(3))
+///     .execute(conn)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MssqlScaled<T> {
+    value: T,
+    scale: u8,
+}
+
+impl<T> MssqlScaled<T> {
+    /// Wrap `value`, keeping the default scale of 7 until [`with_scale`](Self::with_scale)
+    /// sets another.
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            scale: MAX_TIME_SCALE,
+        }
+    }
+
+    /// Send the value with `scale` fractional-second digits, from 0 to 7.
+    pub fn with_scale(mut self, scale: u8) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// The number of fractional-second digits the value is sent with.
+    pub fn scale(&self) -> u8 {
+        self.scale
+    }
+
+    /// Take the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: Type<Mssql>> Type<Mssql> for MssqlScaled<T> {
+    fn type_info() -> MssqlTypeInfo {
+        T::type_info()
+    }
+
+    fn compatible(ty: &MssqlTypeInfo) -> bool {
+        T::compatible(ty)
+    }
+}
+
+impl<'q, T: Encode<'q, Mssql>> Encode<'q, Mssql> for MssqlScaled<T> {
+    fn encode_by_ref(&self, buf: &mut Vec<MssqlArgumentValue>) -> Result<IsNull, BoxDynError> {
+        if self.scale > MAX_TIME_SCALE {
+            return Err(format!(
+                "fractional-second scale {} is out of range (must be 0..={MAX_TIME_SCALE})",
+                self.scale
+            )
+            .into());
+        }
+
+        let mut inner = Vec::new();
+        if self.value.encode_by_ref(&mut inner)?.is_null() {
+            return Ok(IsNull::Yes);
+        }
+
+        let value = match inner.pop() {
+            Some(value) if inner.is_empty() && has_time_of_day(&value) => value,
+            value => {
+                return Err(format!(
+                    "cannot set the fractional-second scale of {value:?}; \
+                     only TIME, DATETIME2 and DATETIMEOFFSET values have one"
+                )
+                .into())
+            }
+        };
+
+        buf.push(MssqlArgumentValue::Scaled(Box::new(value), self.scale));
+        Ok(IsNull::No)
+    }
+}
+
+fn has_time_of_day(value: &MssqlArgumentValue) -> bool {
+    match value {
+        #[cfg(feature = "chrono")]
+        MssqlArgumentValue::NaiveTime(_)
+        | MssqlArgumentValue::NaiveDateTime(_)
+        | MssqlArgumentValue::DateTimeFixedOffset(_) => true,
+        #[cfg(feature = "time")]
+        MssqlArgumentValue::TimeTime(_)
+        | MssqlArgumentValue::TimePrimitiveDateTime(_)
+        | MssqlArgumentValue::TimeOffsetDateTime(_) => true,
+        _ => false,
+    }
+}
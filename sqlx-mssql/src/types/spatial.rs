@@ -0,0 +1,95 @@
+use crate::database::MssqlArgumentValue;
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::value::MssqlData;
+use crate::{Mssql, MssqlTypeInfo, MssqlValueRef};
+
+/// A `GEOGRAPHY` or `GEOMETRY` value as Well-Known Binary (WKB).
+///
+/// SQL Server sends spatial columns as CLR user-defined types in a proprietary format, which
+/// the TDS layer cannot read. Select them through `STAsBinary()` (or `AsBinaryZM()` to keep
+/// Z and M values) instead, which returns standard WKB that crates such as `wkb` or `geozero`
+/// can parse. The SRID isn't part of WKB; select `STSrid` alongside if you need it.
+///
+/// Bound as a `VARBINARY` parameter, so writing one back goes through
+/// `geography::STGeomFromWKB(@p1, <srid>)` or `geometry::STGeomFromWKB(@p1, <srid>)`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+/// use sqlx::mssql::MssqlSpatial;
+///
+/// let location: MssqlSpatial =
+///     sqlx::query_scalar("SELECT location.STAsBinary() FROM stores WHERE id = @p1")
+///         .bind(7i32)
+///         .fetch_one(&mut *conn)
+///         .await?;
+///
+/// sqlx::query("UPDATE stores SET location = geography::STGeomFromWKB(@p1, 4326) WHERE id = @p2")
+///     .bind(&location)
+///     .bind(8i32)
+///     .execute(&mut *conn)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MssqlSpatial(pub Vec<u8>);
+
+impl MssqlSpatial {
+    /// The Well-Known Binary bytes.
+    pub fn as_wkb(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Take the Well-Known Binary bytes.
+    pub fn into_wkb(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl From<Vec<u8>> for MssqlSpatial {
+    fn from(wkb: Vec<u8>) -> Self {
+        MssqlSpatial(wkb)
+    }
+}
+
+impl AsRef<[u8]> for MssqlSpatial {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Type<Mssql> for MssqlSpatial {
+    fn type_info() -> MssqlTypeInfo {
+        MssqlTypeInfo::new("VARBINARY")
+    }
+
+    fn compatible(ty: &MssqlTypeInfo) -> bool {
+        matches!(ty.base_name(), "VARBINARY" | "BINARY" | "IMAGE")
+    }
+}
+
+impl Encode<'_, Mssql> for MssqlSpatial {
+    fn encode_by_ref(&self, buf: &mut Vec<MssqlArgumentValue>) -> Result<IsNull, BoxDynError> {
+        buf.push(MssqlArgumentValue::Binary(self.0.clone()));
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Mssql> for MssqlSpatial {
+    fn decode(value: MssqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.data {
+            MssqlData::Binary(wkb) => Ok(MssqlSpatial(wkb.clone())),
+            MssqlData::Null => Err("unexpected NULL".into()),
+            _ => Err(format!(
+                "expected Well-Known Binary from STAsBinary(), got {:?}",
+                value.data
+            )
+            .into()),
+        }
+    }
+}
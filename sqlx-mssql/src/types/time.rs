@@ -1,4 +1,4 @@
-use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+use time::{Date, Duration, OffsetDateTime, PrimitiveDateTime, Time};
 
 use crate::database::MssqlArgumentValue;
 use crate::decode::Decode;
@@ -126,3 +126,82 @@ impl Decode<'_, Mssql> for OffsetDateTime {
         }
     }
 }
+
+// ── Duration ───────────────────────────────────────────────────────────────
+
+/// A `Duration` is stored as the `TIME` that far past midnight, so it must be at least zero
+/// and less than 24 hours. Longer spans can be stored as `BIGINT` nanoseconds with
+/// [`MssqlDurationNanos`][crate::MssqlDurationNanos].
+impl Type<Mssql> for Duration {
+    fn type_info() -> MssqlTypeInfo {
+        MssqlTypeInfo::new("TIME")
+    }
+
+    fn compatible(ty: &MssqlTypeInfo) -> bool {
+        ty.base_name() == "TIME"
+    }
+}
+
+impl Encode<'_, Mssql> for Duration {
+    fn encode_by_ref(&self, buf: &mut Vec<MssqlArgumentValue>) -> Result<IsNull, BoxDynError> {
+        if self.is_negative() || *self >= Duration::DAY {
+            return Err(format!(
+                "duration {self} is out of range for TIME (must be at least 0 and less than 24 \
+                 hours); bind it as MssqlDurationNanos to store it as BIGINT nanoseconds"
+            )
+            .into());
+        }
+
+        buf.push(MssqlArgumentValue::TimeTime(Time::MIDNIGHT + *self));
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Mssql> for Duration {
+    fn decode(value: MssqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.data {
+            MssqlData::TimeTime(v) => Ok(*v - Time::MIDNIGHT),
+            MssqlData::Null => Err("unexpected NULL".into()),
+            _ => Err(format!("expected time, got {:?}", value.data).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_round_trips_durations_within_a_day() {
+        for duration in [
+            Duration::ZERO,
+            Duration::new(3_723, 400_000_000),
+            Duration::DAY - Duration::nanoseconds(100),
+        ] {
+            let mut buf = Vec::new();
+            let _ = duration.encode_by_ref(&mut buf).unwrap();
+
+            let data = match buf.pop() {
+                Some(MssqlArgumentValue::TimeTime(v)) => MssqlData::TimeTime(v),
+                other => panic!("unexpected argument {other:?}"),
+            };
+            let decoded = Duration::decode(MssqlValueRef {
+                data: &data,
+                type_info: MssqlTypeInfo::new("TIME"),
+            })
+            .unwrap();
+
+            assert_eq!(decoded, duration);
+        }
+    }
+
+    #[test]
+    fn it_rejects_durations_outside_a_day() {
+        for duration in [Duration::DAY, Duration::days(3), Duration::nanoseconds(-1)] {
+            let Err(err) = duration.encode_by_ref(&mut Vec::new()) else {
+                panic!("{duration} encoded");
+            };
+            assert!(err.to_string().contains("out of range for TIME"), "{err}");
+        }
+    }
+}
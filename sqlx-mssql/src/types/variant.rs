@@ -0,0 +1,236 @@
+use crate::database::MssqlArgumentValue;
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::value::MssqlData;
+use crate::{Mssql, MssqlTypeInfo, MssqlValueRef};
+
+/// The longest string tiberius binds as `NVARCHAR(4000)` rather than `NVARCHAR(MAX)`, in
+/// UTF-8 bytes.
+const MAX_STRING_BYTES: usize = 4000;
+
+/// The longest binary value tiberius binds as `VARBINARY(8000)` rather than `VARBINARY(MAX)`.
+const MAX_BINARY_BYTES: usize = 8000;
+
+/// A value tagged with its SQL Server base type, for storing into a `SQL_VARIANT` column.
+///
+/// A `SQL_VARIANT` keeps the base type of each value written to it, so one column can hold,
+/// say, an `INT` in one row and an `NVARCHAR` in the next, and
+/// `SQL_VARIANT_PROPERTY(v, 'BaseType')` reports which is which. Each variant is bound as a
+/// parameter of its base type, reported by [`base_type`][Self::base_type].
+///
+/// `SQL_VARIANT` cannot hold `MAX` types, `XML`, `TEXT`/`NTEXT`/`IMAGE`, `TIMESTAMP` or
+/// user-defined types. Since strings longer than 4000 UTF-8 bytes and binary values longer
+/// than 8000 bytes are sent as `NVARCHAR(MAX)` and `VARBINARY(MAX)`, encoding them fails.
+///
+/// The driver cannot read a `SQL_VARIANT` column itself, so select it with a `CAST` to its
+/// base type; decoding picks the variant from the type of the column that comes back.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+/// use sqlx::mssql::MssqlVariant;
+///
+/// for value in [MssqlVariant::Int(42), MssqlVariant::NVarChar("hello".into())] {
+///     sqlx::query("INSERT INTO settings (value) VALUES (@p1)")
+///         .bind(value)
+///         .execute(&mut *conn)
+///         .await?;
+/// }
+///
+/// let ints: Vec<MssqlVariant> = sqlx::query_scalar(
+///     "SELECT CAST(value AS INT) FROM settings \
+///      WHERE SQL_VARIANT_PROPERTY(value, 'BaseType') = 'int'",
+/// )
+/// .fetch_all(&mut *conn)
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum MssqlVariant {
+    Bit(bool),
+    TinyInt(u8),
+    SmallInt(i16),
+    Int(i32),
+    BigInt(i64),
+    Real(f32),
+    Float(f64),
+    /// At most 4000 UTF-8 bytes.
+    NVarChar(String),
+    /// At most 8000 bytes.
+    VarBinary(Vec<u8>),
+    #[cfg(feature = "uuid")]
+    UniqueIdentifier(uuid::Uuid),
+    /// Stored as `NUMERIC` with the precision and scale of the value.
+    #[cfg(feature = "rust_decimal")]
+    Decimal(rust_decimal::Decimal),
+    #[cfg(feature = "chrono")]
+    Date(chrono::NaiveDate),
+    #[cfg(feature = "chrono")]
+    DateTime2(chrono::NaiveDateTime),
+}
+
+impl MssqlVariant {
+    /// The base type the value is stored with, as reported by
+    /// `SQL_VARIANT_PROPERTY(value, 'BaseType')`.
+    pub fn base_type(&self) -> &'static str {
+        match self {
+            Self::Bit(_) => "bit",
+            Self::TinyInt(_) => "tinyint",
+            Self::SmallInt(_) => "smallint",
+            Self::Int(_) => "int",
+            Self::BigInt(_) => "bigint",
+            Self::Real(_) => "real",
+            Self::Float(_) => "float",
+            Self::NVarChar(_) => "nvarchar",
+            Self::VarBinary(_) => "varbinary",
+            #[cfg(feature = "uuid")]
+            Self::UniqueIdentifier(_) => "uniqueidentifier",
+            #[cfg(feature = "rust_decimal")]
+            Self::Decimal(_) => "numeric",
+            #[cfg(feature = "chrono")]
+            Self::Date(_) => "date",
+            #[cfg(feature = "chrono")]
+            Self::DateTime2(_) => "datetime2",
+        }
+    }
+}
+
+impl Type<Mssql> for MssqlVariant {
+    fn type_info() -> MssqlTypeInfo {
+        MssqlTypeInfo::new("SQL_VARIANT")
+    }
+
+    fn compatible(ty: &MssqlTypeInfo) -> bool {
+        matches!(
+            ty.base_name(),
+            "SQL_VARIANT"
+                | "BIT"
+                | "TINYINT"
+                | "SMALLINT"
+                | "INT"
+                | "BIGINT"
+                | "REAL"
+                | "FLOAT"
+                | "NVARCHAR"
+                | "NCHAR"
+                | "VARCHAR"
+                | "CHAR"
+                | "VARBINARY"
+                | "BINARY"
+                | "UNIQUEIDENTIFIER"
+                | "DECIMAL"
+                | "NUMERIC"
+                | "DATE"
+                | "DATETIME2"
+                | "DATETIME"
+                | "SMALLDATETIME"
+        )
+    }
+}
+
+impl Encode<'_, Mssql> for MssqlVariant {
+    fn encode_by_ref(&self, buf: &mut Vec<MssqlArgumentValue>) -> Result<IsNull, BoxDynError> {
+        let value = match self {
+            Self::Bit(v) => MssqlArgumentValue::Bool(*v),
+            Self::TinyInt(v) => MssqlArgumentValue::U8(*v),
+            Self::SmallInt(v) => MssqlArgumentValue::I16(*v),
+            Self::Int(v) => MssqlArgumentValue::I32(*v),
+            Self::BigInt(v) => MssqlArgumentValue::I64(*v),
+            Self::Real(v) => MssqlArgumentValue::F32(*v),
+            Self::Float(v) => MssqlArgumentValue::F64(*v),
+            Self::NVarChar(v) => {
+                if v.len() > MAX_STRING_BYTES {
+                    return Err(format!(
+                        "SQL_VARIANT cannot hold NVARCHAR(MAX); the string is {} bytes, \
+                         more than {MAX_STRING_BYTES}",
+                        v.len()
+                    )
+                    .into());
+                }
+                MssqlArgumentValue::String(v.clone())
+            }
+            Self::VarBinary(v) => {
+                if v.len() > MAX_BINARY_BYTES {
+                    return Err(format!(
+                        "SQL_VARIANT cannot hold VARBINARY(MAX); the value is {} bytes, \
+                         more than {MAX_BINARY_BYTES}",
+                        v.len()
+                    )
+                    .into());
+                }
+                MssqlArgumentValue::Binary(v.clone())
+            }
+            #[cfg(feature = "uuid")]
+            Self::UniqueIdentifier(v) => MssqlArgumentValue::Uuid(*v),
+            #[cfg(feature = "rust_decimal")]
+            Self::Decimal(v) => MssqlArgumentValue::Decimal(*v),
+            #[cfg(feature = "chrono")]
+            Self::Date(v) => MssqlArgumentValue::NaiveDate(*v),
+            #[cfg(feature = "chrono")]
+            Self::DateTime2(v) => MssqlArgumentValue::NaiveDateTime(*v),
+        };
+
+        buf.push(value);
+        Ok(IsNull::No)
+    }
+}
+
+impl Decode<'_, Mssql> for MssqlVariant {
+    fn decode(value: MssqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        Ok(match value.data {
+            MssqlData::Bool(v) => Self::Bit(*v),
+            MssqlData::U8(v) => Self::TinyInt(*v),
+            MssqlData::I16(v) => Self::SmallInt(*v),
+            MssqlData::I32(v) => Self::Int(*v),
+            MssqlData::I64(v) => Self::BigInt(*v),
+            MssqlData::F32(v) => Self::Real(*v),
+            MssqlData::F64(v) => Self::Float(*v),
+            MssqlData::String(v) => Self::NVarChar(v.clone()),
+            MssqlData::Binary(v) => Self::VarBinary(v.clone()),
+            #[cfg(feature = "uuid")]
+            MssqlData::Uuid(v) => Self::UniqueIdentifier(*v),
+            #[cfg(feature = "rust_decimal")]
+            MssqlData::Decimal(v) => Self::Decimal(*v),
+            #[cfg(feature = "chrono")]
+            MssqlData::NaiveDate(v) => Self::Date(*v),
+            #[cfg(feature = "chrono")]
+            MssqlData::NaiveDateTime(v) => Self::DateTime2(*v),
+            MssqlData::Null => return Err("unexpected NULL".into()),
+            _ => {
+                return Err(format!(
+                    "{} {:?} has no MssqlVariant equivalent",
+                    value.type_info, value.data
+                )
+                .into())
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(value: MssqlVariant) -> Result<Vec<MssqlArgumentValue>, BoxDynError> {
+        let mut buf = Vec::new();
+        let _ = value.encode_by_ref(&mut buf)?;
+        Ok(buf)
+    }
+
+    #[test]
+    fn it_rejects_values_needing_max_types() {
+        assert!(encode(MssqlVariant::NVarChar("x".repeat(4000))).is_ok());
+        let err = encode(MssqlVariant::NVarChar("x".repeat(4001))).unwrap_err();
+        assert!(err.to_string().contains("NVARCHAR(MAX)"), "{err}");
+
+        // 2000 characters, but 4002 UTF-8 bytes
+        assert!(encode(MssqlVariant::NVarChar(format!("{}a", "é".repeat(2000)))).is_err());
+
+        assert!(encode(MssqlVariant::VarBinary(vec![0; 8000])).is_ok());
+        assert!(encode(MssqlVariant::VarBinary(vec![0; 8001])).is_err());
+    }
+}
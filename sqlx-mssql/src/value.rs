@@ -1,4 +1,8 @@
 use std::borrow::Cow;
+use std::fmt::Write as _;
+
+use futures_io::AsyncWrite;
+use futures_util::AsyncWriteExt;
 
 pub(crate) use sqlx_core::value::*;
 
@@ -40,6 +44,121 @@ pub(crate) enum MssqlData {
     TimeOffsetDateTime(time::OffsetDateTime),
     #[cfg(all(feature = "bigdecimal", not(feature = "rust_decimal")))]
     BigDecimal(bigdecimal::BigDecimal),
+    /// A `DECIMAL`/`NUMERIC` value as its unscaled value and scale, kept when no enabled
+    /// decimal type can hold it: always without a decimal feature, and for values beyond
+    /// the 28 digits of `rust_decimal`. `bigdecimal` holds any of them.
+    #[cfg(any(feature = "rust_decimal", not(feature = "bigdecimal")))]
+    Numeric(i128, u8),
+}
+
+impl MssqlData {
+    /// Format the value as text, or `None` if it is `NULL`.
+    ///
+    /// Used for lossy "everything as a string" reads: numbers use Rust's `Display`
+    /// (decimals keep their scale), `BIT` is `1`/`0` as in `CAST(... AS NVARCHAR)`, binary
+    /// is `0x`-prefixed uppercase hex and date/time values are ISO 8601 with as many
+    /// fractional digits (0, 3, 6 or 9) as needed.
+    pub(crate) fn to_text(&self) -> Option<String> {
+        let text = match self {
+            MssqlData::Null => return None,
+            MssqlData::Bool(v) => u8::from(*v).to_string(),
+            MssqlData::U8(v) => v.to_string(),
+            MssqlData::I16(v) => v.to_string(),
+            MssqlData::I32(v) => v.to_string(),
+            MssqlData::I64(v) => v.to_string(),
+            MssqlData::F32(v) => v.to_string(),
+            MssqlData::F64(v) => v.to_string(),
+            MssqlData::String(v) => v.clone(),
+            MssqlData::Binary(v) => {
+                let mut hex = String::with_capacity(2 + v.len() * 2);
+                hex.push_str("0x");
+                for byte in v {
+                    let _ = write!(hex, "{byte:02X}");
+                }
+                hex
+            }
+            #[cfg(feature = "chrono")]
+            MssqlData::NaiveDateTime(v) => v.format("%Y-%m-%dT%H:%M:%S%.f").to_string(),
+            #[cfg(feature = "chrono")]
+            MssqlData::NaiveDate(v) => v.format("%Y-%m-%d").to_string(),
+            #[cfg(feature = "chrono")]
+            MssqlData::NaiveTime(v) => v.format("%H:%M:%S%.f").to_string(),
+            #[cfg(feature = "chrono")]
+            MssqlData::DateTimeFixedOffset(v) => v.format("%Y-%m-%dT%H:%M:%S%.f%:z").to_string(),
+            #[cfg(feature = "uuid")]
+            MssqlData::Uuid(v) => v.to_string(),
+            #[cfg(feature = "rust_decimal")]
+            MssqlData::Decimal(v) => v.to_string(),
+            #[cfg(all(feature = "time", not(feature = "chrono")))]
+            MssqlData::TimeDate(v) => time_date_text(*v),
+            #[cfg(all(feature = "time", not(feature = "chrono")))]
+            MssqlData::TimeTime(v) => time_time_text(*v),
+            #[cfg(all(feature = "time", not(feature = "chrono")))]
+            MssqlData::TimePrimitiveDateTime(v) => {
+                format!("{}T{}", time_date_text(v.date()), time_time_text(v.time()))
+            }
+            #[cfg(all(feature = "time", not(feature = "chrono")))]
+            MssqlData::TimeOffsetDateTime(v) => {
+                let (hours, minutes, _) = v.offset().as_hms();
+                let sign = if v.offset().is_negative() { '-' } else { '+' };
+                format!(
+                    "{}T{}{sign}{:02}:{:02}",
+                    time_date_text(v.date()),
+                    time_time_text(v.time()),
+                    hours.unsigned_abs(),
+                    minutes.unsigned_abs()
+                )
+            }
+            #[cfg(all(feature = "bigdecimal", not(feature = "rust_decimal")))]
+            MssqlData::BigDecimal(v) => v.to_plain_string(),
+            #[cfg(any(feature = "rust_decimal", not(feature = "bigdecimal")))]
+            MssqlData::Numeric(value, scale) => numeric_text(*value, *scale),
+        };
+
+        Some(text)
+    }
+}
+
+/// Format an unscaled `NUMERIC` value with `scale` fractional digits, e.g. `-1250, 2` as
+/// `-12.50`.
+#[cfg(any(feature = "rust_decimal", not(feature = "bigdecimal")))]
+pub(crate) fn numeric_text(value: i128, scale: u8) -> String {
+    let sign = if value < 0 { "-" } else { "" };
+    let scale = usize::from(scale);
+    let digits = format!("{:0>width$}", value.unsigned_abs(), width = scale + 1);
+    let (int, fraction) = digits.split_at(digits.len() - scale);
+
+    if fraction.is_empty() {
+        format!("{sign}{int}")
+    } else {
+        format!("{sign}{int}.{fraction}")
+    }
+}
+
+#[cfg(all(feature = "time", not(feature = "chrono")))]
+fn time_date_text(date: time::Date) -> String {
+    format!(
+        "{:04}-{:02}-{:02}",
+        date.year(),
+        u8::from(date.month()),
+        date.day()
+    )
+}
+
+/// Format a `time::Time` like chrono's `%H:%M:%S%.f`.
+#[cfg(all(feature = "time", not(feature = "chrono")))]
+fn time_time_text(time: time::Time) -> String {
+    let (h, m, s, ns) = time.as_hms_nano();
+    let fraction = if ns == 0 {
+        String::new()
+    } else if ns % 1_000_000 == 0 {
+        format!(".{:03}", ns / 1_000_000)
+    } else if ns % 1_000 == 0 {
+        format!(".{:06}", ns / 1_000)
+    } else {
+        format!(".{ns:09}")
+    };
+    format!("{h:02}:{m:02}:{s:02}{fraction}")
 }
 
 /// Implementation of [`Value`] for MSSQL.
@@ -75,6 +194,96 @@ impl<'r> MssqlValueRef<'r> {
     }
 }
 
+impl MssqlValueRef<'_> {
+    /// Describe the value for debugging: the column's SQL type followed by the Rust
+    /// representation the driver decoded it into, e.g. `INT I32(42)`,
+    /// `DECIMAL(10,2) Decimal(12.50)` or `NVARCHAR Null`.
+    ///
+    /// Useful to see why a decode failed, e.g. a `BIGINT` column read into an `i32`.
+    /// Strings longer than 64 characters and binary values longer than 32 bytes are
+    /// shortened. The format is meant for humans and may change.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// use sqlx::Row;
+    ///
+    /// let row = sqlx::query("SELECT COUNT_BIG(*) FROM users")
+    ///     .fetch_one(&mut *conn)
+    ///     .await?;
+    ///
+    /// if let Err(e) = row.try_get::<i32, _>(0) {
+    ///     eprintln!("{e}: {}", row.try_get_raw(0)?.debug_repr());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn debug_repr(&self) -> String {
+        const MAX_CHARS: usize = 64;
+        const MAX_BYTES: usize = 32;
+
+        let repr = match self.data {
+            MssqlData::String(s) if s.chars().count() > MAX_CHARS => {
+                let head: String = s.chars().take(MAX_CHARS).collect();
+                format!("String({head:?}... {} chars)", s.chars().count())
+            }
+            MssqlData::Binary(bytes) => {
+                let mut hex = String::with_capacity(2 + 2 * MAX_BYTES + 3);
+                hex.push_str("0x");
+                for byte in bytes.iter().take(MAX_BYTES) {
+                    let _ = write!(hex, "{byte:02X}");
+                }
+                if bytes.len() > MAX_BYTES {
+                    hex.push_str("...");
+                }
+                format!("Binary({hex}, {} bytes)", bytes.len())
+            }
+            data => format!("{data:?}"),
+        };
+
+        format!("{} {repr}", self.type_info)
+    }
+
+    /// Write the contents of a binary or string value to `writer`, returning the number of
+    /// bytes written.
+    ///
+    /// Intended for large `VARBINARY(MAX)` / `NVARCHAR(MAX)` values, e.g. to send a file
+    /// download straight to a socket without first decoding it into a `Vec<u8>` or `String`.
+    /// Strings are written as UTF-8.
+    ///
+    /// ### Note
+    /// The value has already been read into memory with the rest of the row; this only
+    /// avoids a second copy at the call site.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::mssql::MssqlConnection) -> sqlx::Result<()> {
+    /// use sqlx::Row;
+    ///
+    /// let row = sqlx::query("SELECT content FROM files WHERE id = 1")
+    ///     .fetch_one(&mut *conn)
+    ///     .await?;
+    ///
+    /// let mut out = Vec::new();
+    /// let written = row.try_get_raw("content")?.copy_to(&mut out).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy_to<W>(&self, writer: &mut W) -> Result<u64, Error>
+    where
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        let bytes = self.as_bytes().map_err(Error::Decode)?;
+
+        writer.write_all(bytes).await?;
+        writer.flush().await?;
+
+        Ok(bytes.len() as u64)
+    }
+}
+
 impl Value for MssqlValue {
     type Database = Mssql;
 
@@ -134,13 +343,7 @@ pub(crate) fn column_data_to_mssql_data(
         #[cfg(feature = "chrono")]
         tiberius::ColumnData::DateTime2(Some(dt2)) => {
             let date = chrono_date_from_days(dt2.date().days() as i64, 1)?;
-            // SAFETY: TDS time increments at scale 7 max out at 863_999_999_999, well within i64.
-            let t = dt2.time();
-            #[allow(clippy::cast_possible_wrap)]
-            let ns = t.increments() as i64 * 10i64.pow(9u32.saturating_sub(t.scale() as u32));
-            // infallible: (0,0,0) is always valid
-            let time = chrono::NaiveTime::from_hms_opt(0, 0, 0).unwrap()
-                + chrono::Duration::nanoseconds(ns);
+            let time = chrono_time_from_nanoseconds(time_nanoseconds(dt2.time())?)?;
             Ok(MssqlData::NaiveDateTime(chrono::NaiveDateTime::new(
                 date, time,
             )))
@@ -148,10 +351,8 @@ pub(crate) fn column_data_to_mssql_data(
         #[cfg(feature = "chrono")]
         tiberius::ColumnData::DateTime(Some(dt)) => {
             let date = chrono_date_from_days(dt.days() as i64, 1900)?;
-            let ns = dt.seconds_fragments() as i64 * 1_000_000_000i64 / 300;
-            // infallible: (0,0,0) is always valid
-            let time = chrono::NaiveTime::from_hms_opt(0, 0, 0).unwrap()
-                + chrono::Duration::nanoseconds(ns);
+            let ns = u64::from(dt.seconds_fragments()) * 1_000_000_000u64 / 300;
+            let time = chrono_time_from_nanoseconds(ns)?;
             Ok(MssqlData::NaiveDateTime(chrono::NaiveDateTime::new(
                 date, time,
             )))
@@ -176,25 +377,13 @@ pub(crate) fn column_data_to_mssql_data(
             1,
         )?)),
         #[cfg(feature = "chrono")]
-        tiberius::ColumnData::Time(Some(t)) => {
-            // SAFETY: TDS time increments at scale 7 max out at 863_999_999_999, well within i64.
-            #[allow(clippy::cast_possible_wrap)]
-            let ns = t.increments() as i64 * 10i64.pow(9u32.saturating_sub(t.scale() as u32));
-            // infallible: (0,0,0) is always valid
-            let time = chrono::NaiveTime::from_hms_opt(0, 0, 0).unwrap()
-                + chrono::Duration::nanoseconds(ns);
-            Ok(MssqlData::NaiveTime(time))
-        }
+        tiberius::ColumnData::Time(Some(t)) => Ok(MssqlData::NaiveTime(
+            chrono_time_from_nanoseconds(time_nanoseconds(t)?)?,
+        )),
         #[cfg(feature = "chrono")]
         tiberius::ColumnData::DateTimeOffset(Some(dto)) => {
             let date = chrono_date_from_days(dto.datetime2().date().days() as i64, 1)?;
-            // SAFETY: TDS time increments at scale 7 max out at 863_999_999_999, well within i64.
-            let t = dto.datetime2().time();
-            #[allow(clippy::cast_possible_wrap)]
-            let ns = t.increments() as i64 * 10i64.pow(9u32.saturating_sub(t.scale() as u32));
-            // infallible: (0,0,0) is always valid
-            let time = chrono::NaiveTime::from_hms_opt(0, 0, 0).unwrap()
-                + chrono::Duration::nanoseconds(ns);
+            let time = chrono_time_from_nanoseconds(time_nanoseconds(dto.datetime2().time())?)?;
             let naive = chrono::NaiveDateTime::new(date, time);
             let offset_secs = dto.offset() as i32 * 60;
             let fixed_offset = chrono::FixedOffset::east_opt(offset_secs).ok_or_else(|| {
@@ -215,9 +404,12 @@ pub(crate) fn column_data_to_mssql_data(
         tiberius::ColumnData::Guid(Some(v)) => Ok(MssqlData::Uuid(v)),
 
         #[cfg(feature = "rust_decimal")]
-        tiberius::ColumnData::Numeric(Some(n)) => Ok(MssqlData::Decimal(
-            rust_decimal::Decimal::from_i128_with_scale(n.value(), n.scale() as u32),
-        )),
+        tiberius::ColumnData::Numeric(Some(n)) => Ok(
+            rust_decimal::Decimal::try_from_i128_with_scale(n.value(), u32::from(n.scale()))
+                .map_or(MssqlData::Numeric(n.value(), n.scale()), MssqlData::Decimal),
+        ),
+        #[cfg(not(any(feature = "rust_decimal", feature = "bigdecimal")))]
+        tiberius::ColumnData::Numeric(Some(n)) => Ok(MssqlData::Numeric(n.value(), n.scale())),
 
         #[cfg(all(feature = "time", not(feature = "chrono")))]
         tiberius::ColumnData::Date(Some(d)) => Ok(MssqlData::TimeDate(time_date_from_days(
@@ -225,16 +417,13 @@ pub(crate) fn column_data_to_mssql_data(
             1,
         )?)),
         #[cfg(all(feature = "time", not(feature = "chrono")))]
-        tiberius::ColumnData::Time(Some(t)) => {
-            let ns = t.increments() * 10u64.pow(9u32.saturating_sub(t.scale() as u32));
-            Ok(MssqlData::TimeTime(time_from_sec_fragments(ns)?))
-        }
+        tiberius::ColumnData::Time(Some(t)) => Ok(MssqlData::TimeTime(time_from_sec_fragments(
+            time_nanoseconds(t)?,
+        )?)),
         #[cfg(all(feature = "time", not(feature = "chrono")))]
         tiberius::ColumnData::DateTime2(Some(dt2)) => {
             let date = time_date_from_days(i64::from(dt2.date().days()), 1)?;
-            let t = dt2.time();
-            let ns = t.increments() * 10u64.pow(9u32.saturating_sub(t.scale() as u32));
-            let time = time_from_sec_fragments(ns)?;
+            let time = time_from_sec_fragments(time_nanoseconds(dt2.time())?)?;
             Ok(MssqlData::TimePrimitiveDateTime(
                 time::PrimitiveDateTime::new(date, time),
             ))
@@ -260,9 +449,7 @@ pub(crate) fn column_data_to_mssql_data(
         #[cfg(all(feature = "time", not(feature = "chrono")))]
         tiberius::ColumnData::DateTimeOffset(Some(dto)) => {
             let date = time_date_from_days(i64::from(dto.datetime2().date().days()), 1)?;
-            let t = dto.datetime2().time();
-            let ns = t.increments() * 10u64.pow(9u32.saturating_sub(t.scale() as u32));
-            let time = time_from_sec_fragments(ns)?;
+            let time = time_from_sec_fragments(time_nanoseconds(dto.datetime2().time())?)?;
             let naive = time::PrimitiveDateTime::new(date, time);
             let offset_secs = dto.offset() as i32 * 60;
             let offset = time::UtcOffset::from_whole_seconds(offset_secs).map_err(|_| {
@@ -336,10 +523,52 @@ fn time_date_from_days(days: i64, start_year: i32) -> Result<time::Date, Error>
         })
 }
 
+/// Nanoseconds in a day; `TIME` values are always less.
+#[cfg(any(feature = "chrono", feature = "time"))]
+const NANOS_PER_DAY: u64 = 86_400_000_000_000;
+
+/// Convert a TDS time of day (increments of `10^-scale` seconds) to nanoseconds since
+/// midnight.
+///
+/// SQL Server never sends a time of 24 hours or more, so such a value, or one whose scale
+/// is out of range, is reported as a protocol error rather than wrapped or panicked on.
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn time_nanoseconds(time: tiberius::time::Time) -> Result<u64, Error> {
+    let nanoseconds = 9u32
+        .checked_sub(u32::from(time.scale()))
+        .and_then(|exponent| time.increments().checked_mul(10u64.pow(exponent)))
+        .filter(|nanoseconds| *nanoseconds < NANOS_PER_DAY);
+
+    nanoseconds.ok_or_else(|| {
+        Error::Protocol(format!(
+            "time out of range: {} increments at scale {} is not within a day",
+            time.increments(),
+            time.scale()
+        ))
+    })
+}
+
+/// Convert nanoseconds-since-midnight to a `chrono::NaiveTime`.
+#[cfg(feature = "chrono")]
+fn chrono_time_from_nanoseconds(nanoseconds: u64) -> Result<chrono::NaiveTime, Error> {
+    let seconds = u32::try_from(nanoseconds / 1_000_000_000).ok();
+    // always < 1_000_000_000, so it fits
+    #[allow(clippy::cast_possible_truncation)]
+    let nanos = (nanoseconds % 1_000_000_000) as u32;
+
+    seconds
+        .filter(|_| nanoseconds < NANOS_PER_DAY)
+        .and_then(|seconds| chrono::NaiveTime::from_num_seconds_from_midnight_opt(seconds, nanos))
+        .ok_or_else(|| {
+            Error::Protocol(format!(
+                "time nanoseconds out of range: {nanoseconds} (must be < {NANOS_PER_DAY})"
+            ))
+        })
+}
+
 /// Convert nanoseconds-since-midnight to a `time::Time`.
 #[cfg(all(feature = "time", not(feature = "chrono")))]
 fn time_from_sec_fragments(nanoseconds: u64) -> Result<time::Time, Error> {
-    const NANOS_PER_DAY: u64 = 86_400_000_000_000;
     if nanoseconds >= NANOS_PER_DAY {
         return Err(Error::Protocol(format!(
             "time nanoseconds out of range: {nanoseconds} (must be < {NANOS_PER_DAY})"
@@ -377,3 +606,194 @@ fn chrono_date_from_days(days: i64, start_year: i32) -> Result<chrono::NaiveDate
             ))
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    #[test]
+    fn it_rejects_times_outside_a_day() {
+        use tiberius::time::{Date, DateTime2, Time};
+
+        // 24:00:00 at scale 7, the first value past the end of the day
+        let day = Time::new(864_000_000_000, 7);
+        let out_of_range = [
+            tiberius::ColumnData::Time(Some(day)),
+            tiberius::ColumnData::Time(Some(Time::new(u64::MAX, 0))),
+            tiberius::ColumnData::Time(Some(Time::new(1, 12))),
+            tiberius::ColumnData::DateTime2(Some(DateTime2::new(Date::new(0), day))),
+        ];
+
+        for data in out_of_range {
+            let err = column_data_to_mssql_data(data).unwrap_err();
+            assert!(matches!(err, Error::Protocol(_)), "{err}");
+        }
+
+        // 23:59:59.9999999 is the last valid value
+        let last = Time::new(863_999_999_999, 7);
+        assert!(column_data_to_mssql_data(tiberius::ColumnData::Time(Some(last))).is_ok());
+    }
+
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    #[test]
+    fn it_decodes_the_first_and_last_date() {
+        for (days, expected) in [(0, (1, 1, 1)), (3_652_058, (9999, 12, 31))] {
+            let data = tiberius::ColumnData::Date(Some(tiberius::time::Date::new(days)));
+
+            let (year, month, day) = match column_data_to_mssql_data(data).unwrap() {
+                #[cfg(feature = "chrono")]
+                MssqlData::NaiveDate(date) => {
+                    use chrono::Datelike;
+                    (date.year(), date.month(), date.day())
+                }
+                #[cfg(all(feature = "time", not(feature = "chrono")))]
+                MssqlData::TimeDate(date) => (
+                    date.year(),
+                    u32::from(u8::from(date.month())),
+                    u32::from(date.day()),
+                ),
+                other => panic!("unexpected value {other:?}"),
+            };
+            assert_eq!((year, month, day), expected);
+        }
+    }
+
+    #[test]
+    fn it_formats_values_as_text() {
+        assert_eq!(MssqlData::Null.to_text(), None);
+        assert_eq!(MssqlData::Bool(true).to_text().unwrap(), "1");
+        assert_eq!(MssqlData::I32(-42).to_text().unwrap(), "-42");
+        assert_eq!(MssqlData::F64(1.5).to_text().unwrap(), "1.5");
+        assert_eq!(
+            MssqlData::Binary(vec![0x00, 0xab, 0x10]).to_text().unwrap(),
+            "0x00AB10"
+        );
+    }
+
+    fn debug_repr(data: MssqlData, type_name: &str) -> String {
+        MssqlValueRef {
+            data: &data,
+            type_info: MssqlTypeInfo::new(type_name),
+        }
+        .debug_repr()
+    }
+
+    #[test]
+    fn it_describes_values_for_debugging() {
+        assert_eq!(debug_repr(MssqlData::Null, "INT"), "INT Null");
+        assert_eq!(debug_repr(MssqlData::I64(7), "BIGINT"), "BIGINT I64(7)");
+        assert_eq!(debug_repr(MssqlData::Bool(true), "BIT"), "BIT Bool(true)");
+        assert_eq!(debug_repr(MssqlData::F32(1.5), "REAL"), "REAL F32(1.5)");
+        assert_eq!(
+            debug_repr(MssqlData::String("it's".into()), "NVARCHAR(10)"),
+            r#"NVARCHAR(10) String("it's")"#
+        );
+        assert_eq!(
+            debug_repr(MssqlData::Binary(vec![0x00, 0xab, 0x10]), "VARBINARY(3)"),
+            "VARBINARY(3) Binary(0x00AB10, 3 bytes)"
+        );
+
+        let long = debug_repr(MssqlData::String("x".repeat(100)), "NVARCHAR(MAX)");
+        assert_eq!(
+            long,
+            format!("NVARCHAR(MAX) String({:?}... 100 chars)", "x".repeat(64))
+        );
+
+        let long = debug_repr(MssqlData::Binary(vec![0xff; 40]), "VARBINARY(MAX)");
+        assert_eq!(
+            long,
+            format!("VARBINARY(MAX) Binary(0x{}..., 40 bytes)", "FF".repeat(32))
+        );
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn it_describes_decimals_for_debugging() {
+        let value = rust_decimal::Decimal::new(1250, 2);
+        assert_eq!(
+            debug_repr(MssqlData::Decimal(value), "DECIMAL(10,2)"),
+            "DECIMAL(10,2) Decimal(12.50)"
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn it_formats_chrono_values_as_text() {
+        use chrono::Timelike as _;
+
+        let dt = chrono::NaiveDate::from_ymd_opt(2024, 1, 2)
+            .unwrap()
+            .and_hms_milli_opt(3, 4, 5, 500)
+            .unwrap();
+        assert_eq!(
+            MssqlData::NaiveDateTime(dt).to_text().unwrap(),
+            "2024-01-02T03:04:05.500"
+        );
+
+        let offset = chrono::FixedOffset::east_opt(2 * 3600).unwrap();
+        let dto = dt
+            .with_nanosecond(0)
+            .unwrap()
+            .and_local_timezone(offset)
+            .unwrap();
+        assert_eq!(
+            MssqlData::DateTimeFixedOffset(dto).to_text().unwrap(),
+            "2024-01-02T03:04:05+02:00"
+        );
+    }
+
+    #[cfg(all(feature = "time", not(feature = "chrono")))]
+    #[test]
+    fn it_formats_time_values_as_text() {
+        let dt = time::macros::datetime!(2024-01-02 03:04:05.5);
+        assert_eq!(
+            MssqlData::TimePrimitiveDateTime(dt).to_text().unwrap(),
+            "2024-01-02T03:04:05.500"
+        );
+
+        let dto = time::macros::datetime!(2024-01-02 03:04:05 -05:30);
+        assert_eq!(
+            MssqlData::TimeOffsetDateTime(dto).to_text().unwrap(),
+            "2024-01-02T03:04:05-05:30"
+        );
+    }
+
+    #[cfg(any(feature = "rust_decimal", not(feature = "bigdecimal")))]
+    #[test]
+    fn it_formats_numerics_as_text() {
+        assert_eq!(MssqlData::Numeric(-1250, 2).to_text().unwrap(), "-12.50");
+        assert_eq!(MssqlData::Numeric(5, 3).to_text().unwrap(), "0.005");
+        assert_eq!(
+            MssqlData::Numeric(i128::MAX, 0).to_text().unwrap(),
+            i128::MAX.to_string()
+        );
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn it_keeps_decimals_beyond_rust_decimal_as_numeric() {
+        let big = 10_i128.pow(38) - 1;
+        let data =
+            tiberius::ColumnData::Numeric(Some(tiberius::numeric::Numeric::new_with_scale(big, 0)));
+        assert!(matches!(
+            column_data_to_mssql_data(data).unwrap(),
+            MssqlData::Numeric(value, 0) if value == big
+        ));
+
+        let data = tiberius::ColumnData::Numeric(Some(tiberius::numeric::Numeric::new_with_scale(
+            1250, 2,
+        )));
+        assert!(matches!(
+            column_data_to_mssql_data(data).unwrap(),
+            MssqlData::Decimal(_)
+        ));
+    }
+
+    #[cfg(all(feature = "bigdecimal", not(feature = "rust_decimal")))]
+    #[test]
+    fn it_formats_big_decimals_without_exponent() {
+        let value: bigdecimal::BigDecimal = "1.2E+5".parse().unwrap();
+        assert_eq!(MssqlData::BigDecimal(value).to_text().unwrap(), "120000");
+    }
+}
@@ -63,6 +63,39 @@ impl<E> SpecError<E> for SpecErrorWrapper<E> {
     }
 }
 
+/// The error decoding a column for a struct field in a derived `FromRow`, naming the field.
+///
+/// [`Error::source`] returns the error the column's decoding failed with, so it can still be
+/// downcast to its original type.
+#[derive(Debug)]
+pub struct FieldDecodeError {
+    field: &'static str,
+    source: Box<dyn Error + Send + Sync + 'static>,
+}
+
+impl FieldDecodeError {
+    pub fn new(field: &'static str, source: Box<dyn Error + Send + Sync + 'static>) -> Self {
+        Self { field, source }
+    }
+
+    /// The field as `Struct::field`.
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
+}
+
+impl Display for FieldDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "field `{}`: {}", self.field, self.source)
+    }
+}
+
+impl Error for FieldDecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __spec_error {
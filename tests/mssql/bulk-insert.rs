@@ -1,5 +1,5 @@
 use sqlx::mssql::{IntoRow, Mssql};
-use sqlx::Row;
+use sqlx::{Connection, Row};
 use sqlx_test::new;
 
 #[sqlx_macros::test]
@@ -75,3 +75,352 @@ async fn it_bulk_inserts_various_types() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_aborts_bulk_insert() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    sqlx::query("CREATE TABLE #bulk_abort (id INT NOT NULL, name NVARCHAR(100) NOT NULL)")
+        .execute(&mut conn)
+        .await?;
+
+    let mut tx = conn.begin().await?;
+    sqlx::query("INSERT INTO #bulk_abort VALUES (0, N'before')")
+        .execute(&mut *tx)
+        .await?;
+
+    // Enough rows that several packets reach the server before the abort.
+    let mut bulk = tx.bulk_insert("#bulk_abort").await?;
+    for id in 1..=1000i32 {
+        bulk.send((id, "a row that is discarded by the abort").into_row())
+            .await?;
+    }
+    bulk.abort().await?;
+
+    // Only the load is rolled back; the transaction stays usable.
+    let count: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM #bulk_abort")
+        .fetch_one(&mut *tx)
+        .await?;
+    assert_eq!(count, 1);
+    tx.commit().await?;
+
+    // Outside a transaction, the rows sent before the abort are kept.
+    let mut bulk = conn.bulk_insert("#bulk_abort").await?;
+    bulk.send((1i32, "kept").into_row()).await?;
+    bulk.abort().await?;
+
+    let count: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM #bulk_abort")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_computes_bulk_insert_identity_range() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    sqlx::query(
+        "CREATE TABLE #bulk_identity (id INT IDENTITY(100, 1) NOT NULL, name NVARCHAR(50) NOT NULL)",
+    )
+    .execute(&mut conn)
+    .await?;
+
+    let mut bulk = conn.bulk_insert("#bulk_identity").await?;
+    bulk.send("alpha".into_row()).await?;
+    bulk.send("beta".into_row()).await?;
+    bulk.send("gamma".into_row()).await?;
+    let total = bulk.finalize().await?;
+    assert_eq!(total, 3);
+
+    let range = conn
+        .bulk_insert_identity_range("#bulk_identity", total)
+        .await?
+        .expect("table has an identity column");
+    assert_eq!(range, 100..=102);
+
+    let ids: Vec<i32> = sqlx::query_scalar("SELECT id FROM #bulk_identity ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(ids, vec![100, 101, 102]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_has_no_identity_range_without_identity_column() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    sqlx::query("CREATE TABLE #bulk_no_identity (id INT NOT NULL)")
+        .execute(&mut conn)
+        .await?;
+
+    let mut bulk = conn.bulk_insert("#bulk_no_identity").await?;
+    bulk.send(1i32.into_row()).await?;
+    let total = bulk.finalize().await?;
+
+    assert_eq!(
+        conn.bulk_insert_identity_range("#bulk_no_identity", total)
+            .await?,
+        None
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_bulk_copies_between_connections() -> anyhow::Result<()> {
+    let mut source = new::<Mssql>().await?;
+    let mut destination = new::<Mssql>().await?;
+
+    sqlx::query(
+        "CREATE TABLE #copy_source (id INT NOT NULL, name NVARCHAR(50) NULL, \
+         price DECIMAL(10, 3) NOT NULL, created DATETIME2 NOT NULL)",
+    )
+    .execute(&mut source)
+    .await?;
+
+    sqlx::query(
+        "INSERT INTO #copy_source VALUES \
+         (1, N'alpha', 1.005, '2024-01-02 03:04:05.678'), \
+         (2, NULL, 2.5, '2024-06-30 23:59:59.999'), \
+         (3, N'gamma', 3, '2025-12-31 00:00:00')",
+    )
+    .execute(&mut source)
+    .await?;
+
+    // Identity columns are skipped; the other columns are converted to the destination types.
+    sqlx::query(
+        "CREATE TABLE #copy_dest (seq INT IDENTITY(1, 1) NOT NULL, id BIGINT NOT NULL, \
+         name NVARCHAR(50) NULL, price DECIMAL(10, 2) NOT NULL, created DATETIME NOT NULL)",
+    )
+    .execute(&mut destination)
+    .await?;
+
+    let copied = source
+        .bulk_copy(
+            "SELECT id, name, price, created FROM #copy_source ORDER BY id",
+            &mut destination,
+            "#copy_dest",
+        )
+        .await?;
+    assert_eq!(copied, 3);
+
+    let rows: Vec<(i64, Option<String>, String, String)> = sqlx::query_as(
+        "SELECT id, name, CAST(price AS NVARCHAR(20)), \
+         CONVERT(NVARCHAR(30), created, 121) FROM #copy_dest ORDER BY seq",
+    )
+    .fetch_all(&mut destination)
+    .await?;
+
+    assert_eq!(
+        rows,
+        vec![
+            (
+                1,
+                Some("alpha".to_owned()),
+                "1.01".to_owned(),
+                "2024-01-02 03:04:05.677".to_owned()
+            ),
+            (
+                2,
+                None,
+                "2.50".to_owned(),
+                "2024-07-01 00:00:00.000".to_owned()
+            ),
+            (
+                3,
+                Some("gamma".to_owned()),
+                "3.00".to_owned(),
+                "2025-12-31 00:00:00.000".to_owned()
+            ),
+        ]
+    );
+
+    // Bound parameters are supported, and a query without rows copies nothing.
+    let copied = source
+        .bulk_copy(
+            sqlx::query("SELECT id, name, price, created FROM #copy_source WHERE id > @p1")
+                .bind(10i32),
+            &mut destination,
+            "#copy_dest",
+        )
+        .await?;
+    assert_eq!(copied, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rejects_bulk_copy_with_mismatched_columns() -> anyhow::Result<()> {
+    let mut source = new::<Mssql>().await?;
+    let mut destination = new::<Mssql>().await?;
+
+    sqlx::query("CREATE TABLE #copy_narrow (id INT NOT NULL)")
+        .execute(&mut destination)
+        .await?;
+
+    let err = source
+        .bulk_copy(
+            "SELECT 1 AS id, 2 AS extra",
+            &mut destination,
+            "#copy_narrow",
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::InvalidArgument(_)), "{err}");
+
+    // Nothing was started on the destination, so it is still usable.
+    let count: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM #copy_narrow")
+        .fetch_one(&mut destination)
+        .await?;
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_aborts_bulk_copy_on_conversion_error() -> anyhow::Result<()> {
+    let mut source = new::<Mssql>().await?;
+    let mut destination = new::<Mssql>().await?;
+
+    sqlx::query("CREATE TABLE #copy_tiny (id TINYINT NOT NULL)")
+        .execute(&mut destination)
+        .await?;
+
+    let mut tx = destination.begin().await?;
+    let err = source
+        .bulk_copy("SELECT 1 UNION ALL SELECT 300", &mut tx, "#copy_tiny")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::Encode(_)), "{err}");
+
+    // The load was aborted, so the destination is usable and the first row is discarded.
+    let count: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM #copy_tiny")
+        .fetch_one(&mut *tx)
+        .await?;
+    assert_eq!(count, 0);
+    tx.commit().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_inserts_chunked_rows() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    sqlx::query(
+        "CREATE TABLE #chunked (id INT NOT NULL PRIMARY KEY, name NVARCHAR(20) NOT NULL, \
+         value BIGINT NOT NULL)",
+    )
+    .execute(&mut conn)
+    .await?;
+
+    // 3 parameters per row, so 699 rows per statement
+    let rows: Vec<(i32, String, i64)> = (0..5000)
+        .map(|i| (i, format!("row {i}"), i64::from(i) * 2))
+        .collect();
+
+    let inserted = conn
+        .insert_chunked(
+            "INSERT INTO #chunked (id, name, value) ",
+            &rows,
+            |mut b, (id, name, value)| {
+                b.push_bind(*id).push_bind(name.clone()).push_bind(*value);
+            },
+        )
+        .await?;
+    assert_eq!(inserted, 5000);
+
+    let (count, sum): (i32, i64) = sqlx::query_as("SELECT COUNT(*), SUM(value) FROM #chunked")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 5000);
+    assert_eq!(sum, 4999 * 5000);
+
+    let name: String = sqlx::query_scalar("SELECT name FROM #chunked WHERE id = 4321")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(name, "row 4321");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_chunks_rows_binding_different_numbers_of_values() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    sqlx::query("CREATE TABLE #chunked_mixed (id INT NOT NULL PRIMARY KEY, name NVARCHAR(20))")
+        .execute(&mut conn)
+        .await?;
+
+    // The first row binds one value and the rest two, so sizing every statement from the
+    // first row would exceed the parameter limit.
+    let rows: Vec<i32> = (0..3000).collect();
+    let mut calls = 0;
+
+    let inserted = conn
+        .insert_chunked(
+            "INSERT INTO #chunked_mixed (id, name) ",
+            &rows,
+            |mut b, id| {
+                calls += 1;
+                b.push_bind(*id);
+                if *id == 0 {
+                    b.push("NULL");
+                } else {
+                    b.push_bind(format!("row {id}"));
+                }
+            },
+        )
+        .await?;
+    assert_eq!(inserted, 3000);
+    assert_eq!(calls, 3000);
+
+    // Row 1049 is the first that doesn't fit in the first statement
+    let names: Vec<Option<String>> = sqlx::query_scalar(
+        "SELECT name FROM #chunked_mixed WHERE id IN (0, 1048, 1049, 2999) ORDER BY id",
+    )
+    .fetch_all(&mut conn)
+    .await?;
+    assert_eq!(
+        names,
+        [
+            None,
+            Some("row 1048".into()),
+            Some("row 1049".into()),
+            Some("row 2999".into())
+        ]
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rolls_back_chunked_insert_on_failure() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    sqlx::query("CREATE TABLE #chunked_fail (id INT NOT NULL PRIMARY KEY)")
+        .execute(&mut conn)
+        .await?;
+
+    // The duplicate key lands in the third chunk, after two chunks were inserted.
+    let mut ids: Vec<i32> = (0..2500).collect();
+    ids.push(10);
+
+    let err = conn
+        .insert_chunked("INSERT INTO #chunked_fail (id) ", &ids, |mut b, id| {
+            b.push_bind(*id);
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::Database(_)), "{err}");
+
+    let count: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM #chunked_fail")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 0);
+
+    Ok(())
+}
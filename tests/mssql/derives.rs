@@ -89,6 +89,144 @@ async fn test_derive_weak_enum() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx::test]
+async fn test_derive_strong_enum() -> anyhow::Result<()> {
+    #[derive(sqlx::Type, Debug, PartialEq, Eq)]
+    #[sqlx(rename_all = "lowercase")]
+    enum Color {
+        Red,
+        Green,
+        Blue,
+    }
+
+    let mut conn = new::<Mssql>().await?;
+
+    sqlx::raw_sql("CREATE TABLE #strong_enum (id INT, color NVARCHAR(16))")
+        .execute(&mut conn)
+        .await?;
+
+    for (id, color) in [(1, Color::Red), (2, Color::Green), (3, Color::Blue)] {
+        sqlx::query("INSERT INTO #strong_enum (id, color) VALUES (@p1, @p2)")
+            .bind(id)
+            .bind(color)
+            .execute(&mut conn)
+            .await?;
+    }
+
+    let stored: Vec<String> = sqlx::query_scalar("SELECT color FROM #strong_enum ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(stored, ["red", "green", "blue"]);
+
+    let colors: Vec<Color> = sqlx::query_scalar("SELECT color FROM #strong_enum ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(colors, [Color::Red, Color::Green, Color::Blue]);
+
+    let err = sqlx::query_scalar::<_, Color>("SELECT N'purple'")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(err, sqlx::Error::ColumnDecode { .. }),
+        "expected ColumnDecode, got {err:?}"
+    );
+    assert!(err.to_string().contains("purple"), "{err}");
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn test_from_row_decode_error_names_field() -> anyhow::Result<()> {
+    #[derive(sqlx::FromRow, Debug)]
+    #[allow(dead_code)]
+    struct User {
+        id: i32,
+        #[sqlx(rename = "user_name")]
+        name: i64,
+    }
+
+    let mut conn = new::<Mssql>().await?;
+
+    let err = sqlx::query_as::<_, User>("SELECT 1 AS id, N'alice' AS user_name")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    let sqlx::Error::ColumnDecode { index, source } = &err else {
+        panic!("expected ColumnDecode, got {err:?}");
+    };
+
+    assert!(index.contains("user_name"), "{index}");
+    assert!(!index.contains("User::name"), "{index}");
+    assert!(
+        source.to_string().contains("field `User::name`"),
+        "{source}"
+    );
+    assert!(err.to_string().contains("User::name"), "{err}");
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn test_from_row_decode_error_keeps_the_original_source() -> anyhow::Result<()> {
+    #[derive(Debug, PartialEq)]
+    struct NegativeLevel(i32);
+
+    impl std::fmt::Display for NegativeLevel {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "negative level {}", self.0)
+        }
+    }
+
+    impl std::error::Error for NegativeLevel {}
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Level(u32);
+
+    impl TryFrom<i32> for Level {
+        type Error = NegativeLevel;
+
+        fn try_from(value: i32) -> Result<Self, Self::Error> {
+            u32::try_from(value)
+                .map(Level)
+                .map_err(|_| NegativeLevel(value))
+        }
+    }
+
+    #[derive(sqlx::FromRow, Debug)]
+    #[allow(dead_code)]
+    struct Player {
+        #[sqlx(try_from = "i32")]
+        level: Level,
+    }
+
+    let mut conn = new::<Mssql>().await?;
+
+    let err = sqlx::query_as::<_, Player>("SELECT -3 AS level")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    let sqlx::Error::ColumnDecode { source, .. } = &err else {
+        panic!("expected ColumnDecode, got {err:?}");
+    };
+
+    let field = source
+        .downcast_ref::<sqlx::spec_error::FieldDecodeError>()
+        .expect("the field is named");
+    assert_eq!(field.field(), "Player::level");
+
+    let original = std::error::Error::source(field).and_then(|e| e.downcast_ref::<NegativeLevel>());
+    assert_eq!(original, Some(&NegativeLevel(-3)));
+
+    Ok(())
+}
+
 #[derive(PartialEq, Eq, Debug, sqlx::Type)]
 #[sqlx(transparent)]
 struct TransparentTuple(i64);
@@ -108,3 +246,84 @@ test_type!(transparent_named<TransparentNamed>(Mssql,
     "CAST(0 AS BIGINT)" == TransparentNamed { field: 0 },
     "CAST(23523 AS BIGINT)" == TransparentNamed { field: 23523 },
 ));
+
+#[derive(PartialEq, Eq, Debug, sqlx::Type)]
+#[sqlx(transparent)]
+struct UserId(i32);
+
+#[derive(PartialEq, Eq, Debug, sqlx::Type)]
+#[sqlx(transparent)]
+struct Status(u8);
+
+#[derive(PartialEq, Eq, Debug, sqlx::Type)]
+#[sqlx(transparent)]
+struct Rank(i16);
+
+#[derive(PartialEq, Eq, Debug, sqlx::Type)]
+#[sqlx(transparent)]
+struct Level(i8);
+
+test_type!(transparent_int<UserId>(Mssql,
+    "CAST(0 AS INT)" == UserId(0),
+    "CAST(2147483647 AS INT)" == UserId(i32::MAX),
+));
+
+test_type!(transparent_tinyint<Status>(Mssql,
+    "CAST(0 AS TINYINT)" == Status(0),
+    "CAST(255 AS TINYINT)" == Status(255),
+));
+
+test_type!(transparent_smallint<Rank>(Mssql,
+    "CAST(-32768 AS SMALLINT)" == Rank(i16::MIN),
+    "CAST(32767 AS SMALLINT)" == Rank(i16::MAX),
+));
+
+#[sqlx::test]
+async fn test_transparent_integer_ids_round_trip() -> anyhow::Result<()> {
+    #[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
+    struct Account {
+        id: UserId,
+        rank: Rank,
+        status: Status,
+    }
+
+    let mut conn = new::<Mssql>().await?;
+
+    sqlx::raw_sql("CREATE TABLE #accounts (id INT IDENTITY(1, 1), rank SMALLINT, status TINYINT)")
+        .execute(&mut conn)
+        .await?;
+
+    let id: UserId = sqlx::query_scalar(
+        "INSERT INTO #accounts (rank, status) OUTPUT INSERTED.id VALUES (@p1, @p2)",
+    )
+    .bind(Rank(-7))
+    .bind(Status(200))
+    .fetch_one(&mut conn)
+    .await?;
+
+    let account: Account = sqlx::query_as("SELECT id, rank, status FROM #accounts WHERE id = @p1")
+        .bind(&id)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(
+        account,
+        Account {
+            id: UserId(1),
+            rank: Rank(-7),
+            status: Status(200),
+        }
+    );
+
+    // TINYINT is unsigned: values above 127 don't fit a newtype over i8
+    let err = sqlx::query_scalar::<_, Level>("SELECT status FROM #accounts")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, sqlx::Error::ColumnDecode { .. }),
+        "expected ColumnDecode, got {err:?}"
+    );
+
+    Ok(())
+}
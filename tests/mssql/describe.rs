@@ -39,3 +39,20 @@ async fn it_describes_with_params() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_describes_spatial_columns() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    let d = conn
+        .describe(
+            "SELECT geography::Point(47.6, -122.3, 4326) AS g, geometry::Point(1, 2, 0) AS m"
+                .into_sql_str(),
+        )
+        .await?;
+
+    assert_eq!(d.columns()[0].type_info().name(), "GEOGRAPHY");
+    assert_eq!(d.columns()[1].type_info().name(), "GEOMETRY");
+
+    Ok(())
+}
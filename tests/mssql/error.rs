@@ -1,6 +1,6 @@
 use sqlx::error::ErrorKind;
-use sqlx::mssql::Mssql;
-use sqlx::Connection;
+use sqlx::mssql::{Mssql, MssqlDatabaseError};
+use sqlx::{Connection, Executor};
 use sqlx_test::new;
 
 #[sqlx_macros::test]
@@ -77,3 +77,63 @@ async fn it_fails_with_check_violation() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_fails_with_truncation() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    conn.execute("CREATE TABLE #truncation (code VARCHAR(3))")
+        .await?;
+
+    let res: Result<_, sqlx::Error> = sqlx::query("INSERT INTO #truncation (code) VALUES (@p1)")
+        .bind("ABCD")
+        .execute(&mut conn)
+        .await;
+    let err = res.unwrap_err();
+
+    let err = err.into_database_error().unwrap();
+
+    assert!(err.downcast_ref::<MssqlDatabaseError>().is_truncation());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_distinguishes_lock_timeouts_from_other_errors() -> anyhow::Result<()> {
+    let mut holder = new::<Mssql>().await?;
+    let mut waiter = new::<Mssql>().await?;
+
+    holder
+        .execute("CREATE TABLE ##sqlx_lock_timeout (id INT NOT NULL)")
+        .await?;
+
+    let mut tx = holder.begin().await?;
+    tx.execute("INSERT INTO ##sqlx_lock_timeout (id) VALUES (1)")
+        .await?;
+
+    // The uncommitted insert blocks the read until the lock timeout expires.
+    let err = waiter
+        .execute("SET LOCK_TIMEOUT 100; SELECT id FROM ##sqlx_lock_timeout")
+        .await
+        .unwrap_err();
+    let err = err.into_database_error().unwrap();
+    let err = err.downcast_ref::<MssqlDatabaseError>();
+    assert!(err.is_timeout(), "{err}");
+    assert_eq!(err.number(), 1222);
+
+    tx.rollback().await?;
+
+    let err = waiter.execute("SELECT 1 / 0").await.unwrap_err();
+    let err = err.into_database_error().unwrap();
+    assert!(!err.downcast_ref::<MssqlDatabaseError>().is_timeout());
+
+    // The waiting connection is still usable after the timeout.
+    let count: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM ##sqlx_lock_timeout")
+        .fetch_one(&mut waiter)
+        .await?;
+    assert_eq!(count, 0);
+
+    holder.execute("DROP TABLE ##sqlx_lock_timeout").await?;
+
+    Ok(())
+}
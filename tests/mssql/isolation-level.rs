@@ -1,5 +1,5 @@
 use sqlx::mssql::{Mssql, MssqlIsolationLevel};
-use sqlx::Row;
+use sqlx::{Connection, Row};
 use sqlx_test::new;
 
 #[sqlx_macros::test]
@@ -54,3 +54,37 @@ async fn it_begins_with_serializable() -> anyhow::Result<()> {
     tx.commit().await?;
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_begins_read_only_with_snapshot_isolation() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    sqlx::query("ALTER DATABASE CURRENT SET ALLOW_SNAPSHOT_ISOLATION ON")
+        .execute(&mut conn)
+        .await?;
+
+    const LEVEL: &str =
+        "SELECT transaction_isolation_level FROM sys.dm_exec_sessions WHERE session_id = @@SPID";
+
+    let before: i16 = sqlx::query_scalar(LEVEL).fetch_one(&mut conn).await?;
+
+    let mut tx = conn.begin_read_only().await?;
+    let level: i16 = sqlx::query_scalar(LEVEL).fetch_one(&mut *tx).await?;
+    assert_eq!(level, 5);
+
+    {
+        // nested, it takes a savepoint and keeps the outer isolation level
+        let mut nested = tx.begin_read_only().await?;
+        let level: i16 = sqlx::query_scalar(LEVEL).fetch_one(&mut *nested).await?;
+        assert_eq!(level, 5);
+        nested.commit().await?;
+    }
+
+    tx.commit().await?;
+
+    let after: i16 = sqlx::query_scalar(LEVEL).fetch_one(&mut conn).await?;
+    assert_eq!(after, before);
+    assert!(!conn.is_in_transaction());
+
+    Ok(())
+}
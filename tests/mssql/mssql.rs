@@ -1,9 +1,13 @@
-use futures_util::TryStreamExt;
+use futures_util::{FutureExt, TryStreamExt};
+use sqlx::mssql::MssqlQueryBuilderExt;
 use sqlx::mssql::MssqlRow;
-use sqlx::mssql::{Mssql, MssqlPoolOptions};
-use sqlx::mssql::{MssqlAdvisoryLock, MssqlIsolationLevel};
+use sqlx::mssql::{Mssql, MssqlPoolExt, MssqlPoolOptions, MssqlPoolOptionsExt};
+use sqlx::mssql::{MssqlAdvisoryLock, MssqlConnectOptions, MssqlIsolationLevel};
+use sqlx::mssql::{MssqlEncryptionLevel, MssqlProcedureParams, MssqlSslMode};
+use sqlx::query_builder::QueryBuilder;
 use sqlx::{Column, Connection, Executor, MssqlConnection, Row, SqlSafeStr, Statement, TypeInfo};
 use sqlx_test::new;
+use std::panic::AssertUnwindSafe;
 use std::sync::atomic::{AtomicI32, Ordering};
 use std::time::Duration;
 
@@ -42,6 +46,139 @@ async fn it_can_select_expression_by_name() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_traces_handshake_phases() -> anyhow::Result<()> {
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Records the `phase` field of every handshake event.
+    #[derive(Default)]
+    struct Phases(Arc<Mutex<Vec<String>>>);
+
+    struct PhaseVisitor<'a>(&'a mut Option<String>);
+
+    impl Visit for PhaseVisitor<'_> {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            if field.name() == "phase" {
+                *self.0 = Some(value.to_owned());
+            }
+        }
+
+        fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+    }
+
+    impl Subscriber for Phases {
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            metadata.target() == "sqlx::mssql::handshake"
+        }
+
+        fn new_span(&self, _span: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut phase = None;
+            event.record(&mut PhaseVisitor(&mut phase));
+            self.0.lock().unwrap().extend(phase);
+        }
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    let phases = Phases::default();
+    let recorded = Arc::clone(&phases.0);
+    let _guard = tracing::subscriber::set_default(phases);
+
+    let conn = new::<Mssql>().await?;
+    conn.close().await?;
+
+    assert_eq!(
+        *recorded.lock().unwrap(),
+        ["connect", "handshake", "established"]
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_traces_queries_with_connection_attributes() -> anyhow::Result<()> {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Records the fields of every `mssql.query` span.
+    #[derive(Default)]
+    struct QuerySpans(Arc<Mutex<Vec<HashMap<String, String>>>>);
+
+    struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+    impl Visit for FieldVisitor<'_> {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.insert(field.name().to_owned(), value.to_owned());
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0.insert(field.name().to_owned(), format!("{value:?}"));
+        }
+    }
+
+    impl Subscriber for QuerySpans {
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            metadata.is_span() && metadata.name() == "mssql.query"
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut fields = HashMap::new();
+            span.record(&mut FieldVisitor(&mut fields));
+            let mut spans = self.0.lock().unwrap();
+            spans.push(fields);
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, _event: &Event<'_>) {}
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    let options: MssqlConnectOptions = dotenvy::var("DATABASE_URL")?.parse()?;
+    let mut conn = MssqlConnection::connect_with(&options).await?;
+
+    let spans = QuerySpans::default();
+    let recorded = Arc::clone(&spans.0);
+    let guard = tracing::subscriber::set_default(spans);
+
+    conn.execute("SELECT 1").await?;
+
+    drop(guard);
+
+    let recorded = recorded.lock().unwrap();
+    assert_eq!(recorded.len(), 1);
+    assert_eq!(recorded[0]["db.system"], "mssql");
+    assert_eq!(
+        recorded[0].get("db.name").map(String::as_str),
+        options.get_database()
+    );
+    assert_eq!(recorded[0]["net.peer.name"], options.get_host());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_fail_to_connect() -> anyhow::Result<()> {
     let mut url = dotenvy::var("DATABASE_URL")?;
@@ -57,264 +194,1475 @@ async fn it_can_fail_to_connect() -> anyhow::Result<()> {
 }
 
 #[sqlx_macros::test]
-async fn it_can_inspect_errors() -> anyhow::Result<()> {
-    let mut conn = new::<Mssql>().await?;
-
-    let res: Result<_, sqlx::Error> = sqlx::query("select f").execute(&mut conn).await;
-    let err = res.unwrap_err();
+async fn it_sets_deadlock_priority_on_connect() -> anyhow::Result<()> {
+    let opts: MssqlConnectOptions = dotenvy::var("DATABASE_URL")?.parse()?;
+    let mut conn = MssqlConnection::connect_with(&opts.deadlock_priority(-5)).await?;
 
-    // can also do [as_database_error] or use `match ..`
-    let err = err.into_database_error().unwrap();
+    let priority: i32 = sqlx::query_scalar(
+        "SELECT deadlock_priority FROM sys.dm_exec_sessions WHERE session_id = @@SPID",
+    )
+    .fetch_one(&mut conn)
+    .await?;
 
-    assert_eq!(err.message(), "Invalid column name 'f'.");
+    assert_eq!(priority, -5);
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_maths() -> anyhow::Result<()> {
-    let mut conn = new::<Mssql>().await?;
-
-    let value = sqlx::query("SELECT 1 + @p1")
-        .bind(5_i32)
-        .try_map(|row: MssqlRow| row.try_get::<i32, _>(0))
-        .fetch_one(&mut conn)
-        .await?;
+async fn it_sends_app_name_with_attributes() -> anyhow::Result<()> {
+    let opts: MssqlConnectOptions = dotenvy::var("DATABASE_URL")?.parse()?;
+    let opts = opts
+        .app_name("sqlx-test")
+        .app_attribute("pool", "reporting")
+        .app_attribute("tenant", "acme");
+    let mut conn = MssqlConnection::connect_with(&opts).await?;
+
+    let program_name: String = sqlx::query_scalar(
+        "SELECT program_name FROM sys.dm_exec_sessions WHERE session_id = @@SPID",
+    )
+    .fetch_one(&mut conn)
+    .await?;
 
-    assert_eq!(6_i32, value);
+    assert_eq!(program_name, "sqlx-test;pool=reporting;tenant=acme");
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_executes() -> anyhow::Result<()> {
+async fn it_sets_required_set_options_on_connect() -> anyhow::Result<()> {
     let mut conn = new::<Mssql>().await?;
 
-    let _ = conn
-        .execute(
-            r#"
-CREATE TABLE #users (id INTEGER PRIMARY KEY);
-            "#,
-        )
+    let arithabort: i32 = sqlx::query_scalar("SELECT CAST(SESSIONPROPERTY('ARITHABORT') AS INT)")
+        .fetch_one(&mut conn)
         .await?;
+    assert_eq!(arithabort, 1);
+
+    // An index on a computed column fails with error 1934 unless the options are set.
+    conn.execute(
+        "CREATE TABLE #computed_index (price INT NOT NULL, qty INT NOT NULL, \
+         total AS price * qty PERSISTED); \
+         CREATE INDEX ix_computed_total ON #computed_index (total); \
+         INSERT INTO #computed_index (price, qty) VALUES (3, 4);",
+    )
+    .await?;
 
-    for index in 1..=10_i32 {
-        let done = sqlx::query("INSERT INTO #users (id) VALUES (@p1)")
-            .bind(index * 2)
-            .execute(&mut conn)
-            .await?;
+    let total: i32 = sqlx::query_scalar("SELECT total FROM #computed_index WHERE total = 12")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(total, 12);
 
-        assert_eq!(done.rows_affected(), 1);
-    }
+    let opts: MssqlConnectOptions = dotenvy::var("DATABASE_URL")?.parse()?;
+    let mut conn = MssqlConnection::connect_with(&opts.required_set_options(false)).await?;
 
-    let sum: i32 = sqlx::query("SELECT id FROM #users")
-        .try_map(|row: MssqlRow| row.try_get::<i32, _>(0))
-        .fetch(&mut conn)
-        .try_fold(0_i32, |acc, x| async move { Ok(acc + x) })
+    let arithabort: i32 = sqlx::query_scalar("SELECT CAST(SESSIONPROPERTY('ARITHABORT') AS INT)")
+        .fetch_one(&mut conn)
         .await?;
-
-    assert_eq!(sum, 110);
+    assert_eq!(arithabort, 0);
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_return_1000_rows() -> anyhow::Result<()> {
-    let mut conn = new::<Mssql>().await?;
+async fn it_reports_no_rows_affected_with_no_count() -> anyhow::Result<()> {
+    let opts: MssqlConnectOptions = dotenvy::var("DATABASE_URL")?.parse()?;
 
-    let _ = conn
-        .execute(
-            r#"
-CREATE TABLE #users (id INTEGER PRIMARY KEY);
-            "#,
+    for no_count in [false, true] {
+        let mut conn = MssqlConnection::connect_with(&opts.clone().no_count(no_count)).await?;
+
+        let enabled: i32 = sqlx::query_scalar("SELECT CAST(@@OPTIONS & 512 AS INT)")
+            .fetch_one(&mut conn)
+            .await?;
+        assert_eq!(enabled != 0, no_count);
+
+        conn.execute(
+            "CREATE TABLE #no_count (id INT NOT NULL); \
+             INSERT INTO #no_count VALUES (1), (2), (3);",
         )
         .await?;
 
-    for index in 1..=1000_i32 {
-        let done = sqlx::query("INSERT INTO #users (id) VALUES (@p1)")
-            .bind(index * 2)
+        let updated = sqlx::query("UPDATE #no_count SET id = id + @p1 WHERE id > 1")
+            .bind(10i32)
             .execute(&mut conn)
-            .await?;
+            .await?
+            .rows_affected();
+        assert_eq!(updated, if no_count { 0 } else { 2 });
 
-        assert_eq!(done.rows_affected(), 1);
+        // @@ROWCOUNT is maintained either way
+        assert_eq!(conn.last_rowcount().await?, 2);
     }
 
-    let sum: i32 = sqlx::query("SELECT id FROM #users")
-        .try_map(|row: MssqlRow| row.try_get::<i32, _>(0))
-        .fetch(&mut conn)
-        .try_fold(0_i32, |acc, x| async move { Ok(acc + x) })
-        .await?;
-
-    assert_eq!(sum, 1001000);
-
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_selects_null() -> anyhow::Result<()> {
+async fn it_checks_the_expected_collation_on_connect() -> anyhow::Result<()> {
     let mut conn = new::<Mssql>().await?;
 
-    let (_, val): (i32, Option<i32>) = sqlx::query_as("SELECT 5, NULL")
-        .fetch_one(&mut conn)
-        .await?;
+    let collation: String =
+        sqlx::query_scalar("SELECT CAST(SERVERPROPERTY('Collation') AS NVARCHAR(128))")
+            .fetch_one(&mut conn)
+            .await?;
 
-    assert!(val.is_none());
+    let opts: MssqlConnectOptions = dotenvy::var("DATABASE_URL")?.parse()?;
 
-    let val: Option<i32> = conn.fetch_one("SELECT 10, NULL").await?.try_get(1)?;
+    // The comparison ignores case.
+    let mut conn =
+        MssqlConnection::connect_with(&opts.clone().expected_collation(&collation.to_lowercase()))
+            .await?;
+    conn.ping().await?;
 
-    assert!(val.is_none());
+    let err = MssqlConnection::connect_with(&opts.expected_collation("Not_A_Real_Collation"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::Configuration(_)), "{err}");
+    assert!(err.to_string().contains(&collation), "{err}");
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_binds_empty_string_and_null() -> anyhow::Result<()> {
+async fn it_detects_rowversion_conflicts() -> anyhow::Result<()> {
     let mut conn = new::<Mssql>().await?;
 
-    let (val, val2): (String, Option<String>) = sqlx::query_as("SELECT @p1, @p2")
-        .bind("")
-        .bind(None::<String>)
+    conn.execute(
+        "CREATE TABLE #rowversion_orders (id INT PRIMARY KEY, qty INT, version ROWVERSION); \
+         INSERT INTO #rowversion_orders (id, qty) VALUES (1, 1)",
+    )
+    .await?;
+
+    let version: [u8; 8] =
+        sqlx::query_scalar("SELECT version FROM #rowversion_orders WHERE id = 1")
+            .fetch_one(&mut conn)
+            .await?;
+
+    let update = |qty: i32, version: &[u8]| -> anyhow::Result<QueryBuilder<Mssql>> {
+        let mut qb = QueryBuilder::<Mssql>::new("UPDATE #rowversion_orders SET qty = ");
+        qb.push_bind(qty);
+        qb.push(" OUTPUT INSERTED.version WHERE id = ")
+            .push_bind(1_i32);
+        qb.push(" AND ");
+        qb.push_rowversion_check("version", version)?;
+        Ok(qb)
+    };
+
+    // The row is unchanged since it was read: the update goes through
+    let result = update(2, &version)?.build().execute(&mut conn).await?;
+    assert_eq!(result.rows_affected(), 1);
+
+    // The rowversion has changed since: updating with the stale value is a conflict
+    let result = update(3, &version)?.build().execute(&mut conn).await?;
+    assert_eq!(result.rows_affected(), 0);
+
+    let qty: i32 = sqlx::query_scalar("SELECT qty FROM #rowversion_orders WHERE id = 1")
         .fetch_one(&mut conn)
         .await?;
-
-    assert!(val.is_empty());
-    assert!(val2.is_none());
+    assert_eq!(qty, 2);
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_work_with_transactions() -> anyhow::Result<()> {
+async fn it_reads_merge_output_actions() -> anyhow::Result<()> {
     let mut conn = new::<Mssql>().await?;
 
-    conn.execute("IF OBJECT_ID('_sqlx_users_1922', 'U') IS NULL CREATE TABLE _sqlx_users_1922 (id INTEGER PRIMARY KEY)")
-        .await?;
+    conn.execute(
+        "CREATE TABLE #merge_target (id INT PRIMARY KEY, qty INT NOT NULL); \
+         INSERT INTO #merge_target (id, qty) VALUES (1, 10), (2, 20), (3, 30);",
+    )
+    .await?;
 
-    conn.execute("DELETE FROM _sqlx_users_1922").await?;
+    // Upsert ids 2 and 4, and delete rows missing from the source
+    let rows = sqlx::query(
+        "MERGE #merge_target AS t \
+         USING (VALUES (@p1, @p2), (@p3, @p4)) AS s (id, qty) ON t.id = s.id \
+         WHEN MATCHED THEN UPDATE SET qty = s.qty \
+         WHEN NOT MATCHED BY TARGET THEN INSERT (id, qty) VALUES (s.id, s.qty) \
+         WHEN NOT MATCHED BY SOURCE THEN DELETE \
+         OUTPUT $action, COALESCE(INSERTED.id, DELETED.id) AS id;",
+    )
+    .bind(2_i32)
+    .bind(25_i32)
+    .bind(4_i32)
+    .bind(40_i32)
+    .fetch_all(&mut conn)
+    .await?;
 
-    // begin .. rollback
+    assert_eq!(rows[0].column(0).name(), "$action");
+    assert_eq!(rows[0].column(0).type_info().name(), "NVARCHAR");
+
+    let mut actions = rows
+        .iter()
+        .map(|row| Ok((row.try_get::<i32, _>("id")?, row.try_get::<String, _>(0)?)))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    actions.sort();
+
+    assert_eq!(
+        actions,
+        [
+            (1, "DELETE".to_owned()),
+            (2, "UPDATE".to_owned()),
+            (3, "DELETE".to_owned()),
+            (4, "INSERT".to_owned()),
+        ]
+    );
 
-    let mut tx = conn.begin().await?;
+    Ok(())
+}
 
-    sqlx::query("INSERT INTO _sqlx_users_1922 (id) VALUES (@p1)")
-        .bind(10_i32)
-        .execute(&mut *tx)
-        .await?;
+#[sqlx_macros::test]
+async fn it_runs_dbcc_commands() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
 
-    tx.rollback().await?;
+    // One row per database with its transaction log usage
+    let rows = conn.dbcc("SQLPERF(LOGSPACE)").await?;
+    assert!(!rows.is_empty());
 
-    let (count,): (i32,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_1922")
-        .fetch_one(&mut conn)
-        .await?;
+    let name: String = rows[0].try_get("Database Name")?;
+    assert!(!name.is_empty());
 
-    assert_eq!(count, 0);
+    // Without TABLERESULTS, OPENTRAN only prints messages
+    let rows = conn.dbcc("DBCC OPENTRAN").await?;
+    assert!(rows.is_empty());
 
-    // begin .. commit
+    Ok(())
+}
 
-    let mut tx = conn.begin().await?;
+#[sqlx_macros::test]
+async fn it_reads_any_column_as_string() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
 
-    sqlx::query("INSERT INTO _sqlx_users_1922 (id) VALUES (@p1)")
-        .bind(10_i32)
-        .execute(&mut *tx)
+    let row = conn
+        .fetch_one(
+            "SELECT CAST(42 AS INT) AS n, \
+             CAST('2024-01-02T03:04:05.5' AS DATETIME2) AS dt, \
+             CAST(12.34 AS DECIMAL(10, 3)) AS d, \
+             CAST(NULL AS INT) AS missing",
+        )
         .await?;
 
-    tx.commit().await?;
-
-    let (count,): (i32,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_1922")
-        .fetch_one(&mut conn)
-        .await?;
+    assert_eq!(row.try_get_string("n")?.as_deref(), Some("42"));
+    assert_eq!(
+        row.try_get_string("dt")?.as_deref(),
+        Some("2024-01-02T03:04:05.500")
+    );
+    assert_eq!(row.try_get_string("d")?.as_deref(), Some("12.340"));
+    assert_eq!(row.try_get_string("missing")?, None);
 
-    assert_eq!(count, 1);
+    // Strict decoding still requires a character column
+    assert!(row.try_get::<String, _>("n").is_err());
 
-    // begin .. (drop)
+    Ok(())
+}
 
-    {
-        let mut tx = conn.begin().await?;
+#[sqlx_macros::test]
+async fn it_reads_server_capabilities() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
 
-        sqlx::query("INSERT INTO _sqlx_users_1922 (id) VALUES (@p1)")
-            .bind(20_i32)
-            .execute(&mut *tx)
+    let major: i32 =
+        sqlx::query_scalar("SELECT CAST(SERVERPROPERTY('ProductMajorVersion') AS INT)")
+            .fetch_one(&mut conn)
             .await?;
-    }
 
-    conn = new::<Mssql>().await?;
+    let caps = conn.server_capabilities().await?;
+    assert_eq!(i32::from(caps.major_version()), major);
 
-    let (count,): (i32,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_1922")
-        .fetch_one(&mut conn)
-        .await?;
+    // The test images are SQL Server 2017 and later
+    assert!(caps.supports_offset_fetch());
+    assert!(caps.supports_json());
+    assert!(caps.supports_string_agg());
+    assert_eq!(caps.supports_strict_encryption(), major >= 16);
 
-    assert_eq!(count, 1);
+    // Cached for the lifetime of the connection
+    assert_eq!(conn.server_capabilities().await?, caps);
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_work_with_nested_transactions() -> anyhow::Result<()> {
-    let mut conn = new::<Mssql>().await?;
-
-    conn.execute("IF OBJECT_ID('_sqlx_users_2523', 'U') IS NULL CREATE TABLE _sqlx_users_2523 (id INTEGER PRIMARY KEY)")
-        .await?;
+async fn it_reports_the_negotiated_encryption_level() -> anyhow::Result<()> {
+    let opts: MssqlConnectOptions = dotenvy::var("DATABASE_URL")?.parse()?;
+
+    // The test images ship a self-signed certificate, so `Preferred` negotiates TLS.
+    let mut conn = MssqlConnection::connect_with(
+        &opts
+            .clone()
+            .ssl_mode(MssqlSslMode::Preferred)
+            .trust_server_certificate(true),
+    )
+    .await?;
+    assert_eq!(
+        conn.encryption_level().await?,
+        MssqlEncryptionLevel::Encrypted
+    );
 
-    conn.execute("DELETE FROM _sqlx_users_2523").await?;
+    let mut conn = MssqlConnection::connect_with(&opts.ssl_mode(MssqlSslMode::Disabled)).await?;
+    assert_eq!(
+        conn.encryption_level().await?,
+        MssqlEncryptionLevel::NotEncrypted
+    );
 
-    // begin
-    let mut tx = conn.begin().await?;
+    Ok(())
+}
 
-    // insert a user
-    sqlx::query("INSERT INTO _sqlx_users_2523 (id) VALUES (@p1)")
-        .bind(50_i32)
-        .execute(&mut *tx)
-        .await?;
+#[sqlx_macros::test]
+async fn it_reads_the_request_info_of_another_session() -> anyhow::Result<()> {
+    let mut worker = new::<Mssql>().await?;
+    let mut diagnostics = new::<Mssql>().await?;
+
+    let session_id = worker.session_id().await?;
+    assert_ne!(session_id, diagnostics.session_id().await?);
+
+    // An idle session has no request.
+    assert!(diagnostics
+        .current_request_info(session_id)
+        .await?
+        .is_none());
+
+    let (waited, info) =
+        futures_util::future::join(worker.execute("WAITFOR DELAY '00:00:02'"), async {
+            sqlx_core::rt::sleep(Duration::from_millis(500)).await;
+            diagnostics.current_request_info(session_id).await
+        })
+        .await;
+    waited?;
 
-    // begin once more
-    let mut tx2 = tx.begin().await?;
+    let info = info?.expect("the WAITFOR request");
+    assert_eq!(info.session_id(), session_id);
+    assert_eq!(info.command(), "WAITFOR");
+    assert_eq!(info.status(), "suspended");
+    assert_eq!(info.wait_type(), Some("WAITFOR"));
+    assert_eq!(info.blocking_session_id(), None);
+    assert!(info.elapsed_time() >= Duration::from_millis(400));
+    assert_eq!(info.statement(), Some("WAITFOR DELAY '00:00:02'"));
 
-    // insert another user
-    sqlx::query("INSERT INTO _sqlx_users_2523 (id) VALUES (@p1)")
-        .bind(10_i32)
-        .execute(&mut *tx2)
-        .await?;
+    Ok(())
+}
 
-    // never mind, rollback
-    tx2.rollback().await?;
+#[sqlx_macros::test]
+async fn it_reads_the_last_deadlock_graph() -> anyhow::Result<()> {
+    use sqlx::mssql::MssqlDatabaseError;
 
-    // did we really?
-    let (count,): (i32,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_2523")
-        .fetch_one(&mut *tx)
-        .await?;
+    let mut conn = new::<Mssql>().await?;
 
-    assert_eq!(count, 1);
+    let allowed: i32 =
+        sqlx::query_scalar("SELECT HAS_PERMS_BY_NAME(NULL, NULL, 'VIEW SERVER STATE')")
+            .fetch_one(&mut conn)
+            .await?;
 
-    // actually, commit
-    tx.commit().await?;
+    if allowed == 0 {
+        let err = conn.last_deadlock_graph().await.unwrap_err();
+        let err = err.as_database_error().expect("a database error");
+        assert!(err
+            .downcast_ref::<MssqlDatabaseError>()
+            .is_permission_denied());
+        return Ok(());
+    }
 
-    // did we really?
-    let (count,): (i32,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_2523")
-        .fetch_one(&mut conn)
-        .await?;
+    conn.execute(
+        "IF OBJECT_ID('tempdb..##sqlx_deadlock_a') IS NULL \
+             CREATE TABLE ##sqlx_deadlock_a (id INT PRIMARY KEY, v INT); \
+         IF OBJECT_ID('tempdb..##sqlx_deadlock_b') IS NULL \
+             CREATE TABLE ##sqlx_deadlock_b (id INT PRIMARY KEY, v INT); \
+         DELETE FROM ##sqlx_deadlock_a; DELETE FROM ##sqlx_deadlock_b; \
+         INSERT INTO ##sqlx_deadlock_a VALUES (1, 0); INSERT INTO ##sqlx_deadlock_b VALUES (1, 0);",
+    )
+    .await?;
 
-    assert_eq!(count, 1);
+    // Each transaction locks one table, then waits for the other's.
+    let mut first = new::<Mssql>().await?;
+    let mut second = new::<Mssql>().await?;
+    let mut tx1 = first.begin().await?;
+    let mut tx2 = second.begin().await?;
+    tx1.execute("UPDATE ##sqlx_deadlock_a SET v = 1").await?;
+    tx2.execute("UPDATE ##sqlx_deadlock_b SET v = 2").await?;
+
+    let (r1, r2) = futures_util::future::join(
+        tx1.execute("UPDATE ##sqlx_deadlock_b SET v = 1"),
+        tx2.execute("UPDATE ##sqlx_deadlock_a SET v = 2"),
+    )
+    .await;
+    let victim = r1
+        .err()
+        .or(r2.err())
+        .expect("one transaction is the deadlock victim");
+    let victim = victim.as_database_error().expect("a database error");
+    assert_eq!(victim.code().as_deref(), Some("1205"));
+
+    // The event reaches the ring buffer asynchronously, so only the graph's shape is checked;
+    // it may still be an older deadlock, or none.
+    if let Some(graph) = conn.last_deadlock_graph().await? {
+        let graph: &str = graph.as_ref();
+        assert!(graph.starts_with("<deadlock"), "{graph}");
+        assert!(graph.contains("<victim-list>"), "{graph}");
+        assert!(graph.contains("<process-list>"), "{graph}");
+    }
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
+async fn it_executes_as_another_user() -> anyhow::Result<()> {
     let mut conn = new::<Mssql>().await?;
-    let mut tx = conn.begin().await?;
 
-    let tweet_id: i64 = sqlx::query_scalar(
-        "INSERT INTO tweet ( id, text ) OUTPUT INSERTED.id VALUES ( 50, 'Hello, World' )",
+    conn.execute(
+        "IF USER_ID('sqlx_execute_as') IS NULL CREATE USER sqlx_execute_as WITHOUT LOGIN; \
+         IF USER_ID('sqlx_execute_as_inner') IS NULL \
+             CREATE USER sqlx_execute_as_inner WITHOUT LOGIN; \
+         GRANT IMPERSONATE ON USER::sqlx_execute_as_inner TO sqlx_execute_as;",
     )
-    .fetch_one(&mut *tx)
     .await?;
 
-    let statement = tx
-        .prepare("SELECT * FROM tweet WHERE id = @p1".into_sql_str())
+    let original: String = sqlx::query_scalar("SELECT USER_NAME()")
+        .fetch_one(&mut conn)
         .await?;
 
-    assert_eq!(statement.column(0).name(), "id");
-    assert_eq!(statement.column(1).name(), "text");
+    let (outer, inner, restored) = conn
+        .execute_as("sqlx_execute_as", |conn| {
+            Box::pin(async move {
+                let outer: String = sqlx::query_scalar("SELECT USER_NAME()")
+                    .fetch_one(&mut *conn)
+                    .await?;
+
+                let inner: String = conn
+                    .execute_as("sqlx_execute_as_inner", |conn| {
+                        Box::pin(async move {
+                            sqlx::query_scalar("SELECT USER_NAME()")
+                                .fetch_one(&mut *conn)
+                                .await
+                        })
+                    })
+                    .await?;
+
+                let restored: String = sqlx::query_scalar("SELECT USER_NAME()")
+                    .fetch_one(&mut *conn)
+                    .await?;
+
+                Ok::<_, sqlx::Error>((outer, inner, restored))
+            })
+        })
+        .await?;
+
+    assert_eq!(outer, "sqlx_execute_as");
+    assert_eq!(inner, "sqlx_execute_as_inner");
+    assert_eq!(restored, "sqlx_execute_as");
+
+    // the impersonation is reverted even when the callback fails
+    let res: Result<(), sqlx::Error> = conn
+        .execute_as("sqlx_execute_as", |conn| {
+            Box::pin(async move {
+                conn.execute("SELECT 1 / 0").await?;
+                Ok(())
+            })
+        })
+        .await;
+    assert!(res.is_err());
+
+    let user: String = sqlx::query_scalar("SELECT USER_NAME()")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(user, original);
+
+    assert!(matches!(
+        conn.execute_as("", |_| Box::pin(async { Ok::<_, sqlx::Error>(()) }))
+            .await,
+        Err(sqlx::Error::InvalidArgument(_))
+    ));
+
+    conn.execute("DROP USER sqlx_execute_as_inner; DROP USER sqlx_execute_as;")
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_inserts_explicit_identity_values() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    conn.execute(
+        "CREATE TABLE #identity_insert (id INT IDENTITY(1, 1) PRIMARY KEY, name NVARCHAR(50))",
+    )
+    .await?;
+
+    let insert = "INSERT INTO #identity_insert (id, name) VALUES (@p1, @p2)";
+
+    conn.with_identity_insert("#identity_insert", |conn| {
+        Box::pin(async move {
+            sqlx::query(insert)
+                .bind(42_i32)
+                .bind("alice")
+                .execute(&mut *conn)
+                .await
+        })
+    })
+    .await?;
+
+    let name: String = sqlx::query_scalar("SELECT name FROM #identity_insert WHERE id = 42")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(name, "alice");
+
+    // IDENTITY_INSERT is switched off again, even when the callback fails
+    let res: Result<(), sqlx::Error> = conn
+        .with_identity_insert("#identity_insert", |conn| {
+            Box::pin(async move {
+                sqlx::query(insert)
+                    .bind(42_i32)
+                    .bind("duplicate")
+                    .execute(&mut *conn)
+                    .await?;
+                Ok(())
+            })
+        })
+        .await;
+    assert!(res.is_err());
+
+    assert!(sqlx::query(insert)
+        .bind(43_i32)
+        .bind("bob")
+        .execute(&mut conn)
+        .await
+        .is_err());
+
+    // ... and when it panics
+    let res = AssertUnwindSafe(
+        conn.with_identity_insert::<_, (), sqlx::Error>("#identity_insert", |_| {
+            Box::pin(async { panic!("callback panicked") })
+        }),
+    )
+    .catch_unwind()
+    .await;
+    assert!(res.is_err());
+
+    assert!(sqlx::query(insert)
+        .bind(44_i32)
+        .bind("carol")
+        .execute(&mut conn)
+        .await
+        .is_err());
+
+    assert!(matches!(
+        conn.with_identity_insert("#identity_insert; DROP TABLE x", |_| Box::pin(async {
+            Ok::<_, sqlx::Error>(())
+        }))
+        .await,
+        Err(sqlx::Error::InvalidArgument(_))
+    ));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reads_last_rowcount() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    conn.execute("CREATE TABLE #rowcount (id INT PRIMARY KEY)")
+        .await?;
+
+    // the batch touches 4 rows in total, but its last statement only 1
+    conn.execute(
+        "INSERT INTO #rowcount (id) VALUES (1), (2), (3); DELETE FROM #rowcount WHERE id = 1",
+    )
+    .await?;
+    assert_eq!(conn.last_rowcount().await?, 1);
+
+    // captured mid-batch, @@ROWCOUNT reflects the statement right before it
+    let mid_batch: i64 = sqlx::query_scalar(
+        "UPDATE #rowcount SET id = id + 10; SELECT ROWCOUNT_BIG(); DELETE FROM #rowcount",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+    assert_eq!(mid_batch, 2);
+    assert_eq!(conn.last_rowcount().await?, 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reads_last_rowcount_past_appended_and_pending_queries() -> anyhow::Result<()> {
+    let opts: MssqlConnectOptions = dotenvy::var("DATABASE_URL")?.parse()?;
+    let mut conn = MssqlConnection::connect_with(&opts.capture_last_insert_id(true)).await?;
+
+    conn.execute("CREATE TABLE #rowcount (id INT IDENTITY PRIMARY KEY, n INT)")
+        .await?;
+
+    // not the 1 of the `SELECT` appended to capture the identity
+    conn.execute("INSERT INTO #rowcount (n) VALUES (1), (2), (3)")
+        .await?;
+    assert_eq!(conn.last_rowcount().await?, 3);
+
+    sqlx::query("INSERT INTO #rowcount (n) VALUES (@p1), (@p2)")
+        .bind(4_i32)
+        .bind(5_i32)
+        .execute(&mut conn)
+        .await?;
+    assert_eq!(conn.last_rowcount().await?, 2);
+
+    // a later statement that isn't extended replaces the captured count
+    conn.execute("UPDATE #rowcount SET n = n + 1 WHERE n > 1")
+        .await?;
+    assert_eq!(conn.last_rowcount().await?, 4);
+
+    // read before the `ROLLBACK` of the dropped transaction
+    let mut tx = conn.begin().await?;
+    tx.execute("DELETE FROM #rowcount WHERE n > 3").await?;
+    drop(tx);
+    assert_eq!(conn.last_rowcount().await?, 3);
+
+    let count: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM #rowcount")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 5);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_streams_rows_as_they_arrive() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    // Far more rows than are worth buffering; only the first few are read.
+    let sql = "SELECT TOP 500000 ROW_NUMBER() OVER (ORDER BY (SELECT NULL)) AS n \
+               FROM sys.all_objects a CROSS JOIN sys.all_objects b CROSS JOIN sys.all_objects c";
+
+    {
+        let mut rows = sqlx::query_scalar::<_, i64>(sql).fetch(&mut conn);
+        for expected in 1..=10 {
+            assert_eq!(rows.try_next().await?, Some(expected));
+        }
+    }
+
+    // The rest of the response is discarded and the connection stays usable.
+    let value: i32 = sqlx::query_scalar("SELECT 42").fetch_one(&mut conn).await?;
+    assert_eq!(value, 42);
+
+    // Result sets of a batch are still separated by their query results.
+    let steps = conn
+        .fetch_many("SELECT 1 AS a UNION ALL SELECT 2; SELECT 3 AS b;")
+        .try_collect::<Vec<_>>()
+        .await?;
+    let shape: Vec<Option<u64>> = steps
+        .iter()
+        .map(|step| step.as_ref().left().map(|result| result.rows_affected()))
+        .collect();
+    assert_eq!(shape, [None, None, Some(2), None, Some(1)]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rolls_back_dropped_transaction_before_streaming() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    conn.execute("CREATE TABLE #stream_rollback (id INT NOT NULL)")
+        .await?;
+
+    {
+        let mut tx = conn.begin().await?;
+        tx.execute("INSERT INTO #stream_rollback (id) VALUES (1)")
+            .await?;
+        // dropped without commit
+    }
+
+    let ids: Vec<i32> = sqlx::query_scalar::<_, i32>("SELECT id FROM #stream_rollback")
+        .fetch(&mut conn)
+        .try_collect()
+        .await?;
+    assert!(ids.is_empty());
+
+    let open: i32 = sqlx::query_scalar("SELECT @@TRANCOUNT")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(open, 0);
+
+    Ok(())
+}
+
+/// Resident set size of this process, in bytes.
+#[cfg(target_os = "linux")]
+fn resident_memory() -> anyhow::Result<u64> {
+    let statm = std::fs::read_to_string("/proc/self/statm")?;
+    let pages: u64 = statm
+        .split_whitespace()
+        .nth(1)
+        .ok_or_else(|| anyhow::anyhow!("unexpected /proc/self/statm: {statm}"))?
+        .parse()?;
+    Ok(pages * 4096)
+}
+
+#[sqlx_macros::test]
+async fn it_executes_batches_discarding_rows() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    #[cfg(target_os = "linux")]
+    let before = resident_memory()?;
+
+    // About 400 MB of row data if it were collected.
+    let affected = conn
+        .execute_discard(
+            "CREATE TABLE #discard (id INT NOT NULL); \
+             INSERT INTO #discard (id) VALUES (1), (2), (3); \
+             SELECT TOP 200000 REPLICATE(N'x', 1000) AS payload \
+             FROM sys.all_objects a CROSS JOIN sys.all_objects b; \
+             SELECT id FROM #discard;",
+        )
+        .await?;
+
+    // A plain batch counts the rows of each result set.
+    assert_eq!(affected, 200_003);
+
+    #[cfg(target_os = "linux")]
+    {
+        let growth = resident_memory()?.saturating_sub(before);
+        assert!(growth < 64 * 1024 * 1024, "grew by {growth} bytes");
+    }
+
+    let count: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM #discard")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 3);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_binds_id_lists_as_json() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    let sql = "SELECT n FROM (VALUES (1), (2), (3)) AS t(n) \
+               WHERE n IN (SELECT CAST(value AS INT) FROM OPENJSON(@p1)) ORDER BY n";
+
+    for (ids, expected) in [
+        (Some("[]"), vec![]),
+        (None, vec![]),
+        (Some("[2]"), vec![2]),
+        (Some("[3, 1, 4]"), vec![1, 3]),
+    ] {
+        let found: Vec<i32> = sqlx::query_scalar(sql)
+            .bind(ids)
+            .fetch_all(&mut conn)
+            .await?;
+        assert_eq!(found, expected, "{ids:?}");
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_binds_id_lists_with_string_split() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    conn.execute(
+        "CREATE TABLE #split_ids (id INT NOT NULL PRIMARY KEY); \
+         INSERT INTO #split_ids (id) VALUES (1), (2), (3), (4);",
+    )
+    .await?;
+
+    for (ids, expected) in [
+        (vec![], vec![]),
+        (vec![2], vec![2]),
+        (vec![4, 1, 9], vec![1, 4]),
+    ] {
+        let mut qb = QueryBuilder::<Mssql>::new("SELECT id FROM #split_ids WHERE ");
+        qb.push_string_split_in("id", &ids, ',')?;
+        qb.push(" ORDER BY id");
+
+        let found: Vec<i32> = qb.build_query_scalar().fetch_all(&mut conn).await?;
+        assert_eq!(found, expected, "{ids:?}");
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[sqlx_macros::test]
+async fn it_joins_against_typed_rows_with_openjson() -> anyhow::Result<()> {
+    use sqlx::mssql::MssqlJsonColumn;
+
+    #[derive(serde::Serialize)]
+    struct Filter {
+        id: i32,
+        name: String,
+    }
+
+    let mut conn = new::<Mssql>().await?;
+
+    conn.execute(
+        "CREATE TABLE #openjson_users (id INT PRIMARY KEY, name NVARCHAR(50) NOT NULL); \
+         INSERT INTO #openjson_users (id, name) VALUES (1, N'alice'), (2, N'bob'), (3, N'carol');",
+    )
+    .await?;
+
+    let columns = [
+        MssqlJsonColumn::of::<i32>("id")?,
+        MssqlJsonColumn::new("name", "NVARCHAR(50)")?,
+    ];
+
+    for (filters, expected) in [
+        (vec![], vec![]),
+        (
+            vec![
+                Filter {
+                    id: 3,
+                    name: "carol".into(),
+                },
+                Filter {
+                    id: 2,
+                    name: "not bob".into(),
+                },
+                Filter {
+                    id: 1,
+                    name: "alice".into(),
+                },
+            ],
+            vec![1, 3],
+        ),
+    ] {
+        let mut qb = QueryBuilder::<Mssql>::new("SELECT u.id FROM #openjson_users u JOIN ");
+        qb.push_openjson(&filters, &columns)?;
+        qb.push(" AS f ON f.id = u.id AND f.name = u.name ORDER BY u.id");
+
+        let found: Vec<i32> = qb.build_query_scalar().fetch_all(&mut conn).await?;
+        assert_eq!(found, expected);
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_passes_json_rows_as_a_table_valued_parameter() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    conn.execute_discard(
+        "IF TYPE_ID('dbo.sqlx_test_id_list') IS NULL \
+         CREATE TYPE dbo.sqlx_test_id_list AS TABLE (id INT NOT NULL PRIMARY KEY);",
+    )
+    .await?;
+    conn.execute_discard(
+        "CREATE OR ALTER PROCEDURE dbo.sqlx_test_sum_ids @ids dbo.sqlx_test_id_list READONLY \
+         AS SELECT COUNT(*) AS n, SUM(id) AS total FROM @ids;",
+    )
+    .await?;
+
+    let (n, total): (i32, i32) = sqlx::query_as(
+        "DECLARE @ids dbo.sqlx_test_id_list; \
+         INSERT INTO @ids (id) SELECT id FROM OPENJSON(@p1) WITH (id INT '$'); \
+         EXEC dbo.sqlx_test_sum_ids @ids;",
+    )
+    .bind("[1, 2, 3, 10]")
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!((n, total), (4, 16));
+
+    conn.execute_discard("DROP PROCEDURE dbo.sqlx_test_sum_ids")
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_calls_procedures_with_output_parameters() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    conn.execute_discard(
+        "CREATE OR ALTER PROCEDURE dbo.sqlx_test_outputs \
+             @n INT, @doubled INT OUTPUT, @label NVARCHAR(20) OUTPUT \
+         AS BEGIN \
+             SELECT @n AS n; \
+             SET @doubled = @n * 2; \
+             SET @label = @label + N'!'; \
+             RETURN 7; \
+         END",
+    )
+    .await?;
+
+    let params = MssqlProcedureParams::new()
+        .bind(21_i32)
+        .bind_output("INT")
+        .bind_in_out("NVARCHAR(20)", "done");
+
+    let result = conn.call_procedure("dbo.sqlx_test_outputs", params).await?;
+
+    assert_eq!(result.return_value(), 7);
+    assert_eq!(result.output::<i32>(0)?, 42);
+    assert_eq!(result.output::<String>(1)?, "done!");
+    assert!(result.output::<i32>(2).is_err());
+
+    let sets = result.into_result_sets();
+    assert_eq!(sets.len(), 1);
+    assert_eq!(sets[0][0].try_get::<i32, _>("n")?, 21);
+
+    conn.execute_discard("DROP PROCEDURE dbo.sqlx_test_outputs")
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_with_stats() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    conn.execute(
+        "CREATE TABLE #stats_scan (id INT NOT NULL, payload NVARCHAR(200) NOT NULL); \
+         INSERT INTO #stats_scan (id, payload) \
+         SELECT TOP 1000 ROW_NUMBER() OVER (ORDER BY (SELECT NULL)), REPLICATE(N'x', 200) \
+         FROM sys.all_objects a CROSS JOIN sys.all_objects b",
+    )
+    .await?;
+
+    let (rows, stats) = conn
+        .fetch_with_stats("SELECT id, payload FROM #stats_scan")
+        .await?;
+
+    assert_eq!(rows.len(), 1000);
+    assert!(stats.logical_reads() > 0, "{stats:?}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_inspect_errors() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    let res: Result<_, sqlx::Error> = sqlx::query("select f").execute(&mut conn).await;
+    let err = res.unwrap_err();
+
+    // can also do [as_database_error] or use `match ..`
+    let err = err.into_database_error().unwrap();
+
+    assert_eq!(err.message(), "Invalid column name 'f'.");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_maths() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    let value = sqlx::query("SELECT 1 + @p1")
+        .bind(5_i32)
+        .try_map(|row: MssqlRow| row.try_get::<i32, _>(0))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(6_i32, value);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    let _ = conn
+        .execute(
+            r#"
+CREATE TABLE #users (id INTEGER PRIMARY KEY);
+            "#,
+        )
+        .await?;
+
+    for index in 1..=10_i32 {
+        let done = sqlx::query("INSERT INTO #users (id) VALUES (@p1)")
+            .bind(index * 2)
+            .execute(&mut conn)
+            .await?;
+
+        assert_eq!(done.rows_affected(), 1);
+    }
+
+    let sum: i32 = sqlx::query("SELECT id FROM #users")
+        .try_map(|row: MssqlRow| row.try_get::<i32, _>(0))
+        .fetch(&mut conn)
+        .try_fold(0_i32, |acc, x| async move { Ok(acc + x) })
+        .await?;
+
+    assert_eq!(sum, 110);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_return_1000_rows() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    let _ = conn
+        .execute(
+            r#"
+CREATE TABLE #users (id INTEGER PRIMARY KEY);
+            "#,
+        )
+        .await?;
+
+    for index in 1..=1000_i32 {
+        let done = sqlx::query("INSERT INTO #users (id) VALUES (@p1)")
+            .bind(index * 2)
+            .execute(&mut conn)
+            .await?;
+
+        assert_eq!(done.rows_affected(), 1);
+    }
+
+    let sum: i32 = sqlx::query("SELECT id FROM #users")
+        .try_map(|row: MssqlRow| row.try_get::<i32, _>(0))
+        .fetch(&mut conn)
+        .try_fold(0_i32, |acc, x| async move { Ok(acc + x) })
+        .await?;
+
+    assert_eq!(sum, 1001000);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_selects_null() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    let (_, val): (i32, Option<i32>) = sqlx::query_as("SELECT 5, NULL")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(val.is_none());
+
+    let val: Option<i32> = conn.fetch_one("SELECT 10, NULL").await?.try_get(1)?;
+
+    assert!(val.is_none());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_binds_empty_string_and_null() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    let (val, val2): (String, Option<String>) = sqlx::query_as("SELECT @p1, @p2")
+        .bind("")
+        .bind(None::<String>)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(val.is_empty());
+    assert!(val2.is_none());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_work_with_transactions() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    conn.execute("IF OBJECT_ID('_sqlx_users_1922', 'U') IS NULL CREATE TABLE _sqlx_users_1922 (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    conn.execute("DELETE FROM _sqlx_users_1922").await?;
+
+    // begin .. rollback
+
+    let mut tx = conn.begin().await?;
+
+    sqlx::query("INSERT INTO _sqlx_users_1922 (id) VALUES (@p1)")
+        .bind(10_i32)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.rollback().await?;
+
+    let (count,): (i32,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_1922")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    // begin .. commit
+
+    let mut tx = conn.begin().await?;
+
+    sqlx::query("INSERT INTO _sqlx_users_1922 (id) VALUES (@p1)")
+        .bind(10_i32)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    let (count,): (i32,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_1922")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 1);
+
+    // begin .. (drop)
+
+    {
+        let mut tx = conn.begin().await?;
+
+        sqlx::query("INSERT INTO _sqlx_users_1922 (id) VALUES (@p1)")
+            .bind(20_i32)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    conn = new::<Mssql>().await?;
+
+    let (count,): (i32,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_1922")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_work_with_nested_transactions() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    conn.execute("IF OBJECT_ID('_sqlx_users_2523', 'U') IS NULL CREATE TABLE _sqlx_users_2523 (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    conn.execute("DELETE FROM _sqlx_users_2523").await?;
+
+    // begin
+    let mut tx = conn.begin().await?;
+
+    // insert a user
+    sqlx::query("INSERT INTO _sqlx_users_2523 (id) VALUES (@p1)")
+        .bind(50_i32)
+        .execute(&mut *tx)
+        .await?;
+
+    // begin once more
+    let mut tx2 = tx.begin().await?;
+
+    // insert another user
+    sqlx::query("INSERT INTO _sqlx_users_2523 (id) VALUES (@p1)")
+        .bind(10_i32)
+        .execute(&mut *tx2)
+        .await?;
+
+    // never mind, rollback
+    tx2.rollback().await?;
+
+    // did we really?
+    let (count,): (i32,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_2523")
+        .fetch_one(&mut *tx)
+        .await?;
+
+    assert_eq!(count, 1);
+
+    // actually, commit
+    tx.commit().await?;
+
+    // did we really?
+    let (count,): (i32,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_2523")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_rollback_all_nested_transactions() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    conn.execute("CREATE TABLE #rollback_all (id INT PRIMARY KEY)")
+        .await?;
+
+    {
+        let mut tx = conn.begin().await?;
+
+        sqlx::query("INSERT INTO #rollback_all (id) VALUES (1)")
+            .execute(&mut *tx)
+            .await?;
+
+        let mut tx2 = tx.begin().await?;
+        let mut tx3 = tx2.begin().await?;
+
+        sqlx::query("INSERT INTO #rollback_all (id) VALUES (2)")
+            .execute(&mut *tx3)
+            .await?;
+
+        tx3.rollback_all().await?;
+        assert!(!tx3.is_in_transaction());
+
+        // the guards of the rolled-back transaction can't report it as committed
+        tx3.rollback().await?;
+        assert!(matches!(tx2.commit().await, Err(sqlx::Error::Protocol(_))));
+        assert!(matches!(tx.commit().await, Err(sqlx::Error::Protocol(_))));
+    }
+
+    assert!(!conn.is_in_transaction());
+
+    // once they are gone, transactions commit as usual
+    let mut tx = conn.begin().await?;
+    sqlx::query("INSERT INTO #rollback_all (id) VALUES (3)")
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    sqlx::query("DELETE FROM #rollback_all")
+        .execute(&mut conn)
+        .await?;
+
+    let trancount: i32 = sqlx::query_scalar("SELECT @@TRANCOUNT")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(trancount, 0);
+
+    let count: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM #rollback_all")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_roll_back_to_named_savepoints() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    conn.execute("CREATE TABLE #savepoints (id INT PRIMARY KEY)")
+        .await?;
+
+    async fn ids(conn: &mut MssqlConnection) -> anyhow::Result<Vec<i32>> {
+        Ok(sqlx::query_scalar("SELECT id FROM #savepoints ORDER BY id")
+            .fetch_all(conn)
+            .await?)
+    }
+
+    let err = conn.savepoint("outside").await.unwrap_err();
+    assert!(matches!(err, sqlx::Error::InvalidArgument(_)), "{err:?}");
+
+    let mut tx = conn.begin().await?;
+    tx.execute("INSERT INTO #savepoints VALUES (1)").await?;
+    tx.savepoint("after one").await?;
+    tx.execute("INSERT INTO #savepoints VALUES (2)").await?;
+    tx.savepoint("after two").await?;
+    tx.execute("INSERT INTO #savepoints VALUES (3)").await?;
+
+    // the savepoint survives a rollback to it, later ones are forgotten
+    tx.rollback_to_savepoint("after one").await?;
+    assert_eq!(ids(&mut tx).await?, [1]);
+    assert!(tx.rollback_to_savepoint("after two").await.is_err());
+
+    tx.execute("INSERT INTO #savepoints VALUES (4)").await?;
+    tx.rollback_to_savepoint("after one").await?;
+    assert_eq!(ids(&mut tx).await?, [1]);
+
+    {
+        // a nested transaction can't roll back past its own start
+        let mut nested = tx.begin().await?;
+        nested.execute("INSERT INTO #savepoints VALUES (5)").await?;
+        let err = nested.rollback_to_savepoint("after one").await.unwrap_err();
+        assert!(matches!(err, sqlx::Error::InvalidArgument(_)), "{err:?}");
+
+        nested.savepoint("nested").await?;
+        nested.execute("INSERT INTO #savepoints VALUES (6)").await?;
+        nested.rollback_to_savepoint("nested").await?;
+        nested.commit().await?;
+    }
+
+    // the nested transaction's savepoint ended with it; the depth is unchanged
+    assert!(tx.rollback_to_savepoint("nested").await.is_err());
+    let trancount: i32 = sqlx::query_scalar("SELECT @@TRANCOUNT")
+        .fetch_one(&mut *tx)
+        .await?;
+    assert_eq!(trancount, 1);
+    tx.commit().await?;
+
+    assert!(!conn.is_in_transaction());
+    assert_eq!(ids(&mut conn).await?, [1, 5]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_scopes_begin_with_options_settings_to_the_transaction() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    async fn settings(conn: &mut MssqlConnection) -> anyhow::Result<(i32, i32, i16)> {
+        Ok(sqlx::query_as(
+            "SELECT @@LOCK_TIMEOUT, deadlock_priority, transaction_isolation_level \
+             FROM sys.dm_exec_sessions WHERE session_id = @@SPID",
+        )
+        .fetch_one(conn)
+        .await?)
+    }
+
+    conn.execute("SET DEADLOCK_PRIORITY 3").await?;
+    let before = settings(&mut conn).await?;
+
+    let mut tx = conn
+        .begin_with_options(
+            Some(MssqlIsolationLevel::Serializable),
+            Some(Duration::from_millis(1500)),
+            Some(-5),
+        )
+        .await?;
+    assert_eq!(settings(&mut tx).await?, (1500, -5, 4));
+
+    let err = tx
+        .begin_with_options(None, None, Some(5))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, sqlx::Error::InvalidSavePointStatement),
+        "{err:?}"
+    );
+    tx.commit().await?;
+    assert_eq!(settings(&mut conn).await?, before);
+
+    // only the settings that were given are changed and restored
+    let mut tx = conn
+        .begin_with_options(None, Some(Duration::ZERO), None)
+        .await?;
+    assert_eq!(settings(&mut tx).await?, (0, before.1, before.2));
+    tx.rollback().await?;
+    assert_eq!(settings(&mut conn).await?, before);
+
+    // a dropped guard restores them along with its rollback
+    {
+        let _tx = conn.begin_with_options(None, None, Some(10)).await?;
+    }
+    assert_eq!(settings(&mut conn).await?, before);
+    assert!(!conn.is_in_transaction());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_retries_a_failed_settings_reset_after_commit() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    let isolation = "SELECT transaction_isolation_level FROM sys.dm_exec_sessions \
+                     WHERE session_id = @@SPID";
+
+    conn.execute("SET TRANSACTION ISOLATION LEVEL SNAPSHOT")
+        .await?;
+
+    let mut tx = conn
+        .begin_with_options(Some(MssqlIsolationLevel::ReadCommitted), None, None)
+        .await?;
+
+    // Leave a transaction open on the server after `tx` ends. Restoring SNAPSHOT inside a
+    // transaction that started under another isolation level fails.
+    tx.execute("BEGIN TRANSACTION; SELECT COUNT(*) FROM sys.objects")
+        .await?;
+
+    // The commit succeeded, so the failed reset is not reported as its error
+    tx.commit().await?;
+    assert!(!conn.is_in_transaction());
+
+    conn.execute("COMMIT").await?;
+
+    // Once no transaction is open, the pending reset runs before the next request
+    let level: i16 = sqlx::query_scalar(isolation).fetch_one(&mut conn).await?;
+    assert_eq!(level, 5);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_keeps_transaction_open_after_failed_commit() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    conn.execute("SET XACT_ABORT ON").await?;
+
+    let mut tx = conn.begin().await?;
+
+    // doom the transaction: it can no longer commit, but stays open until rolled back
+    tx.execute("BEGIN TRY SELECT CAST('x' AS INT) END TRY BEGIN CATCH END CATCH")
+        .await?;
+
+    assert!(tx.commit().await.is_err());
+
+    // the failed COMMIT left the transaction open, so dropping the guard rolled it back
+    assert!(!conn.is_in_transaction());
+
+    let trancount: i32 = sqlx::query_scalar("SELECT @@TRANCOUNT")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(trancount, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_syncs_transaction_depth_after_server_rollback() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    conn.execute("SET XACT_ABORT ON").await?;
+
+    let mut tx = conn.begin().await?;
+
+    // under XACT_ABORT the server rolls back the whole transaction on error
+    assert!(tx.execute("SELECT CAST('x' AS INT)").await.is_err());
+    assert!(tx.commit().await.is_err());
+
+    assert!(!conn.is_in_transaction());
+
+    // no stray ROLLBACK is issued on the next query
+    let trancount: i32 = sqlx::query_scalar("SELECT @@TRANCOUNT")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(trancount, 0);
+
+    Ok(())
+}
+
+// requires MSDTC to be enabled on the server
+#[ignore]
+#[sqlx_macros::test]
+async fn it_can_rollback_distributed_transaction_across_databases() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    conn.execute(
+        "IF OBJECT_ID('tempdb.dbo._sqlx_dtc', 'U') IS NULL \
+         CREATE TABLE tempdb.dbo._sqlx_dtc (id INT PRIMARY KEY); \
+         DELETE FROM tempdb.dbo._sqlx_dtc; \
+         IF OBJECT_ID('_sqlx_dtc', 'U') IS NULL CREATE TABLE _sqlx_dtc (id INT PRIMARY KEY); \
+         DELETE FROM _sqlx_dtc",
+    )
+    .await?;
+
+    let mut tx = conn.begin_distributed().await?;
+    assert!(tx.is_in_transaction());
+
+    tx.execute("INSERT INTO _sqlx_dtc (id) VALUES (1)").await?;
+    tx.execute("INSERT INTO tempdb.dbo._sqlx_dtc (id) VALUES (1)")
+        .await?;
+
+    tx.rollback().await?;
+    assert!(!conn.is_in_transaction());
+
+    let count: i32 = sqlx::query_scalar(
+        "SELECT (SELECT COUNT(*) FROM _sqlx_dtc) + (SELECT COUNT(*) FROM tempdb.dbo._sqlx_dtc)",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+    let mut tx = conn.begin().await?;
+
+    let tweet_id: i64 = sqlx::query_scalar(
+        "INSERT INTO tweet ( id, text ) OUTPUT INSERTED.id VALUES ( 50, 'Hello, World' )",
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let statement = tx
+        .prepare("SELECT * FROM tweet WHERE id = @p1".into_sql_str())
+        .await?;
+
+    assert_eq!(statement.column(0).name(), "id");
+    assert_eq!(statement.column(1).name(), "text");
     assert_eq!(statement.column(2).name(), "is_sent");
     assert_eq!(statement.column(3).name(), "owner_id");
 
@@ -331,6 +1679,49 @@ async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_prepares_many_statements_in_one_call() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+    conn.clear_cached_statements().await?;
+
+    let mut sqls: Vec<_> = (0..9)
+        .map(|i| {
+            sqlx::AssertSqlSafe(format!("SELECT CAST({i} AS INT) AS c{i}, N'x' AS s"))
+                .into_sql_str()
+        })
+        .collect();
+    sqls.insert(4, "SELECT * FROM sqlx_missing_table".into_sql_str());
+
+    let statements = conn.prepare_many(&sqls).await?;
+    assert_eq!(statements.len(), 10);
+
+    let mut expected = 0;
+    for (sql, statement) in sqls.iter().zip(&statements) {
+        if sql.as_str().contains("sqlx_missing_table") {
+            assert!(
+                matches!(statement, Err(sqlx::Error::Database(_))),
+                "{statement:?}"
+            );
+            continue;
+        }
+
+        let statement = statement.as_ref().unwrap();
+        assert_eq!(statement.sql().as_str(), sql.as_str());
+        assert_eq!(statement.columns().len(), 2);
+        assert_eq!(statement.column(0).name(), format!("c{expected}"));
+        assert_eq!(statement.column(0).type_info().name(), "INT");
+        expected += 1;
+    }
+    assert_eq!(expected, 9);
+
+    // The statements that could be described are served from the cache afterwards
+    assert_eq!(conn.cached_statements_size(), 9);
+    let statement = conn.prepare(sqls[0].clone()).await?;
+    assert_eq!(statement.column(0).name(), "c0");
+
+    Ok(())
+}
+
 // MSSQL-specific copy of the test case in `tests/any/pool.rs`
 // because MSSQL has its own bespoke syntax for temporary tables.
 #[sqlx_macros::test]
@@ -394,110 +1785,565 @@ async fn test_pool_callbacks() -> anyhow::Result<()> {
                     .fetch_one(conn)
                     .await?;
 
-                // For even IDs, cap by the number of before_acquire calls.
-                // Ignore the check for odd IDs.
-                Ok((stats.id & 1) == 1 || stats.before_acquire_calls < 3)
-            })
-        })
-        .after_release(|conn, meta| {
-            // `age` should be nonzero but `idle_for` should be zero.
-            assert_ne!(meta.age, Duration::ZERO);
-            assert_eq!(meta.idle_for, Duration::ZERO);
+                // For even IDs, cap by the number of before_acquire calls.
+                // Ignore the check for odd IDs.
+                Ok((stats.id & 1) == 1 || stats.before_acquire_calls < 3)
+            })
+        })
+        .after_release(|conn, meta| {
+            // `age` should be nonzero but `idle_for` should be zero.
+            assert_ne!(meta.age, Duration::ZERO);
+            assert_eq!(meta.idle_for, Duration::ZERO);
+
+            Box::pin(async move {
+                sqlx::query(
+                    r#"
+                        UPDATE #conn_stats
+                        SET after_release_calls = after_release_calls + 1
+                    "#,
+                )
+                .execute(&mut *conn)
+                .await?;
+
+                let stats: ConnStats = sqlx::query_as("SELECT * FROM #conn_stats")
+                    .fetch_one(conn)
+                    .await?;
+
+                // For odd IDs, cap by the number of before_release calls.
+                // Ignore the check for even IDs.
+                Ok((stats.id & 1) == 0 || stats.after_release_calls < 4)
+            })
+        })
+        // Don't establish a connection yet.
+        .connect_lazy(&std::env::var("DATABASE_URL")?)?;
+
+    // Expected pattern of (id, before_acquire_calls, after_release_calls)
+    let pattern = [
+        // The connection pool starts empty.
+        (0, 0, 0),
+        (0, 1, 1),
+        (0, 2, 2),
+        (1, 0, 0),
+        (1, 1, 1),
+        (1, 2, 2),
+        // We should expect one more `acquire` because the ID is odd
+        (1, 3, 3),
+        (2, 0, 0),
+        (2, 1, 1),
+        (2, 2, 2),
+        (3, 0, 0),
+    ];
+
+    for (id, before_acquire_calls, after_release_calls) in pattern {
+        let conn_stats: ConnStats = sqlx::query_as("SELECT * FROM #conn_stats")
+            .fetch_one(&pool)
+            .await?;
+
+        assert_eq!(
+            conn_stats,
+            ConnStats {
+                id,
+                before_acquire_calls,
+                after_release_calls
+            }
+        );
+    }
+
+    pool.close().await;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_rows_affected_per_statement() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    conn.execute("CREATE TABLE #per_statement (id INT)").await?;
+
+    let batch = "INSERT INTO #per_statement (id) VALUES (1), (2), (3); \
+                 UPDATE #per_statement SET id = id + @p1 WHERE id > 1; \
+                 DELETE FROM #per_statement WHERE id = @p2";
+
+    let counts: Vec<u64> = conn
+        .execute_many(sqlx::query(batch).bind(10_i32).bind(1_i32))
+        .map_ok(|result| result.rows_affected())
+        .try_collect()
+        .await?;
+    assert_eq!(counts, [3, 2, 1]);
+
+    // `execute` sums the counts of the batch
+    conn.execute("TRUNCATE TABLE #per_statement").await?;
+    let result = sqlx::query(batch)
+        .bind(10_i32)
+        .bind(1_i32)
+        .execute(&mut conn)
+        .await?;
+    assert_eq!(result.rows_affected(), 6);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_captures_the_last_insert_id() -> anyhow::Result<()> {
+    let opts: MssqlConnectOptions = dotenvy::var("DATABASE_URL")?.parse()?;
+    let mut conn = MssqlConnection::connect_with(&opts.capture_last_insert_id(true)).await?;
+
+    conn.execute(
+        "CREATE TABLE #with_identity (id BIGINT IDENTITY(100, 1) PRIMARY KEY, name NVARCHAR(10))",
+    )
+    .await?;
+    conn.execute("CREATE TABLE #without_identity (name NVARCHAR(10))")
+        .await?;
+
+    let result = conn
+        .execute("INSERT INTO #with_identity (name) VALUES ('a')")
+        .await?;
+    assert_eq!(result.rows_affected(), 1);
+    assert_eq!(result.last_insert_id(), Some(100));
+
+    let result = sqlx::query("INSERT INTO #with_identity (name) VALUES (@p1), (@p2)")
+        .bind("b")
+        .bind("c")
+        .execute(&mut conn)
+        .await?;
+    assert_eq!(result.rows_affected(), 2);
+    assert_eq!(result.last_insert_id(), Some(102));
+
+    // Rows returned by `OUTPUT` are still counted once
+    let result = conn
+        .execute("INSERT INTO #with_identity (name) OUTPUT INSERTED.id VALUES ('d')")
+        .await?;
+    assert_eq!(result.rows_affected(), 1);
+    assert_eq!(result.last_insert_id(), Some(103));
+
+    // No identity column: `SCOPE_IDENTITY()` is NULL, not 0
+    let result = sqlx::query("INSERT INTO #without_identity (name) VALUES (@p1)")
+        .bind("e")
+        .execute(&mut conn)
+        .await?;
+    assert_eq!(result.rows_affected(), 1);
+    assert_eq!(result.last_insert_id(), None);
+
+    // Other statements are not extended
+    let result = conn.execute("DELETE FROM #without_identity").await?;
+    assert_eq!(result.last_insert_id(), None);
+
+    // The extra result set is not visible to `fetch`
+    let ids: Vec<i64> = sqlx::query_scalar(
+        "INSERT INTO #with_identity (name) OUTPUT INSERTED.id VALUES ('f'), ('g')",
+    )
+    .fetch_all(&mut conn)
+    .await?;
+    assert_eq!(ids, [104, 105]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_query_multiple_result_sets() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    // A batch that produces two result sets
+    let results = conn
+        .fetch_many("SELECT 1 AS a; SELECT 2 AS b, 3 AS c;")
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    // First result set: one row with column "a"
+    let mut rows_first = Vec::new();
+    let mut rows_second = Vec::new();
+    let mut result_count = 0;
+
+    for item in &results {
+        match item {
+            sqlx::Either::Left(_) => {
+                result_count += 1;
+            }
+            sqlx::Either::Right(row) => {
+                if result_count == 0 {
+                    rows_first.push(row);
+                } else {
+                    rows_second.push(row);
+                }
+            }
+        }
+    }
+
+    assert_eq!(rows_first.len(), 1);
+    assert_eq!(rows_first[0].try_get::<i32, _>("a")?, 1);
+
+    assert_eq!(rows_second.len(), 1);
+    assert_eq!(rows_second[0].try_get::<i32, _>("b")?, 2);
+    assert_eq!(rows_second[0].try_get::<i32, _>("c")?, 3);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_fetch_result_sets_separately() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    let sets = conn
+        .fetch_result_sets(
+            "SELECT 1 AS a UNION ALL SELECT 2; SELECT 3 AS b WHERE 1 = 0; SELECT 4 AS c",
+        )
+        .await?;
+
+    let lens: Vec<usize> = sets.iter().map(Vec::len).collect();
+    assert_eq!(lens, [2, 0, 1]);
+    assert_eq!(sets[2][0].try_get::<i32, _>("c")?, 4);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_skips_statements_without_result_sets() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    let rows = conn.fetch_all("SET NOCOUNT ON; SELECT 1;").await?;
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].try_get::<i32, _>(0)?, 1);
+
+    // SET, PRINT, DECLARE and control flow produce neither metadata nor rows, so the rows
+    // must stay attributed to the SELECT that produced them
+    let sets = conn
+        .fetch_result_sets(
+            "SET NOCOUNT OFF; PRINT 'start'; DECLARE @n INT = 2;              SELECT 1 AS a;              SET @n = @n + 1; PRINT 'middle';              IF @n > 2 SELECT @n AS b UNION ALL SELECT @n + 1;              WHILE @n > 0 SET @n = @n - 1;",
+        )
+        .await?;
+
+    assert_eq!(sets.len(), 2);
+    assert_eq!(sets[0].len(), 1);
+    assert_eq!(sets[0][0].try_get::<i32, _>("a")?, 1);
+    let b: Vec<i32> = sets[1]
+        .iter()
+        .map(|row| row.try_get("b"))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(b, [3, 4]);
+
+    // a batch of only metadata-less statements reports a single empty result
+    let results = conn
+        .fetch_many("SET NOCOUNT ON; PRINT 'nothing'; SET NOCOUNT OFF;")
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(results.len(), 1);
+    assert!(results[0].is_left());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_returns_columns_before_rows() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    let (columns, mut rows) = conn
+        .fetch_with_columns(
+            sqlx::query("SELECT @p1 AS id, N'alice' AS name UNION ALL SELECT 2, N'bob'")
+                .bind(1_i32),
+        )
+        .await?;
+
+    let names: Vec<&str> = columns.iter().map(|c| c.name()).collect();
+    assert_eq!(names, ["id", "name"]);
+    assert_eq!(columns[1].type_info().name(), "NVARCHAR");
+
+    let first = rows.try_next().await?.expect("first row");
+    assert_eq!(first.try_get::<String, _>("name")?, "alice");
+    assert!(rows.try_next().await?.is_some());
+    assert!(rows.try_next().await?.is_none());
+    drop(rows);
+
+    // the metadata is sent even when the result set is empty
+    let (columns, rows) = conn
+        .fetch_with_columns("SELECT 1 AS a, 2 AS b WHERE 1 = 0")
+        .await?;
+    assert_eq!(columns.len(), 2);
+    assert!(rows.try_collect::<Vec<_>>().await?.is_empty());
+
+    // a later statement that fails fails the rows of the first result set
+    let (columns, mut rows) = conn
+        .fetch_with_columns("SELECT 1 AS a; SELECT 1/0 AS b")
+        .await?;
+    assert_eq!(columns.len(), 1);
+    assert!(rows.try_next().await?.is_some());
+    assert!(matches!(
+        rows.try_next().await,
+        Err(sqlx::Error::Database(_))
+    ));
+    drop(rows);
+
+    let one: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(one, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_map_two_result_sets_to_types() -> anyhow::Result<()> {
+    #[derive(sqlx::FromRow, Debug, PartialEq)]
+    struct User {
+        id: i32,
+        name: String,
+    }
+
+    #[derive(sqlx::FromRow, Debug, PartialEq)]
+    struct Order {
+        id: i32,
+        user_id: i32,
+        total: i64,
+    }
+
+    let mut conn = new::<Mssql>().await?;
+
+    let (users, orders) = conn
+        .fetch_two::<User, Order, _>(
+            sqlx::query(
+                "SELECT @p1 AS id, N'alice' AS name; \
+                 SELECT 10 AS id, @p1 AS user_id, CAST(250 AS BIGINT) AS total \
+                 UNION ALL SELECT 11, @p1, CAST(75 AS BIGINT)",
+            )
+            .bind(7_i32),
+        )
+        .await?;
+
+    assert_eq!(
+        users,
+        [User {
+            id: 7,
+            name: "alice".into()
+        }]
+    );
+    assert_eq!(
+        orders,
+        [
+            Order {
+                id: 10,
+                user_id: 7,
+                total: 250
+            },
+            Order {
+                id: 11,
+                user_id: 7,
+                total: 75
+            }
+        ]
+    );
+
+    // fewer result sets than expected
+    let err = conn
+        .fetch_two::<User, Order, _>("SELECT 1 AS id, N'bob' AS name")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("expected 2 result sets"), "{err}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_a_lookup_table_into_a_map_and_set() -> anyhow::Result<()> {
+    use std::collections::{HashMap, HashSet};
+
+    let mut conn = new::<Mssql>().await?;
+
+    conn.execute(
+        "CREATE TABLE #countries (id INT PRIMARY KEY, name NVARCHAR(50) NOT NULL, \
+         region NVARCHAR(20) NOT NULL); \
+         INSERT INTO #countries VALUES (1, N'Chile', N'Americas'), (2, N'Japan', N'Asia'), \
+         (3, N'Peru', N'Americas');",
+    )
+    .await?;
+
+    let countries: HashMap<i32, String> = conn
+        .fetch_map(sqlx::query("SELECT id, name FROM #countries WHERE id < @p1").bind(10_i32))
+        .await?;
+    assert_eq!(
+        countries,
+        HashMap::from([
+            (1, "Chile".to_owned()),
+            (2, "Japan".to_owned()),
+            (3, "Peru".to_owned()),
+        ])
+    );
+
+    let regions: HashSet<String> = conn.fetch_set("SELECT region FROM #countries").await?;
+    assert_eq!(
+        regions,
+        HashSet::from(["Americas".to_owned(), "Asia".to_owned()])
+    );
+
+    // a repeated key is an error rather than a silent overwrite
+    let err = conn
+        .fetch_map::<String, i32, _>("SELECT region, id FROM #countries ORDER BY id")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("duplicate key in row 3"), "{err}");
+
+    // the connection is still usable after the partially read result
+    let count: i32 = sqlx::query_scalar("SELECT COUNT(*) FROM #countries")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 3);
 
-            Box::pin(async move {
-                sqlx::query(
-                    r#"
-                        UPDATE #conn_stats
-                        SET after_release_calls = after_release_calls + 1
-                    "#,
-                )
-                .execute(&mut *conn)
-                .await?;
+    Ok(())
+}
 
-                let stats: ConnStats = sqlx::query_as("SELECT * FROM #conn_stats")
-                    .fetch_one(conn)
-                    .await?;
+#[sqlx_macros::test]
+async fn it_builds_a_tree_from_a_recursive_cte() -> anyhow::Result<()> {
+    use sqlx::mssql::{build_tree, MssqlTreeNode};
 
-                // For odd IDs, cap by the number of before_release calls.
-                // Ignore the check for even IDs.
-                Ok((stats.id & 1) == 0 || stats.after_release_calls < 4)
-            })
-        })
-        // Don't establish a connection yet.
-        .connect_lazy(&std::env::var("DATABASE_URL")?)?;
+    let mut conn = new::<Mssql>().await?;
 
-    // Expected pattern of (id, before_acquire_calls, after_release_calls)
-    let pattern = [
-        // The connection pool starts empty.
-        (0, 0, 0),
-        (0, 1, 1),
-        (0, 2, 2),
-        (1, 0, 0),
-        (1, 1, 1),
-        (1, 2, 2),
-        // We should expect one more `acquire` because the ID is odd
-        (1, 3, 3),
-        (2, 0, 0),
-        (2, 1, 1),
-        (2, 2, 2),
-        (3, 0, 0),
-    ];
+    conn.execute(
+        "CREATE TABLE #categories (id INT PRIMARY KEY, parent_id INT NULL, \
+         name NVARCHAR(50) NOT NULL); \
+         INSERT INTO #categories VALUES (1, NULL, N'root'), (2, 1, N'b'), (3, 1, N'a'), \
+         (4, 2, N'c'), (5, 6, N'cycle x'), (6, 5, N'cycle y');",
+    )
+    .await?;
 
-    for (id, before_acquire_calls, after_release_calls) in pattern {
-        let conn_stats: ConnStats = sqlx::query_as("SELECT * FROM #conn_stats")
-            .fetch_one(&pool)
-            .await?;
+    fn render(node: &MssqlTreeNode) -> String {
+        let name: String = node.row.get("name");
+        if node.children.is_empty() {
+            return name;
+        }
 
-        assert_eq!(
-            conn_stats,
-            ConnStats {
-                id,
-                before_acquire_calls,
-                after_release_calls
-            }
-        );
+        let children: Vec<String> = node.children.iter().map(render).collect();
+        format!("{name}({})", children.join(" "))
     }
 
-    pool.close().await;
+    let query = |anchor: &str| {
+        sqlx::AssertSqlSafe(format!(
+            "WITH tree AS ( \
+                 SELECT id, parent_id, name, CAST(CONCAT('/', id, '/') AS NVARCHAR(MAX)) AS path \
+                 FROM #categories WHERE {anchor} \
+                 UNION ALL \
+                 SELECT c.id, c.parent_id, c.name, CONCAT(t.path, c.id, '/') \
+                 FROM #categories c JOIN tree t ON c.parent_id = t.id \
+                 WHERE t.path NOT LIKE CONCAT('%/', c.id, '/%') \
+             ) \
+             SELECT id, parent_id, name FROM tree ORDER BY name"
+        ))
+        .into_sql_str()
+    };
+
+    let rows = sqlx::query(query("parent_id IS NULL"))
+        .fetch_all(&mut conn)
+        .await?;
+    let roots = build_tree::<i32>(rows, "id", "parent_id")?;
+    assert_eq!(
+        roots.iter().map(render).collect::<Vec<_>>(),
+        ["root(a b(c))"]
+    );
+
+    // anchored on a subtree, the anchor's parent is missing and it becomes the root
+    let rows = sqlx::query(query("id = 2")).fetch_all(&mut conn).await?;
+    let roots = build_tree::<i32>(rows, "id", "parent_id")?;
+    assert_eq!(roots.iter().map(render).collect::<Vec<_>>(), ["b(c)"]);
+
+    // the path check stops the CTE, and build_tree reports the cycle
+    let rows = sqlx::query(query("id = 5")).fetch_all(&mut conn).await?;
+    assert_eq!(rows.len(), 2);
+    let err = build_tree::<i32>(rows, "id", "parent_id").unwrap_err();
+    assert!(matches!(err, sqlx::Error::Decode(_)), "{err:?}");
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_query_multiple_result_sets() -> anyhow::Result<()> {
+async fn it_writes_large_values_from_a_reader() -> anyhow::Result<()> {
     let mut conn = new::<Mssql>().await?;
 
-    // A batch that produces two result sets
-    let results = conn
-        .run("SELECT 1 AS a; SELECT 2 AS b, 3 AS c;", None)
+    conn.execute(
+        "CREATE TABLE #documents (id INT PRIMARY KEY, content VARBINARY(MAX) NULL, \
+         body NVARCHAR(MAX) NULL); \
+         INSERT INTO #documents (id) VALUES (1), (2);",
+    )
+    .await?;
+
+    // spans several chunks and ends in a partial one
+    let content: Vec<u8> = (0..3_500_000u32).map(|i| (i % 251) as u8).collect();
+    let written = conn
+        .write_binary_stream("#documents", "content", "id", 1i32, &content[..])
         .await?;
+    assert_eq!(written, content.len() as u64);
 
-    // First result set: one row with column "a"
-    let mut rows_first = Vec::new();
-    let mut rows_second = Vec::new();
-    let mut result_count = 0;
+    // multi-byte characters straddle the chunk boundaries
+    let body = "héllo wörld € 😀 ".repeat(100_000);
+    let written = conn
+        .write_text_stream("#documents", "body", "id", 1i32, body.as_bytes())
+        .await?;
+    assert_eq!(written, body.len() as u64);
 
-    for item in &results {
-        match item {
-            either::Either::Left(_) => {
-                result_count += 1;
-            }
-            either::Either::Right(row) => {
-                if result_count == 0 {
-                    rows_first.push(row);
-                } else {
-                    rows_second.push(row);
-                }
-            }
-        }
-    }
+    let (stored_content, stored_body): (Vec<u8>, String) =
+        sqlx::query_as("SELECT content, body FROM #documents WHERE id = 1")
+            .fetch_one(&mut conn)
+            .await?;
+    assert!(stored_content == content);
+    assert!(stored_body == body);
 
-    assert_eq!(rows_first.len(), 1);
-    assert_eq!(rows_first[0].try_get::<i32, _>("a")?, 1);
+    // an empty reader leaves an empty value rather than NULL
+    conn.write_binary_stream("#documents", "content", "id", 2i32, &b""[..])
+        .await?;
+    let stored: Option<Vec<u8>> = sqlx::query_scalar("SELECT content FROM #documents WHERE id = 2")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(stored, Some(Vec::new()));
+
+    let err = conn
+        .write_binary_stream("#documents", "content", "id", 3i32, &b"x"[..])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::RowNotFound), "{err:?}");
+
+    // invalid UTF-8 keeps the previous value
+    let err = conn
+        .write_text_stream("#documents", "body", "id", 1i32, &b"ok \xff"[..])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::Io(_)), "{err:?}");
+    let length: i64 = sqlx::query_scalar("SELECT LEN(body) FROM #documents WHERE id = 1")
+        .fetch_one(&mut conn)
+        .await?;
+    assert!(length > 0);
 
-    assert_eq!(rows_second.len(), 1);
-    assert_eq!(rows_second[0].try_get::<i32, _>("b")?, 2);
-    assert_eq!(rows_second[0].try_get::<i32, _>("c")?, 3);
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_counts_the_rows_of_a_filtered_query() -> anyhow::Result<()> {
+    use sqlx::mssql::MssqlArguments;
+    use sqlx::Arguments;
+
+    let mut conn = new::<Mssql>().await?;
+
+    conn.execute(
+        "CREATE TABLE #count_users (id INT PRIMARY KEY, name NVARCHAR(50) NOT NULL, \
+         active BIT NOT NULL); \
+         INSERT INTO #count_users VALUES (1, N'd', 1), (2, N'c', 0), (3, N'b', 1), (4, N'a', 1);",
+    )
+    .await?;
+
+    let sql = "SELECT id, name FROM #count_users WHERE active = @p1 ORDER BY name;";
+
+    let mut arguments = MssqlArguments::default();
+    arguments.add(true).map_err(sqlx::Error::Encode)?;
+    assert_eq!(conn.count_query(sql, arguments.clone()).await?, 3);
+
+    // a page keeps its ORDER BY and counts only its own rows
+    let page = "SELECT id FROM #count_users WHERE active = @p1 \
+                ORDER BY name OFFSET 2 ROWS FETCH NEXT 10 ROWS ONLY";
+    assert_eq!(conn.count_query(page, arguments).await?, 1);
+
+    let mut arguments = MssqlArguments::default();
+    arguments.add(2i32).map_err(sqlx::Error::Encode)?;
+    let total = conn
+        .count_query(
+            "WITH named AS (SELECT id, name FROM #count_users WHERE id > @p1) \
+             SELECT name FROM named ORDER BY name OPTION (RECOMPILE)",
+            arguments,
+        )
+        .await?;
+    assert_eq!(total, 2);
 
     Ok(())
 }
@@ -526,6 +2372,45 @@ async fn it_can_inspect_column_metadata() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_reports_the_collation_of_prepared_columns() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    conn.execute(
+        "CREATE TABLE #collated (id INT NOT NULL, \
+         code VARCHAR(10) COLLATE Latin1_General_CS_AS NOT NULL, \
+         name NVARCHAR(50) COLLATE Latin1_General_CI_AI NULL)",
+    )
+    .await?;
+
+    let statement = conn
+        .prepare("SELECT id, code, name, UPPER(name) AS upper_name FROM #collated".into_sql_str())
+        .await?;
+
+    assert_eq!(statement.column(0).collation(), None);
+    assert_eq!(
+        statement.column(1).collation(),
+        Some("Latin1_General_CS_AS")
+    );
+    assert_eq!(
+        statement.column(2).collation(),
+        Some("Latin1_General_CI_AI")
+    );
+    assert_eq!(
+        statement.column(3).collation(),
+        Some("Latin1_General_CI_AI")
+    );
+
+    // rows of an executed query don't carry it
+    let columns = conn
+        .fetch_with_columns("SELECT code FROM #collated")
+        .await?
+        .0;
+    assert_eq!(columns[0].collation(), None);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_reuse_connection_after_error() -> anyhow::Result<()> {
     let mut conn = new::<Mssql>().await?;
@@ -543,6 +2428,93 @@ async fn it_can_reuse_connection_after_error() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_times_out_a_stalled_handshake() -> anyhow::Result<()> {
+    // The kernel completes the TCP handshake for the listener's backlog, but nothing ever
+    // answers the PRELOGIN packet, like a gateway that accepts the socket and then stalls.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let port = listener.local_addr()?.port();
+
+    let opts = MssqlConnectOptions::new()
+        .host("127.0.0.1")
+        .port(port)
+        .connect_timeout(Duration::from_millis(500));
+
+    match MssqlConnection::connect_with(&opts).await {
+        Err(sqlx::Error::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::TimedOut),
+        other => panic!("expected a timeout, got {other:?}"),
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_drains_an_abandoned_stream_before_the_next_query() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+    let mut tx = conn.begin().await?;
+
+    {
+        // several packets of rows and a second result set, of which one row is read
+        let mut stream = sqlx::query(
+            "SELECT TOP 50000 a.object_id, a.name FROM sys.all_objects a \
+             CROSS JOIN sys.all_objects b; \
+             SELECT 2 AS second",
+        )
+        .fetch(&mut *tx);
+        assert!(stream.try_next().await?.is_some());
+    }
+
+    // no "connection is busy" state: the rest of the response is discarded first
+    let value: i32 = sqlx::query_scalar("SELECT @p1")
+        .bind(7i32)
+        .fetch_one(&mut *tx)
+        .await?;
+    assert_eq!(value, 7);
+
+    tx.commit().await?;
+    assert!(!conn.is_in_transaction());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_closes_the_connection_after_command_timeout() -> anyhow::Result<()> {
+    let opts: MssqlConnectOptions = dotenvy::var("DATABASE_URL")?.parse()?;
+    let opts = opts.command_timeout(Duration::from_secs(1));
+    let mut conn = MssqlConnection::connect_with(&opts).await?;
+
+    // Rows are not held against the timeout once they have arrived.
+    let mut rows = sqlx::query("SELECT 1 UNION ALL SELECT 2").fetch(&mut conn);
+    while rows.try_next().await?.is_some() {
+        sqlx_core::rt::sleep(Duration::from_millis(700)).await;
+    }
+    drop(rows);
+
+    let res = conn.execute("WAITFOR DELAY '00:00:03'").await;
+    match res {
+        Err(sqlx::Error::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::TimedOut),
+        other => panic!("expected a timeout, got {other:?}"),
+    }
+
+    // The abandoned request may still be running, so the connection is not reused.
+    match conn.ping().await {
+        Err(sqlx::Error::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::NotConnected),
+        other => panic!("expected the connection to be unusable, got {other:?}"),
+    }
+    assert!(conn.execute("SELECT 1").await.is_err());
+
+    // A pool discards it and opens a fresh connection.
+    let pool = MssqlPoolOptions::new()
+        .max_connections(1)
+        .connect_with(opts)
+        .await?;
+    assert!(pool.execute("WAITFOR DELAY '00:00:03'").await.is_err());
+    let val: i32 = sqlx::query_scalar("SELECT 42").fetch_one(&pool).await?;
+    assert_eq!(val, 42);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_bind_many_parameters() -> anyhow::Result<()> {
     let mut conn = new::<Mssql>().await?;
@@ -676,3 +2648,194 @@ async fn it_can_try_acquire_advisory_lock() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_verifies_pool_connection_database() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let pool = MssqlPoolOptions::new()
+        .max_connections(1)
+        .verify_database()
+        .connect(&std::env::var("DATABASE_URL")?)
+        .await?;
+
+    let expected: String = sqlx::query_scalar("SELECT DB_NAME()")
+        .fetch_one(&pool)
+        .await?;
+
+    {
+        let mut conn = pool.acquire().await?;
+        conn.execute("USE tempdb").await?;
+        assert!(conn.verify_database().await.is_err());
+    }
+
+    // The leaked connection fails the check and is replaced with a fresh one.
+    let actual: String = sqlx::query_scalar("SELECT DB_NAME()")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(actual, expected);
+
+    // Switching on purpose keeps the connection valid.
+    {
+        let mut conn = pool.acquire().await?;
+        conn.use_database("tempdb").await?;
+        conn.verify_database().await?;
+    }
+
+    let actual: String = sqlx::query_scalar("SELECT DB_NAME()")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(actual, "tempdb");
+
+    pool.close().await;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_drops_temp_tables_on_release_when_enabled() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    async fn temp_table_survives(pool: &sqlx::MssqlPool) -> anyhow::Result<bool> {
+        {
+            let mut conn = pool.acquire().await?;
+            conn.execute(
+                "CREATE TABLE #session_scratch (id INT); CREATE TABLE ##global_scratch (id INT)",
+            )
+            .await?;
+        }
+
+        let mut conn = pool.acquire().await?;
+        let local: bool = sqlx::query_scalar(
+            "SELECT CAST(CASE WHEN OBJECT_ID('tempdb..#session_scratch') IS NULL THEN 0 ELSE 1 END AS BIT)",
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        // global temp tables are never dropped
+        let global: bool = sqlx::query_scalar(
+            "SELECT CAST(CASE WHEN OBJECT_ID('tempdb..##global_scratch') IS NULL THEN 0 ELSE 1 END AS BIT)",
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+        assert!(global);
+
+        conn.execute("DROP TABLE ##global_scratch").await?;
+
+        Ok(local)
+    }
+
+    // by default, temp tables persist across acquires of the same connection
+    let pool = MssqlPoolOptions::new()
+        .max_connections(1)
+        .connect(&std::env::var("DATABASE_URL")?)
+        .await?;
+    assert!(temp_table_survives(&pool).await?);
+    pool.close().await;
+
+    let pool = MssqlPoolOptions::new()
+        .max_connections(1)
+        .drop_temp_tables_on_release()
+        .connect(&std::env::var("DATABASE_URL")?)
+        .await?;
+    assert!(!temp_table_survives(&pool).await?);
+    pool.close().await;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_drops_temp_tables_next_to_table_variables() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    // Leaves a cached temp table and table variable with short `#A1B2C3D4` names in tempdb
+    conn.execute(
+        "CREATE OR ALTER PROCEDURE sqlx_cached_temp_objects AS BEGIN \
+         CREATE TABLE #cached (id INT); DECLARE @v TABLE (id INT); \
+         INSERT INTO @v VALUES (1); INSERT INTO #cached SELECT id FROM @v; END",
+    )
+    .await?;
+    conn.execute("EXEC sqlx_cached_temp_objects").await?;
+
+    conn.execute(
+        "CREATE TABLE [#name with space] (id INT); CREATE TABLE #trailing_ (id INT); \
+         CREATE TABLE #trailing (id INT)",
+    )
+    .await?;
+
+    // A table variable is alive while the cleanup runs on another connection's session
+    let mut other = new::<Mssql>().await?;
+    let holder = sqlx_core::rt::spawn(async move {
+        other
+            .execute("DECLARE @held TABLE (id INT); WAITFOR DELAY '00:00:02'")
+            .await
+    });
+    sqlx_core::rt::sleep(Duration::from_millis(500)).await;
+
+    conn.drop_temp_tables().await?;
+    holder.await?;
+
+    let remaining: i32 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM (VALUES (OBJECT_ID(N'tempdb..[#name with space]')), \
+         (OBJECT_ID(N'tempdb..#trailing_')), (OBJECT_ID(N'tempdb..#trailing'))) AS v(id) \
+         WHERE id IS NOT NULL",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+    assert_eq!(remaining, 0);
+
+    conn.execute("DROP PROCEDURE sqlx_cached_temp_objects")
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_keeps_idle_pool_connections_alive() -> anyhow::Result<()> {
+    let pool = MssqlPoolOptions::new()
+        .max_connections(2)
+        .connect(&std::env::var("DATABASE_URL")?)
+        .await?;
+
+    let mut busy = pool.acquire().await?;
+    let busy_spid: i16 = sqlx::query_scalar("SELECT @@SPID")
+        .fetch_one(&mut *busy)
+        .await?;
+
+    let idle_spid: i16 = {
+        let mut idle = pool.acquire().await?;
+        sqlx::query_scalar("SELECT @@SPID")
+            .fetch_one(&mut *idle)
+            .await?
+    };
+
+    let keepalive = pool.spawn_keepalive(Duration::from_millis(200));
+
+    // Longer than the "firewall timeout" of this test
+    sqlx_core::rt::sleep(Duration::from_millis(1500)).await;
+
+    let mut observer = new::<Mssql>().await?;
+    let silent_for = "SELECT DATEDIFF_BIG(millisecond, last_request_end_time, GETDATE()) \
+                      FROM sys.dm_exec_sessions WHERE session_id = @p1";
+
+    // The idle connection was pinged within the last interval or two
+    let idle_silent_for: i64 = sqlx::query_scalar(silent_for)
+        .bind(idle_spid)
+        .fetch_one(&mut observer)
+        .await?;
+    assert!(idle_silent_for < 1000, "{idle_silent_for}ms");
+
+    // The checked-out connection was left alone
+    let busy_silent_for: i64 = sqlx::query_scalar(silent_for)
+        .bind(busy_spid)
+        .fetch_one(&mut observer)
+        .await?;
+    assert!(busy_silent_for >= 1000, "{busy_silent_for}ms");
+
+    drop(keepalive);
+    busy.ping().await?;
+    drop(busy);
+    pool.close().await;
+
+    Ok(())
+}
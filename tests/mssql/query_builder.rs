@@ -1,4 +1,4 @@
-use sqlx::mssql::Mssql;
+use sqlx::mssql::{Mssql, MssqlHint, MssqlQueryBuilderExt, MssqlSortDirection};
 use sqlx::query_builder::QueryBuilder;
 use sqlx::Execute;
 
@@ -106,3 +106,349 @@ fn test_query_builder_with_args() {
         "SELECT * FROM users WHERE id = @p1 OR membership_level = @p2"
     );
 }
+
+#[test]
+fn test_push_option_hint() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("SELECT * FROM users WHERE id = ");
+    qb.push_bind(42i32);
+    qb.push_option_hint("RECOMPILE").unwrap();
+
+    assert_eq!(
+        qb.sql(),
+        "SELECT * FROM users WHERE id = @p1 OPTION (RECOMPILE)"
+    );
+}
+
+#[test]
+fn test_push_option_hint_multiple_hints() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("SELECT * FROM users");
+    qb.push_option_hint("MAXDOP 1, OPTIMIZE FOR UNKNOWN")
+        .unwrap();
+
+    assert_eq!(
+        qb.sql(),
+        "SELECT * FROM users OPTION (MAXDOP 1, OPTIMIZE FOR UNKNOWN)"
+    );
+}
+
+#[test]
+fn test_push_option_hint_only_once_per_statement() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("SELECT * FROM users");
+    qb.push_option_hint("RECOMPILE").unwrap();
+
+    assert!(qb.push_option_hint("MAXDOP 1").is_err());
+
+    // A new statement may have its own OPTION clause.
+    qb.push("; SELECT * FROM orders");
+    qb.push_option_hint("MAXDOP 1").unwrap();
+
+    assert_eq!(
+        qb.sql(),
+        "SELECT * FROM users OPTION (RECOMPILE); SELECT * FROM orders OPTION (MAXDOP 1)"
+    );
+}
+
+#[test]
+fn test_push_option_hint_rejects_injection() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("SELECT * FROM users");
+
+    assert!(qb
+        .push_option_hint("RECOMPILE); DROP TABLE users; --")
+        .is_err());
+    assert!(qb.push_option_hint("").is_err());
+    assert_eq!(qb.sql(), "SELECT * FROM users");
+}
+
+#[test]
+fn test_push_table_hint() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("SELECT * FROM users");
+    qb.push_table_hint("NOLOCK").unwrap();
+
+    assert_eq!(qb.sql(), "SELECT * FROM users WITH (NOLOCK)");
+}
+
+#[test]
+fn test_push_table_hint_after_alias() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("SELECT * FROM accounts a");
+    qb.push_table_hint("UPDLOCK, HOLDLOCK")
+        .unwrap()
+        .push(" WHERE a.id = ")
+        .push_bind(7i32);
+
+    assert_eq!(
+        qb.sql(),
+        "SELECT * FROM accounts a WITH (UPDLOCK, HOLDLOCK) WHERE a.id = @p1"
+    );
+}
+
+#[test]
+fn test_push_table_hint_rejects_injection() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("SELECT * FROM users");
+
+    assert!(qb
+        .push_table_hint("NOLOCK); DELETE FROM users; --")
+        .is_err());
+    assert_eq!(qb.sql(), "SELECT * FROM users");
+}
+
+#[test]
+fn test_push_typed_hints() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("SELECT balance FROM accounts a");
+    qb.push_table_hints(&[MssqlHint::UpdLock, MssqlHint::HoldLock])
+        .unwrap()
+        .push(" WHERE a.id = ")
+        .push_bind(7i32);
+    qb.push_query_hints(&[MssqlHint::OptimizeForUnknown, MssqlHint::MaxDop(2)])
+        .unwrap();
+
+    assert_eq!(
+        qb.sql(),
+        "SELECT balance FROM accounts a WITH (UPDLOCK, HOLDLOCK) WHERE a.id = @p1 \
+         OPTION (OPTIMIZE FOR UNKNOWN, MAXDOP 2)"
+    );
+}
+
+#[test]
+fn test_push_typed_hints_rejects_misplaced_and_conflicting_hints() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("SELECT * FROM users");
+
+    assert!(qb.push_table_hints(&[MssqlHint::Recompile]).is_err());
+    assert!(qb.push_query_hints(&[MssqlHint::ForceSeek]).is_err());
+    assert!(qb
+        .push_table_hints(&[MssqlHint::NoLock, MssqlHint::HoldLock])
+        .is_err());
+    assert_eq!(qb.sql(), "SELECT * FROM users");
+}
+
+#[test]
+fn test_push_except_chained() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("SELECT id FROM customers");
+    qb.push_except(|qb| {
+        qb.push("SELECT customer_id FROM blocked WHERE reason = ")
+            .push_bind("fraud");
+    })
+    .push_except(|qb| {
+        qb.push("SELECT TOP 10 customer_id FROM complaints ORDER BY created_at DESC");
+    })
+    .push(" ORDER BY id");
+
+    assert_eq!(
+        qb.sql(),
+        "SELECT id FROM customers \
+         EXCEPT (SELECT customer_id FROM blocked WHERE reason = @p1) \
+         EXCEPT (SELECT TOP 10 customer_id FROM complaints ORDER BY created_at DESC) \
+         ORDER BY id"
+    );
+}
+
+#[test]
+fn test_push_intersect_numbers_binds_in_order() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("SELECT id FROM orders WHERE total > ");
+    qb.push_bind(100i32);
+    qb.push_intersect(|qb| {
+        qb.push("SELECT order_id FROM shipments WHERE region = ")
+            .push_bind("EU");
+    });
+
+    assert_eq!(
+        qb.sql(),
+        "SELECT id FROM orders WHERE total > @p1 \
+         INTERSECT (SELECT order_id FROM shipments WHERE region = @p2)"
+    );
+}
+
+const SORTABLE: &[&str] = &["name", "created_at", "u.email"];
+
+#[test]
+fn test_push_order_by_allowed_column() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("SELECT * FROM users u");
+    qb.push_order_by("Created_At", MssqlSortDirection::Asc, SORTABLE)
+        .unwrap();
+
+    assert_eq!(qb.sql(), "SELECT * FROM users u ORDER BY [created_at] ASC");
+}
+
+#[test]
+fn test_push_order_by_directions() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("SELECT * FROM users u");
+    qb.push_order_by("u.email", MssqlSortDirection::Desc, SORTABLE)
+        .unwrap();
+    assert_eq!(qb.sql(), "SELECT * FROM users u ORDER BY [u].[email] DESC");
+
+    qb.reset();
+    qb.push_order_by("name", MssqlSortDirection::default(), SORTABLE)
+        .unwrap();
+    assert_eq!(qb.sql(), "SELECT * FROM users u ORDER BY [name] ASC");
+}
+
+#[test]
+fn test_push_order_by_rejects_unknown_column() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("SELECT * FROM users u");
+
+    for column in ["password_hash", "name; DROP TABLE users", "[name]", ""] {
+        assert!(qb
+            .push_order_by(column, MssqlSortDirection::Asc, SORTABLE)
+            .is_err());
+    }
+    assert_eq!(qb.sql(), "SELECT * FROM users u");
+}
+
+#[test]
+fn test_push_contains() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("SELECT id FROM docs WHERE ");
+    qb.push_contains("(title, body)", "\"rust*\" NEAR async")
+        .unwrap()
+        .push(" AND published = ")
+        .push_bind(true);
+
+    assert_eq!(
+        qb.sql(),
+        "SELECT id FROM docs WHERE CONTAINS(([title], [body]), @p1) AND published = @p2"
+    );
+}
+
+#[test]
+fn test_push_contains_phrase_and_freetext() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("SELECT id FROM docs d WHERE ");
+    qb.push_contains_phrase("d.title", "say \"hi\" AND bye")
+        .unwrap()
+        .push(" OR ");
+    qb.push_freetext("*", "cats and dogs").unwrap();
+
+    assert_eq!(
+        qb.sql(),
+        "SELECT id FROM docs d WHERE CONTAINS([d].[title], @p1) OR FREETEXT(*, @p2)"
+    );
+}
+
+#[test]
+fn test_push_contains_rejects_empty_terms() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("SELECT id FROM docs WHERE ");
+
+    assert!(qb.push_contains("body", "  ").is_err());
+    assert!(qb.push_contains_phrase("body", "").is_err());
+    assert!(qb.push_freetext("body", "").is_err());
+    assert_eq!(qb.sql(), "SELECT id FROM docs WHERE ");
+}
+
+#[test]
+fn test_try_push_values() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("INSERT INTO users (id, name) ");
+
+    qb.try_push_values([(1, "a"), (2, "b"), (3, "c")], |mut b, (id, name)| {
+        b.push_bind(id).push_bind(name);
+    })
+    .unwrap();
+
+    assert_eq!(
+        qb.sql(),
+        "INSERT INTO users (id, name) VALUES (@p1, @p2), (@p3, @p4), (@p5, @p6)"
+    );
+}
+
+#[test]
+fn test_try_push_values_allows_1000_rows() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("INSERT INTO t (a) ");
+
+    qb.try_push_values(0..1000, |mut b, i| {
+        b.push_bind(i);
+    })
+    .unwrap();
+
+    assert!(qb.sql().as_str().ends_with(", (@p1000)"));
+}
+
+#[test]
+fn test_try_push_values_rejects_1001_rows() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("INSERT INTO t (a) ");
+
+    let Err(err) = qb.try_push_values(0..1001, |mut b, i| {
+        b.push_bind(i);
+    }) else {
+        panic!("expected 1001 rows to be rejected");
+    };
+
+    assert!(matches!(err, sqlx::Error::InvalidArgument(_)));
+    assert!(err.to_string().contains("1001 rows"), "{err}");
+    assert_eq!(qb.sql(), "INSERT INTO t (a) ");
+}
+
+#[test]
+fn test_try_push_values_rejects_empty_list() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("INSERT INTO t (a) ");
+
+    assert!(qb
+        .try_push_values(Vec::<i32>::new(), |mut b, i| {
+            b.push_bind(i);
+        })
+        .is_err());
+    assert_eq!(qb.sql(), "INSERT INTO t (a) ");
+}
+
+#[test]
+fn test_push_string_split_in() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("SELECT * FROM users u WHERE ");
+    qb.push_string_split_in("u.id", [1, 2, 3], ',').unwrap();
+    qb.push(" AND active = ").push_bind(true);
+
+    assert_eq!(
+        qb.sql(),
+        "SELECT * FROM users u WHERE [u].[id] IN (SELECT value FROM STRING_SPLIT(@p1, N',')) \
+         AND active = @p2"
+    );
+}
+
+#[test]
+fn test_push_string_split_in_escapes_quote_delimiter() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("SELECT * FROM tags WHERE ");
+    qb.push_string_split_in("name", ["a", "b"], '\'').unwrap();
+
+    assert_eq!(
+        qb.sql(),
+        "SELECT * FROM tags WHERE [name] IN (SELECT value FROM STRING_SPLIT(@p1, N''''))"
+    );
+}
+
+#[test]
+fn test_push_string_split_in_rejects_values_containing_delimiter() {
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("SELECT * FROM tags WHERE ");
+
+    let Err(err) = qb.push_string_split_in("name", ["rust", "c,c++"], ',') else {
+        panic!("expected a value containing the delimiter to be rejected");
+    };
+    assert!(matches!(err, sqlx::Error::InvalidArgument(_)));
+    assert_eq!(qb.sql(), "SELECT * FROM tags WHERE ");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_push_openjson() {
+    use sqlx::mssql::MssqlJsonColumn;
+
+    #[derive(serde::Serialize)]
+    struct Filter {
+        id: i32,
+        #[serde(rename = "display name")]
+        name: String,
+    }
+
+    let mut qb: QueryBuilder<Mssql> = QueryBuilder::new("SELECT * FROM ");
+    qb.push_openjson(
+        &[Filter {
+            id: 1,
+            name: "O'Brien".into(),
+        }],
+        &[
+            MssqlJsonColumn::of::<i32>("id").unwrap(),
+            MssqlJsonColumn::of::<String>("display name").unwrap(),
+        ],
+    )
+    .unwrap();
+    qb.push(" AS f");
+
+    assert_eq!(
+        qb.sql(),
+        "SELECT * FROM OPENJSON(@p1) WITH ([id] INT N'$.id', \
+         [display name] NVARCHAR(MAX) N'$.\"display name\"') AS f"
+    );
+}
@@ -41,6 +41,111 @@ test_type!(i64(
     "CAST(9223372036854775807 AS BIGINT)" == i64::MAX,
 ));
 
+test_type!(u16(
+    Mssql,
+    "CAST(8080 AS INT)" == 8080_u16,
+    "CAST(0 AS INT)" == 0_u16,
+    "CAST(65535 AS INT)" == u16::MAX,
+));
+
+test_type!(u32(
+    Mssql,
+    "CAST(3000000000 AS BIGINT)" == 3_000_000_000_u32,
+    "CAST(4294967295 AS BIGINT)" == u32::MAX,
+));
+
+test_type!(u64(
+    Mssql,
+    "CAST(0 AS BIGINT)" == 0_u64,
+    "CAST(9223372036854775807 AS BIGINT)" == i64::MAX as u64,
+));
+
+test_type!(u64_numeric<u64>(
+    Mssql,
+    "CAST(9223372036854775808 AS DECIMAL(20, 0))" == 9_223_372_036_854_775_808_u64,
+    "CAST(18446744073709551615 AS DECIMAL(20, 0))" == u64::MAX,
+));
+
+test_type!(i128(
+    Mssql,
+    "CAST(0 AS DECIMAL(38, 0))" == 0_i128,
+    "CAST(-170141183460469231731687303715884105 AS DECIMAL(38, 0))"
+        == -170_141_183_460_469_231_731_687_303_715_884_105_i128,
+    "CAST(99999999999999999999999999999999999999 AS DECIMAL(38, 0))"
+        == 99_999_999_999_999_999_999_999_999_999_999_999_999_i128,
+    "CAST(-99999999999999999999999999999999999999 AS DECIMAL(38, 0))"
+        == -99_999_999_999_999_999_999_999_999_999_999_999_999_i128,
+));
+
+test_type!(u128(
+    Mssql,
+    "CAST(18446744073709551616 AS DECIMAL(38, 0))" == 18_446_744_073_709_551_616_u128,
+    "CAST(99999999999999999999999999999999999999 AS DECIMAL(38, 0))"
+        == 99_999_999_999_999_999_999_999_999_999_999_999_999_u128,
+));
+
+#[sqlx_macros::test]
+async fn it_decodes_big_integers_only_without_a_fraction() -> anyhow::Result<()> {
+    let mut conn = sqlx_test::new::<Mssql>().await?;
+
+    let value: i128 = sqlx::query_scalar("SELECT CAST(42.000 AS DECIMAL(38, 3))")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 42);
+
+    let value: i128 = sqlx::query_scalar("SELECT CAST(-7 AS BIGINT)")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, -7);
+
+    let result: Result<i128, _> = sqlx::query_scalar("SELECT CAST(42.5 AS DECIMAL(38, 1))")
+        .fetch_one(&mut conn)
+        .await;
+    assert!(result.is_err());
+
+    let result: Result<u128, _> = sqlx::query_scalar("SELECT CAST(-1 AS DECIMAL(38, 0))")
+        .fetch_one(&mut conn)
+        .await;
+    assert!(result.is_err());
+
+    // 39 digits are more than DECIMAL(38, 0) can hold
+    let result = sqlx::query("SELECT @p1")
+        .bind(10_i128.pow(38))
+        .execute(&mut conn)
+        .await;
+    assert!(result.is_err());
+
+    let result = sqlx::query("SELECT @p1")
+        .bind(u128::MAX)
+        .execute(&mut conn)
+        .await;
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rejects_out_of_range_unsigned_integers() -> anyhow::Result<()> {
+    let mut conn = sqlx_test::new::<Mssql>().await?;
+
+    let result: Result<u16, _> = sqlx::query_scalar("SELECT CAST(-1 AS INT)")
+        .fetch_one(&mut conn)
+        .await;
+    assert!(result.is_err());
+
+    let result: Result<u32, _> = sqlx::query_scalar("SELECT CAST(4294967296 AS BIGINT)")
+        .fetch_one(&mut conn)
+        .await;
+    assert!(result.is_err());
+
+    let result: Result<u64, _> = sqlx::query_scalar("SELECT CAST(-1 AS BIGINT)")
+        .fetch_one(&mut conn)
+        .await;
+    assert!(result.is_err());
+
+    Ok(())
+}
+
 test_type!(f32(
     Mssql,
     "CAST(3.1410000324249268 AS REAL)" == 3.141f32 as f64 as f32
@@ -71,6 +176,177 @@ test_type!(rust_decimal_smallmoney<sqlx::types::Decimal>(Mssql,
     "CAST(0 AS SMALLMONEY)" == sqlx::types::Decimal::ZERO,
 ));
 
+#[sqlx_macros::test]
+async fn it_round_trips_money_through_money_columns() -> anyhow::Result<()> {
+    use sqlx::mssql::{MssqlMoney, MssqlSmallMoney};
+
+    let mut conn = sqlx_test::new::<Mssql>().await?;
+
+    sqlx::query("CREATE TABLE #money (id INT NOT NULL, m MONEY NOT NULL, s SMALLMONEY NOT NULL)")
+        .execute(&mut conn)
+        .await?;
+
+    let values = [
+        (1, MssqlMoney(0), MssqlSmallMoney(0)),
+        (2, MssqlMoney(12_345_678), MssqlSmallMoney(-12_345_678)),
+        (3, MssqlMoney(1 << 51), MssqlSmallMoney(i32::MAX)),
+        (4, MssqlMoney(-(1 << 51)), MssqlSmallMoney(i32::MIN)),
+    ];
+
+    for (id, m, s) in values {
+        sqlx::query("INSERT INTO #money (id, m, s) VALUES (@p1, @p2, @p3)")
+            .bind(id)
+            .bind(m)
+            .bind(s)
+            .execute(&mut conn)
+            .await?;
+    }
+
+    let rows: Vec<(i32, MssqlMoney, MssqlSmallMoney)> =
+        sqlx::query_as("SELECT id, m, s FROM #money ORDER BY id")
+            .fetch_all(&mut conn)
+            .await?;
+    assert_eq!(rows, values);
+
+    // stored exactly, so an equality lookup finds the row
+    let id: i32 = sqlx::query_scalar("SELECT id FROM #money WHERE m = @p1")
+        .bind(MssqlMoney(12_345_678))
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(id, 2);
+
+    // a SMALLMONEY column also reads as MONEY
+    let s: MssqlMoney = sqlx::query_scalar("SELECT s FROM #money WHERE id = 3")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(s, MssqlMoney(i64::from(i32::MAX)));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reads_rowversion_for_optimistic_updates() -> anyhow::Result<()> {
+    use sqlx::mssql::MssqlRowVersion;
+
+    let mut conn = sqlx_test::new::<Mssql>().await?;
+
+    sqlx::query(
+        "CREATE TABLE #orders (id INT NOT NULL, qty INT NOT NULL, version ROWVERSION NOT NULL); \
+         INSERT INTO #orders (id, qty) VALUES (1, 5)",
+    )
+    .execute(&mut conn)
+    .await?;
+
+    let read: MssqlRowVersion = sqlx::query_scalar("SELECT version FROM #orders WHERE id = 1")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let update = "UPDATE #orders SET qty = qty + 1 OUTPUT INSERTED.version \
+                  WHERE id = 1 AND version = @p1";
+
+    let updated: MssqlRowVersion = sqlx::query_scalar(update)
+        .bind(read)
+        .fetch_one(&mut conn)
+        .await?;
+    assert!(updated > read);
+    assert!(updated.to_u64() > read.to_u64());
+
+    // the stale version no longer matches
+    let conflict: Option<MssqlRowVersion> = sqlx::query_scalar(update)
+        .bind(read)
+        .fetch_optional(&mut conn)
+        .await?;
+    assert_eq!(conflict, None);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_round_trips_geography_as_wkb() -> anyhow::Result<()> {
+    use sqlx::mssql::MssqlSpatial;
+
+    let mut conn = sqlx_test::new::<Mssql>().await?;
+
+    // POINT (1 2), little-endian
+    let mut wkb = vec![0x01, 0x01, 0x00, 0x00, 0x00];
+    wkb.extend_from_slice(&1f64.to_le_bytes());
+    wkb.extend_from_slice(&2f64.to_le_bytes());
+    let point = MssqlSpatial::from(wkb);
+
+    sqlx::query("CREATE TABLE #places (id INT NOT NULL, location GEOGRAPHY NOT NULL)")
+        .execute(&mut conn)
+        .await?;
+    sqlx::query(
+        "INSERT INTO #places (id, location) VALUES (1, geography::STGeomFromWKB(@p1, 4326))",
+    )
+    .bind(&point)
+    .execute(&mut conn)
+    .await?;
+
+    let (wkt, srid, read): (String, i32, MssqlSpatial) = sqlx::query_as(
+        "SELECT location.STAsText(), location.STSrid, location.STAsBinary() FROM #places",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+    assert_eq!(wkt, "POINT (1 2)");
+    assert_eq!(srid, 4326);
+    assert_eq!(read, point);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_stores_an_int_and_a_string_in_one_sql_variant_column() -> anyhow::Result<()> {
+    use sqlx::mssql::MssqlVariant;
+
+    let mut conn = sqlx_test::new::<Mssql>().await?;
+
+    sqlx::query("CREATE TABLE #variants (id INT NOT NULL, v SQL_VARIANT NOT NULL)")
+        .execute(&mut conn)
+        .await?;
+
+    let values = [
+        (1, MssqlVariant::Int(42)),
+        (2, MssqlVariant::NVarChar("hello".into())),
+    ];
+
+    for (id, v) in &values {
+        sqlx::query("INSERT INTO #variants (id, v) VALUES (@p1, @p2)")
+            .bind(id)
+            .bind(v.clone())
+            .execute(&mut conn)
+            .await?;
+    }
+
+    let base_types: Vec<(i32, String)> = sqlx::query_as(
+        "SELECT id, CAST(SQL_VARIANT_PROPERTY(v, 'BaseType') AS NVARCHAR(128)) \
+         FROM #variants ORDER BY id",
+    )
+    .fetch_all(&mut conn)
+    .await?;
+    assert_eq!(
+        base_types,
+        [(1, "int".to_owned()), (2, "nvarchar".to_owned())]
+    );
+
+    let int: MssqlVariant = sqlx::query_scalar(
+        "SELECT CAST(v AS INT) FROM #variants WHERE SQL_VARIANT_PROPERTY(v, 'BaseType') = 'int'",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+    assert_eq!(int, values[0].1);
+
+    let string: MssqlVariant = sqlx::query_scalar(
+        "SELECT CAST(v AS NVARCHAR(4000)) FROM #variants \
+         WHERE SQL_VARIANT_PROPERTY(v, 'BaseType') = 'nvarchar'",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+    assert_eq!(string, values[1].1);
+
+    Ok(())
+}
+
 test_type!(str_nvarchar<String>(Mssql,
     "CAST('this is foo' as NVARCHAR)" == "this is foo",
 ));
@@ -138,6 +414,59 @@ test_type!(null_f64<Option<f64>>(Mssql,
     "CAST(NULL AS FLOAT)" == None::<f64>,
 ));
 
+test_type!(bytes_array<[u8; 4]>(Mssql,
+    "CAST(0xDEADBEEF AS BINARY(4))" == [0xDE_u8, 0xAD, 0xBE, 0xEF],
+));
+
+test_type!(bytes_array_sha256<[u8; 32]>(Mssql,
+    "CAST(HASHBYTES('SHA2_256', 'sqlx') AS BINARY(32))"
+        == [
+            0x28_u8, 0x22, 0x5b, 0x9b, 0x09, 0x30, 0xda, 0xbf, 0x2b, 0x6c, 0x65, 0x67, 0x73, 0xec,
+            0x30, 0x2d, 0xf9, 0xcc, 0x28, 0x0d, 0xa1, 0x7c, 0xb1, 0xd2, 0xb8, 0x2f, 0xdd, 0x76,
+            0xe9, 0xc7, 0x11, 0x05
+        ],
+));
+
+#[sqlx_macros::test]
+async fn it_rejects_byte_array_length_mismatch() -> anyhow::Result<()> {
+    use sqlx::Row;
+
+    let mut conn = sqlx_test::new::<Mssql>().await?;
+
+    let row = sqlx::query("SELECT CAST(0xDEADBEEF AS BINARY(4))")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let err = row.try_get::<[u8; 32], _>(0).unwrap_err();
+    assert!(matches!(err, sqlx::Error::ColumnDecode { .. }), "{err:?}");
+    assert!(err.to_string().contains("expected 32 bytes"), "{err}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_copies_large_binary_value_to_writer() -> anyhow::Result<()> {
+    use sqlx::Row;
+
+    let mut conn = sqlx_test::new::<Mssql>().await?;
+
+    // 1 MiB of 0xAB
+    let row = sqlx::query(
+        "SELECT CAST(REPLICATE(CAST(0xAB AS VARBINARY(MAX)), 1048576) AS VARBINARY(MAX))",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    let mut out = Vec::new();
+    let written = row.try_get_raw(0)?.copy_to(&mut out).await?;
+
+    assert_eq!(written, 1048576);
+    assert_eq!(out.len(), 1048576);
+    assert!(out.iter().all(|&b| b == 0xAB));
+
+    Ok(())
+}
+
 test_type!(null_bytes<Option<Vec<u8>>>(Mssql,
     "CAST(NULL AS VARBINARY(MAX))" == None::<Vec<u8>>,
 ));
@@ -165,6 +494,7 @@ mod chrono {
     type NaiveDateTime = sqlx::types::chrono::NaiveDateTime;
     type DateTimeUtc = sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>;
     type DateTimeFixed = sqlx::types::chrono::DateTime<sqlx::types::chrono::FixedOffset>;
+    type DateTimeLocal = sqlx::types::chrono::DateTime<sqlx::types::chrono::Local>;
     type FixedOffset = sqlx::types::chrono::FixedOffset;
 
     test_type!(chrono_naive_date<NaiveDate>(Mssql,
@@ -172,6 +502,10 @@ mod chrono {
             == NaiveDate::from_ymd_opt(2001, 1, 5).unwrap(),
         "CAST('2050-11-23' AS DATE)"
             == NaiveDate::from_ymd_opt(2050, 11, 23).unwrap(),
+        "CAST('0001-01-01' AS DATE)"
+            == NaiveDate::from_ymd_opt(1, 1, 1).unwrap(),
+        "CAST('9999-12-31' AS DATE)"
+            == NaiveDate::from_ymd_opt(9999, 12, 31).unwrap(),
     ));
 
     test_type!(chrono_naive_time<NaiveTime>(Mssql,
@@ -238,6 +572,69 @@ mod chrono {
                 .unwrap()
                 .and_utc(),
     ));
+
+    // The same instant in whatever time zone the tests run in
+    test_type!(chrono_date_time_local<DateTimeLocal>(Mssql,
+        "CAST('2024-06-15 14:30:00.000 +05:30' AS DATETIMEOFFSET)"
+            == NaiveDate::from_ymd_opt(2024, 6, 15)
+                .unwrap()
+                .and_hms_opt(9, 0, 0)
+                .unwrap()
+                .and_utc()
+                .with_timezone(&sqlx::types::chrono::Local),
+    ));
+
+    #[sqlx_macros::test]
+    async fn it_binds_temporal_values_at_a_chosen_scale() -> anyhow::Result<()> {
+        use sqlx::mssql::MssqlScaled;
+        use sqlx::Executor;
+
+        let mut conn = sqlx_test::new::<Mssql>().await?;
+        conn.execute("CREATE TABLE #scaled (id INT, t TIME(3), dt DATETIME2(3));")
+            .await?;
+
+        let time = NaiveTime::from_hms_nano_opt(12, 0, 0, 123_900_000).unwrap();
+        let date_time = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap().and_time(time);
+
+        sqlx::query("INSERT INTO #scaled VALUES (1, @p1, @p2), (2, @p3, @p4)")
+            .bind(MssqlScaled::new(time).with_scale(3))
+            .bind(MssqlScaled::new(date_time).with_scale(3))
+            .bind(time)
+            .bind(date_time)
+            .execute(&mut conn)
+            .await?;
+
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT CONVERT(NVARCHAR(30), t, 121), CONVERT(NVARCHAR(30), dt, 121) \
+             FROM #scaled ORDER BY id",
+        )
+        .fetch_all(&mut conn)
+        .await?;
+
+        // the scaled values are truncated, the full-precision ones rounded by the server
+        assert_eq!(
+            rows,
+            [
+                (
+                    "12:00:00.123".to_owned(),
+                    "2024-01-02 12:00:00.123".to_owned()
+                ),
+                (
+                    "12:00:00.124".to_owned(),
+                    "2024-01-02 12:00:00.124".to_owned()
+                ),
+            ]
+        );
+
+        let err = sqlx::query("SELECT @p1")
+            .bind(MssqlScaled::new(date_time).with_scale(8))
+            .execute(&mut conn)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, sqlx::Error::Encode(_)), "{err:?}");
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "time")]
@@ -249,6 +646,7 @@ mod time_tests {
     type TimeTime = sqlx::types::time::Time;
     type TimePrimitiveDateTime = sqlx::types::time::PrimitiveDateTime;
     type TimeOffsetDateTime = sqlx::types::time::OffsetDateTime;
+    type TimeDuration = time::Duration;
 
     use time::macros::{date, datetime, time as time_macro};
 
@@ -257,6 +655,10 @@ mod time_tests {
             == date!(2001-01-05),
         "CAST('2050-11-23' AS DATE)"
             == date!(2050-11-23),
+        "CAST('0001-01-01' AS DATE)"
+            == date!(0001-01-01),
+        "CAST('9999-12-31' AS DATE)"
+            == date!(9999-12-31),
     ));
 
     test_type!(time_time<TimeTime>(Mssql,
@@ -266,6 +668,15 @@ mod time_tests {
             == time_macro!(00:00:00),
     ));
 
+    test_type!(time_duration<TimeDuration>(Mssql,
+        "CAST('00:00:00' AS TIME)"
+            == TimeDuration::ZERO,
+        "CAST('01:02:03.4567891' AS TIME)"
+            == TimeDuration::new(3_723, 456_789_100),
+        "CAST('23:59:59.9999999' AS TIME)"
+            == TimeDuration::DAY - TimeDuration::nanoseconds(100),
+    ));
+
     test_type!(time_primitive_date_time<TimePrimitiveDateTime>(Mssql,
         "CAST('2019-01-02 05:10:20' AS DATETIME2)"
             == datetime!(2019-01-02 05:10:20),
@@ -284,12 +695,177 @@ test_type!(rust_decimal<sqlx::types::Decimal>(Mssql,
     "CAST('-1.23' AS DECIMAL(10,2))" == sqlx::types::Decimal::new(-123, 2),
 ));
 
+#[cfg(feature = "rust_decimal")]
+#[sqlx_macros::test]
+async fn it_decodes_wide_decimals_into_rust_decimal() -> anyhow::Result<()> {
+    use sqlx::types::Decimal;
+
+    let mut conn = sqlx_test::new::<Mssql>().await?;
+
+    let half: Decimal = sqlx::query_scalar("SELECT CAST('0.5' AS DECIMAL(38, 30))")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(half, Decimal::new(5, 1));
+
+    let err = sqlx::query_scalar::<_, Decimal>("SELECT CAST(REPLICATE('9', 38) AS DECIMAL(38, 0))")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::ColumnDecode { .. }), "{err:?}");
+    assert!(
+        err.to_string().contains("exceeds rust_decimal precision"),
+        "{err}"
+    );
+
+    Ok(())
+}
+
 #[cfg(feature = "rust_decimal")]
 test_type!(rust_decimal_money<sqlx::types::Decimal>(Mssql,
     "CAST(1234.5678 AS MONEY)" == sqlx::types::Decimal::new(12345678, 4),
     "CAST(0 AS MONEY)" == sqlx::types::Decimal::ZERO,
 ));
 
+#[cfg(feature = "rust_decimal")]
+#[sqlx_macros::test]
+async fn it_rounds_f64_to_decimal_half_to_even() -> anyhow::Result<()> {
+    use sqlx::mssql::MssqlFloatDecimal;
+    use sqlx::types::Decimal;
+
+    let mut conn = sqlx_test::new::<Mssql>().await?;
+
+    let values: (Decimal, Decimal) =
+        sqlx::query_as("SELECT CAST(@p1 AS DECIMAL(10, 2)), CAST(@p2 AS DECIMAL(10, 2))")
+            .bind(MssqlFloatDecimal::new(2.675, 2))
+            .bind(MssqlFloatDecimal::new(0.125, 2))
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(values, (Decimal::new(268, 2), Decimal::new(12, 2)));
+
+    let err = sqlx::query("SELECT CAST(@p1 AS DECIMAL(10, 2))")
+        .bind(MssqlFloatDecimal::new(f64::NAN, 2))
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::Encode(_)), "{err:?}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rejects_non_finite_floats() -> anyhow::Result<()> {
+    let mut conn = sqlx_test::new::<Mssql>().await?;
+
+    for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        let err = sqlx::query("SELECT @p1")
+            .bind(value)
+            .execute(&mut conn)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, sqlx::Error::Encode(_)), "{value}: {err:?}");
+    }
+
+    let err = sqlx::query("SELECT @p1")
+        .bind(f32::NAN)
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::Encode(_)), "{err:?}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_binds_bool_as_bit() -> anyhow::Result<()> {
+    let mut conn = sqlx_test::new::<Mssql>().await?;
+
+    // Bound as BIT rather than an integer, so comparisons against a BIT column need no
+    // implicit conversion of the column and can use its index.
+    let base_type: String =
+        sqlx::query_scalar("SELECT CAST(SQL_VARIANT_PROPERTY(@p1, 'BaseType') AS NVARCHAR(128))")
+            .bind(true)
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!(base_type, "bit");
+
+    sqlx::query(
+        "CREATE TABLE #bool_flags (id INT NOT NULL PRIMARY KEY, done BIT NOT NULL); \
+         CREATE INDEX ix_bool_flags_done ON #bool_flags (done); \
+         INSERT INTO #bool_flags (id, done) VALUES (1, 1), (2, 0), (3, 1);",
+    )
+    .execute(&mut conn)
+    .await?;
+
+    for (flag, expected) in [(true, vec![1, 3]), (false, vec![2])] {
+        let ids: Vec<i32> =
+            sqlx::query_scalar("SELECT id FROM #bool_flags WHERE done = @p1 ORDER BY id")
+                .bind(flag)
+                .fetch_all(&mut conn)
+                .await?;
+        assert_eq!(ids, expected, "{flag}");
+    }
+
+    Ok(())
+}
+
+test_type!(duration_nanos<sqlx::mssql::MssqlDurationNanos>(Mssql,
+    "CAST(0 AS BIGINT)" == sqlx::mssql::MssqlDurationNanos(std::time::Duration::ZERO),
+    "CAST(259200000000005 AS BIGINT)"
+        == sqlx::mssql::MssqlDurationNanos(std::time::Duration::new(259_200, 5)),
+));
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+test_type!(date_days<sqlx::mssql::MssqlDateDays>(Mssql,
+    "CAST('0001-01-01' AS DATE)" == sqlx::mssql::MssqlDateDays(0),
+    "CAST('1900-01-01' AS DATE)" == sqlx::mssql::MssqlDateDays(693_595),
+    "CAST('2000-01-01' AS DATE)" == sqlx::mssql::MssqlDateDays(730_119),
+    "CAST('9999-12-31' AS DATE)" == sqlx::mssql::MssqlDateDays(3_652_058),
+));
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+#[sqlx_macros::test]
+async fn it_round_trips_date_days_through_a_date_column() -> anyhow::Result<()> {
+    use sqlx::mssql::MssqlDateDays;
+
+    let mut conn = sqlx_test::new::<Mssql>().await?;
+
+    sqlx::query("CREATE TABLE #date_days (id INT NOT NULL, d DATE NOT NULL)")
+        .execute(&mut conn)
+        .await?;
+
+    for (id, days) in [(1, 0), (2, 738_885), (3, 3_652_058)] {
+        sqlx::query("INSERT INTO #date_days (id, d) VALUES (@p1, @p2)")
+            .bind(id)
+            .bind(MssqlDateDays(days))
+            .execute(&mut conn)
+            .await?;
+    }
+
+    let rows: Vec<(MssqlDateDays, String)> =
+        sqlx::query_as("SELECT d, CONVERT(NVARCHAR(10), d, 23) FROM #date_days ORDER BY id")
+            .fetch_all(&mut conn)
+            .await?;
+
+    assert_eq!(
+        rows,
+        [
+            (MssqlDateDays(0), "0001-01-01".to_owned()),
+            (MssqlDateDays(738_885), "2024-01-01".to_owned()),
+            (MssqlDateDays(3_652_058), "9999-12-31".to_owned()),
+        ]
+    );
+
+    let err = sqlx::query("INSERT INTO #date_days (id, d) VALUES (4, @p1)")
+        .bind(MssqlDateDays(-1))
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::Encode(_)), "{err}");
+
+    Ok(())
+}
+
 #[cfg(feature = "bigdecimal")]
 test_type!(bigdecimal<sqlx::types::BigDecimal>(Mssql,
     "CAST('0' AS DECIMAL(10,2))" == "0.00".parse::<sqlx::types::BigDecimal>().unwrap(),
@@ -324,3 +900,20 @@ mod json_tests {
         "CAST('null' AS NVARCHAR(MAX))" == serde_json::Value::Null,
     ));
 }
+
+#[sqlx_macros::test]
+async fn it_strips_a_leading_bom_only_when_asked() -> anyhow::Result<()> {
+    use sqlx::mssql::MssqlBomStrippedStr;
+
+    let mut conn = sqlx_test::new::<Mssql>().await?;
+
+    let (faithful, stripped): (String, MssqlBomStrippedStr) =
+        sqlx::query_as("SELECT NCHAR(65279) + N'abc', NCHAR(65279) + N'abc'")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(faithful, "\u{feff}abc");
+    assert_eq!(&*stripped, "abc");
+
+    Ok(())
+}